@@ -0,0 +1,61 @@
+"""
+Projection benchmark - compares full reads against projected reads of wide documents
+V5 only: relies on the `fields` keyword of `read_item`
+"""
+import sys
+import time
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent))
+
+from benchmark_tests import get_client, setup_database_and_container, cleanup_database
+
+DB_NAME = "projection_benchmark_db"
+CONTAINER_NAME = "projection_benchmark_container"
+
+
+def make_wide_document(i, num_fields=500):
+    """Build a document with many top-level fields"""
+    doc = {"id": f"wide_{i}", "pk": f"partition_{i % 10}", "name": f"Wide {i}"}
+    for f in range(num_fields):
+        doc[f"field_{f}"] = {"value": f, "text": "x" * 32, "tags": ["a", "b", "c"]}
+    return doc
+
+
+def benchmark_reads(container, num_docs, fields=None):
+    """Read every document, optionally projecting to `fields`"""
+    kwargs = {"fields": fields} if fields else {}
+    cpu_start = time.process_time()
+    start = time.time()
+    
+    for i in range(num_docs):
+        container.read_item(item=f"wide_{i}", partition_key=f"partition_{i % 10}", **kwargs)
+    
+    return {
+        "total_time": time.time() - start,
+        "cpu_time": time.process_time() - cpu_start,
+        "num_reads": num_docs,
+    }
+
+
+def run_projection_benchmark(num_docs=200):
+    client = get_client()
+    database, container = setup_database_and_container(client, DB_NAME, CONTAINER_NAME)
+    
+    try:
+        for i in range(num_docs):
+            container.upsert_item(body=make_wide_document(i))
+        
+        full = benchmark_reads(container, num_docs)
+        projected = benchmark_reads(container, num_docs, fields=["id", "name"])
+        
+        print(f"Full reads:      {full['total_time']:.3f}s wall, {full['cpu_time']:.3f}s CPU")
+        print(f"Projected reads: {projected['total_time']:.3f}s wall, {projected['cpu_time']:.3f}s CPU")
+        if projected["cpu_time"] > 0:
+            print(f"CPU savings:     {full['cpu_time'] / projected['cpu_time']:.2f}x")
+    finally:
+        cleanup_database(client, DB_NAME)
+
+
+if __name__ == "__main__":
+    run_projection_benchmark()
@@ -0,0 +1,62 @@
+"""
+Query benchmark - times converting a large query result into Python objects
+V5 only: run it before and after a change to the response conversion to compare
+"""
+import sys
+import time
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent))
+
+from benchmark_tests import get_client, setup_database_and_container, cleanup_database
+
+DB_NAME = "query_benchmark_db"
+CONTAINER_NAME = "query_benchmark_container"
+
+
+def make_row(i):
+    """Build a row with nested objects, arrays and mixed number types"""
+    return {
+        "id": f"row_{i}",
+        "pk": "rows",
+        "name": f"Row {i}",
+        "price": i * 1.25,
+        "quantity": i,
+        "active": i % 2 == 0,
+        "address": {"street": f"{i} Main St", "city": "Seattle", "zip": None},
+        "tags": ["a", "b", "c"],
+    }
+
+
+def benchmark_query(container, runs):
+    """Run the full single-partition query `runs` times"""
+    cpu_start = time.process_time()
+    start = time.time()
+    
+    for _ in range(runs):
+        rows = container.query_items("SELECT * FROM c", partition_key="rows")
+    
+    return {
+        "total_time": time.time() - start,
+        "cpu_time": time.process_time() - cpu_start,
+        "num_rows": len(rows),
+    }
+
+
+def run_query_benchmark(num_rows=10_000, runs=5):
+    client = get_client()
+    database, container = setup_database_and_container(client, DB_NAME, CONTAINER_NAME)
+    
+    try:
+        for i in range(num_rows):
+            container.upsert_item(body=make_row(i))
+        
+        result = benchmark_query(container, runs)
+        print(f"Rows per query: {result['num_rows']}")
+        print(f"Per query:      {result['total_time'] / runs:.3f}s wall, {result['cpu_time'] / runs:.3f}s CPU")
+    finally:
+        cleanup_database(client, DB_NAME)
+
+
+if __name__ == "__main__":
+    run_query_benchmark()
@@ -1,41 +1,200 @@
 """Azure Cosmos DB Python SDK v5 - Rust-powered native extension."""
 
+from typing import Optional
+
 from azure.cosmos._rust import (
     CosmosClient as _RustCosmosClient,
     DatabaseClient as _RustDatabaseClient,
     ContainerClient as _RustContainerClient,
+    ContainerProperties,
+    DatabaseProperties,
+    QueryBuilder as _RustQueryBuilder,
+    QueryCursor,
+    QueryIterator,
+    QueryPage,
+    merge_session_tokens,
 )
 from azure.cosmos.exceptions import (
     CosmosHttpResponseError,
     CosmosResourceNotFoundError,
     CosmosResourceExistsError,
     CosmosAccessConditionFailedError,
+    CosmosServiceUnavailableError,
+    CosmosInvalidContinuationTokenError,
+    CosmosResponseTooLargeError,
+    CosmosDeserializationError,
+    CosmosTimeoutError,
+    CosmosReadOnlyError,
 )
 
 __all__ = [
     "CosmosClient",
     "DatabaseProxy",
     "ContainerProxy",
+    "ContainerProperties",
+    "DatabaseProperties",
+    "QueryBuilder",
+    "QueryCursor",
+    "QueryIterator",
+    "QueryPage",
+    "merge_session_tokens",
     "CosmosHttpResponseError",
     "CosmosResourceNotFoundError",
     "CosmosResourceExistsError",
     "CosmosAccessConditionFailedError",
+    "CosmosServiceUnavailableError",
+    "CosmosInvalidContinuationTokenError",
+    "CosmosResponseTooLargeError",
+    "CosmosDeserializationError",
+    "CosmosTimeoutError",
+    "CosmosReadOnlyError",
 ]
 
 __version__ = "5.0.0"
 
 
+class QueryBuilder(_RustQueryBuilder):
+    """Fluent builder for parameterized queries.
+    
+    Supports the subset ``SELECT <fields> FROM <alias> WHERE ... ORDER BY ... OFFSET n LIMIT m``.
+    Conditions from repeated :meth:`where` calls are combined with AND. Joins, aggregates,
+    GROUP BY and subqueries are not covered; write those as SQL with the ``parameters``
+    keyword of ``query_items``.
+    
+    :param str alias: The alias of the container in the query. Defaults to ``"c"``.
+    """
+    
+    def where(self, condition: str, **parameters):
+        """Add a filter condition, combined with earlier conditions by AND.
+        
+        :param str condition: The condition, e.g. ``"c.age > @min"``
+        :param parameters: Values bound to the ``@name`` parameters used in the condition
+        :return: This builder
+        :rtype: QueryBuilder
+        """
+        return self.where_(condition, **parameters)
+
+
 class CosmosClient:
     """A client for interacting with Azure Cosmos DB.
     
     :param str url: The URL of the Cosmos DB account
-    :param credential: The credential for authentication (key string or credential object)
+    :param credential: The account key string, or an Azure AD credential such as
+        ``azure.identity.DefaultAzureCredential`` or ``ManagedIdentityCredential``: any object
+        with a synchronous ``get_token(*scopes)`` method. Its tokens are requested when needed
+        and renewed before they expire. Credentials from ``azure.identity.aio`` are not
+        supported; pass the synchronous credential to the async client too.
     :param kwargs: Additional keyword arguments
+    :keyword bool exclude_none_on_write: Recursively drop keys whose value is None from item
+        bodies before they are written. Defaults to False.
+    :keyword int capture_requests: Keep the last N outgoing requests (method, url, headers and
+        body) for :meth:`get_last_requests`. Disabled by default.
+    :keyword bool track_metrics: Accumulate the RU charge, request counts by operation and
+        throttled (429) requests of every request the client sends, for
+        :meth:`get_metrics`. Disabled by default.
+    :keyword str partition_key_mismatch: What to do when a ``partition_key`` keyword passed to
+        an item write differs from the partition key value in the body: ``"ignore"`` (default)
+        sends the keyword value unchecked, ``"raise"`` raises ``ValueError``, ``"warn"`` emits
+        a ``UserWarning`` and writes under the keyword value. Checking reads the container's
+        partition key paths once per container client.
+    :keyword str initial_session_token: Session token obtained from another client (for
+        example passed along by an upstream service). It is sent with every request, merged
+        with any per-operation ``session_token`` by taking the highest LSN per partition key
+        range, so reads observe that client's writes. Only effective with Session consistency.
+    :keyword bool connection_verify: Verify the service's TLS certificate. Defaults to True.
+    :keyword connection_timeout: Timeout for establishing connections, in seconds or as a
+        ``datetime.timedelta``.
+    :keyword bool typed_properties: Return :class:`DatabaseProperties` and
+        :class:`ContainerProperties` objects instead of dicts from ``list_databases``,
+        ``list_containers`` and the database and container ``read`` methods. Their typed
+        accessors catch misspelled keys as ``AttributeError``; ``as_dict()`` returns the dict.
+        Defaults to False.
+    :keyword operation_timeout: Bound, in seconds or as a ``datetime.timedelta``, on each
+        operation, including its retries. An operation exceeding it is abandoned and raises
+        :class:`CosmosTimeoutError`, whose ``operation`` attribute names it; a write already
+        sent may still be applied by the service. Each operation can
+        override it with its own ``operation_timeout`` keyword. The runtime threads are named
+        ``cosmos-<component>-worker-<n>`` so a hung operation shows up in thread dumps.
+    :keyword str id_field: Item property that holds the item's id in application code, such as
+        ``"_id"`` for data migrated from MongoDB-style schemas. Written items have it moved to
+        ``id``, and items returned by reads and queries have ``id`` moved back to it. The
+        service still only knows ``id``: it must be a unique string within the partition,
+        queries must refer to ``c.id``, and a partition key path of ``/id`` reads the mapped
+        value. An item carrying both keys must give them the same value.
+    :keyword int circuit_breaker_threshold: Enable a circuit breaker that opens after this
+        many consecutive 429 or 503 responses within ``circuit_breaker_window`` (default 10
+        seconds). While open, requests fail immediately with
+        :class:`CosmosServiceUnavailableError` instead of adding load; after
+        ``circuit_breaker_cool_down`` (default 30 seconds) one probe request is let through,
+        and its success closes the breaker while another 429 or 503 reopens it. Every attempt
+        is counted, including the SDK's own retries. See :attr:`circuit_breaker_state`.
+        Disabled by default.
+    :keyword bool read_only: Reject every operation that creates, changes or deletes a
+        resource (item writes and deletes, ``patch_item``, creating and deleting containers
+        and databases, ``run_setup``) with :class:`CosmosReadOnlyError` before anything is
+        sent. Reads and queries are unaffected. A client-side safety rail for services that
+        must never write, independent of the credential's permissions; it complements rather
+        than replaces a read-only key or RBAC role. Defaults to False.
+    :keyword int max_response_size_bytes: Largest response body to accept, in bytes. A
+        response declaring a larger body, such as a runaway document or query page, fails
+        with :class:`CosmosResponseTooLargeError` before its body is read; one without a
+        declared size, such as a chunked response, fails as soon as more than the limit has
+        been read, so it is never fully buffered. The service has already executed the request and charged for it, and a
+        write may have been applied. Unlimited by default.
+    :keyword bool enable_query_cache: Serve repeated ``query_items`` and
+        ``query_items_columns`` calls from an in-process cache of their deserialized results,
+        keyed by container, SQL text, parameters and the options that can change the
+        results (``partition_key`` and the container's ``partition_key_type``, ``fields``,
+        ``consistency_level``, ``session_token``, ``enable_scan_in_query`` and
+        ``enable_low_precision_order_by``), without a network call. Writes, including this
+        client's own, do not invalidate entries, so a hit may miss changes made up to
+        ``query_cache_ttl`` ago: cached results bypass the consistency level and session
+        guarantees of the account. Up to 1024 results are cached, evicting the oldest. Meant
+        for read-heavy dashboards that tolerate staleness. Hits and misses are reported by
+        :meth:`get_metrics`. Disabled by default.
+    :keyword query_cache_ttl: How long, in seconds or as a ``datetime.timedelta``, a cached
+        query result is served before the query is sent again. Defaults to 60 seconds.
     """
     
     def __init__(self, url: str, credential=None, **kwargs):
         self._client = _RustCosmosClient(url, credential, **kwargs)
     
+    @classmethod
+    def from_emulator(cls, endpoint: str = "https://localhost:8081", **kwargs) -> "CosmosClient":
+        """Create a client for the local Cosmos DB emulator.
+        
+        Uses the emulator's well-known key, disables TLS verification for its self-signed
+        certificate and sets a 10 second connection timeout unless ``connection_timeout`` is
+        given. Other keyword arguments are passed through as for the constructor.
+        
+        :param str endpoint: The emulator endpoint; must be a localhost or loopback address
+        :raises ValueError: If the endpoint is not local
+        :return: A client connected to the emulator
+        :rtype: CosmosClient
+        """
+        client = cls.__new__(cls)
+        client._client = _RustCosmosClient.from_emulator(endpoint, **kwargs)
+        return client
+    
+    @classmethod
+    def from_connection_string(cls, conn_str: str, **kwargs) -> "CosmosClient":
+        """Create a client from an account connection string.
+        
+        The ``AccountEndpoint`` and ``AccountKey`` segments of a string such as
+        ``"AccountEndpoint=https://<account>.documents.azure.com:443/;AccountKey=<key>;"`` are
+        used, matched case-insensitively; whitespace, a trailing ``;`` and other segments are
+        ignored. Keyword arguments are passed through as for the constructor.
+        
+        :param str conn_str: The connection string, as shown in the Azure portal
+        :raises ValueError: If ``AccountEndpoint`` or ``AccountKey`` is missing; the message
+            names the missing field
+        :return: A client authenticated with the account key
+        :rtype: CosmosClient
+        """
+        client = cls.__new__(cls)
+        client._client = _RustCosmosClient.from_connection_string(conn_str, **kwargs)
+        return client
+    
     def get_database_client(self, database: str) -> "DatabaseProxy":
         """Get a database client.
         
@@ -50,6 +209,11 @@ class CosmosClient:
         """Create a new database.
         
         :param str id: The database ID
+        :keyword int offer_throughput: Manual throughput (RU/s) to provision for the database
+        :keyword bool populate_properties: Return a ``(client, properties)`` tuple, where
+            ``properties`` holds the server-assigned properties (``_rid``, ``_etag``, ...) and,
+            when ``offer_throughput`` was given, the ``offer_throughput`` read back from the
+            created offer (None if no offer exists). Defaults to False.
         :return: Database properties
         :rtype: dict
         """
@@ -65,10 +229,71 @@ class CosmosClient:
     def list_databases(self, **kwargs) -> list:
         """List all databases.
         
+        Each entry holds the database's properties as stored by the service: its plain ``id``
+        plus system properties such as ``_rid``, ``_self`` and ``_etag``.
+        
         :return: List of database properties
-        :rtype: list[dict]
+        :rtype: list[dict] or list[DatabaseProperties]
         """
         return self._client.list_databases(**kwargs)
+    
+    def get_last_requests(self, n: int = None) -> list:
+        """Get the most recently sent requests, for reproducing issues.
+        
+        Only available when the client was created with ``capture_requests=N``. Each attempt
+        of a retried request is captured separately. The ``authorization`` header is redacted.
+        
+        :param int n: Maximum number of requests to return (defaults to all captured)
+        :return: Requests as dicts with method, url, headers and body, oldest first
+        :rtype: list[dict]
+        """
+        return self._client.get_last_requests(n)
+    
+    def get_metrics(self) -> dict:
+        """Get the request totals accumulated since the client was created.
+        
+        Request totals are only tracked when the client was created with
+        ``track_metrics=True``. Every attempt is counted, so a request retried after
+        throttling counts once per attempt. Operation names are derived from the HTTP
+        request, e.g. ``read_item``, ``query_items`` or ``create_database``. Query cache
+        hits and misses are always reported when the client has ``enable_query_cache=True``.
+        
+        :return: Dict with ``total_request_charge``, ``request_count``,
+            ``requests_by_operation`` and ``throttled_requests`` when ``track_metrics=True``,
+            plus ``query_cache_hits`` and ``query_cache_misses`` when
+            ``enable_query_cache=True``
+        :raises ValueError: If neither ``track_metrics`` nor ``enable_query_cache`` is enabled
+        :rtype: dict
+        """
+        return self._client.get_metrics()
+    
+    def in_flight_count(self) -> int:
+        """Get the number of operations currently running on this client.
+        
+        Item, query, container, database and account operations are counted, whether
+        called from a thread or through the async client.
+        
+        :return: The number of running operations
+        :rtype: int
+        """
+        return self._client.in_flight_count()
+    
+    def cancel_all(self) -> None:
+        """Cancel every operation currently running on this client, e.g. to drain on shutdown.
+        
+        Each cancelled operation raises ``asyncio.CancelledError``; operations started after
+        the call are not affected. Cancellation drops the request on the client side only: a
+        write whose request was already sent may still be applied by the service.
+        """
+        self._client.cancel_all()
+    
+    @property
+    def circuit_breaker_state(self) -> Optional[str]:
+        """State of the circuit breaker: ``"closed"``, ``"open"`` or ``"half_open"``.
+        
+        ``None`` unless the client was created with ``circuit_breaker_threshold``.
+        """
+        return self._client.circuit_breaker_state
 
 
 class DatabaseProxy:
@@ -85,25 +310,51 @@ class DatabaseProxy:
         """Get the database ID."""
         return self._client.id
     
-    def get_container_client(self, container: str) -> "ContainerProxy":
+    def get_container_client(
+        self, container: str, partition_key_type: str = None, validate: bool = False
+    ) -> "ContainerProxy":
         """Get a container client.
         
         :param str container: The container ID
-        :return: A container client
+        :param str partition_key_type: The type partition key values are stored as, "string"
+            or "number". When set, partition key values of the other type are coerced with a
+            warning, e.g. ``partition_key="42"`` finds items stored under ``42``. Values that
+            cannot be coerced raise ValueError.
+        :param bool validate: Read the container's properties now, so that a missing
+            container raises ``CosmosResourceNotFoundError`` here instead of on the first
+            operation. Costs one metadata read. Defaults to False.
+        :return: A container client. The underlying client is cached per container id and
+            ``partition_key_type``, so repeated calls share its connection and metadata. Up to
+            256 clients are cached per database, evicting the least recently used.
         :rtype: ContainerProxy
         """
-        container_client = self._client.get_container_client(container)
+        container_client = self._client.get_container_client(
+            container, partition_key_type, validate
+        )
         return ContainerProxy(container_client)
     
-    def create_container(self, id: str, partition_key: dict, **kwargs) -> dict:
+    def create_container(self, id: str, partition_key, **kwargs) -> "ContainerProxy":
         """Create a new container.
         
         :param str id: The container ID
-        :param dict partition_key: Partition key configuration with 'paths' key
-        :return: Container properties
-        :rtype: dict
+        :param partition_key: The partition key path, e.g. ``"/category"``, or a partition key
+            configuration dict with a 'paths' key. Up to three paths define a hierarchical
+            partition key. Paths must start with ``/``.
+        :type partition_key: str or dict
+        :keyword int default_ttl: Time to live of items in seconds. ``-1``, which enables TTL
+            without expiring items by default, raises ``NotImplementedError`` because the Rust
+            SDK cannot send a negative TTL yet. Zero and other negative values raise
+            ``ValueError``. By default items never expire.
+        :keyword int offer_throughput: Manual throughput (RU/s) to provision for the container.
+            By default the container uses the database's shared throughput, if any.
+        :keyword geospatial_config: ``"Geography"`` or ``"Geometry"``, or a dict with that
+            ``type``, selecting how spatial functions such as ``ST_DISTANCE`` interpret
+            coordinates. Defaults to the service default, ``Geography``. Other values raise
+            ``ValueError``.
+        :return: A client for the new container
+        :rtype: ContainerProxy
         """
-        return self._client.create_container(id, partition_key, **kwargs)
+        return ContainerProxy(self._client.create_container(id, partition_key, **kwargs))
     
     def delete_container(self, container: str, **kwargs) -> None:
         """Delete a container.
@@ -116,18 +367,69 @@ class DatabaseProxy:
         """List all containers in this database.
         
         :return: List of container properties
-        :rtype: list[dict]
+        :rtype: list[dict] or list[ContainerProperties]
         """
         return self._client.list_containers(**kwargs)
     
+    def query_containers(self, query, **kwargs) -> list:
+        """Query the containers in this database.
+        
+        The query runs over the container resources, e.g.
+        ``"SELECT * FROM c WHERE STARTSWITH(c.id, @prefix)"``. Each result has the same shape
+        as a ``list_containers`` entry, including ``id`` and ``partitionKey``.
+        
+        :param query: The SQL query string or a ``QueryBuilder``
+        :keyword list parameters: Query parameters as ``{"name": "@x", "value": ...}`` dicts
+        :return: List of container properties
+        :rtype: list[dict] or list[ContainerProperties]
+        """
+        return self._client.query_containers(query, **kwargs)
+    
+    def list_offers(self, **kwargs) -> list:
+        """List the provisioned throughput of this database and each of its containers.
+        
+        Offers are read concurrently, one request per resource, instead of one
+        ``read_throughput`` call per container. Each offer is a dict with ``resource``
+        (``"database"`` or ``"container"``), ``id``, ``mode`` (``"manual"`` or ``"autoscale"``)
+        and ``throughput``: the manual RU/s, or the maximum RU/s for autoscale. Resources with
+        no offer of their own, such as containers sharing the database's throughput, are left
+        out, so ``sum(o["throughput"] for o in database.list_offers())`` is the RU/s
+        provisioned under the database. Serverless accounts have no offers and return an
+        empty list.
+        
+        :return: The offers
+        :rtype: list[dict]
+        """
+        return self._client.list_offers(**kwargs)
+    
     def read(self, **kwargs) -> dict:
         """Read database properties.
         
         :return: Database properties
-        :rtype: dict
+        :rtype: dict or DatabaseProperties
         """
         return self._client.read(**kwargs)
     
+    def run_setup(self, steps: list) -> int:
+        """Run a sequence of setup steps in order, e.g. for test fixtures.
+        
+        Each step is a dict with a ``type`` and that operation's parameters:
+        
+        - ``{"type": "create_container", "id": ..., "partition_key": {"paths": [...]}}``
+        - ``{"type": "delete_container", "id": ...}``
+        - ``{"type": "create_item" | "upsert_item", "container": ..., "body": {...}}``,
+          optionally with ``partition_key``
+        
+        Steps are not transactional. Execution stops at the first failing step, raising a
+        RuntimeError naming its index and type, with the original error as ``__cause__``.
+        Steps that already ran are not undone; cleanup is the caller's responsibility.
+        
+        :param list[dict] steps: The steps to run
+        :return: The number of steps run
+        :rtype: int
+        """
+        return self._client.run_setup(steps)
+    
     def delete(self, **kwargs) -> None:
         """Delete this database."""
         return self._client.delete(**kwargs)
@@ -136,6 +438,83 @@ class DatabaseProxy:
 class ContainerProxy:
     """A proxy to interact with a specific container.
     
+    Item bodies are converted to JSON natively. Tuples are stored as arrays, and sets and
+    frozensets as sorted arrays: they are read back as lists, not as sets. ``enum.Enum``
+    members, as values or dict keys, are stored as their ``.value`` and read back as that
+    plain value; convert them back with ``MyEnum(value)``.
+    Object keys are always written in sorted order, at every nesting level, so the same
+    logical document produces the same request body bytes whatever the order of its keys
+    in Python, e.g. for content hashing.
+    
+    Item ids cannot contain ``/``, ``\\``, ``#`` or ``?``, nor end with a space. Writes of
+    such ids raise ``ValueError`` naming the character before any request is sent.
+    
+    Partition key values may be ``str``, ``int``, ``float`` or ``uuid.UUID``; a UUID matches
+    items whose partition key is its canonical string form, ``str(uuid)``.
+    ``None`` addresses the *undefined* partition: items that have no value at the partition
+    key path, as stored by legacy data or with ``create_item(body, partition_key=None)``.
+    This is distinct from an empty string, which is an ordinary key value.
+    Hierarchical keys are lists of at most one value per path, in path order, and components
+    may mix types, e.g. ``["tenant-1", 42]``. A list key is checked against the container's
+    partition key ``kind``, read once per client: more components than the container has
+    paths, or an empty list, raises ``ValueError`` before a request is sent. Single values
+    do not read the container: once its kind is known, a single value for a ``MultiHash``
+    container also raises ``ValueError``; before that the service rejects the request.
+    
+    Item and query operations accept these per-operation keyword arguments:
+    
+    :keyword int throughput_bucket: Throughput bucket (1-5) the request is charged against, so
+        that workloads in different buckets cannot starve each other. Requires throughput
+        buckets to be enabled on the account.
+    :keyword bool enable_scan_in_query: Allow a query to scan when no index supports its
+        filter, instead of being rejected. Scans read every document in the scoped
+        partitions, so the RU charge grows with the data size rather than the result size.
+    :keyword bool enable_low_precision_order_by: Let the service use the range index's
+        lower-precision numeric values when evaluating ``ORDER BY``, which costs fewer RU on
+        large sorts. Numbers that differ only beyond that precision may come back out of
+        order, so results are exactly sorted only for values the index distinguishes. Sent as
+        ``x-ms-documentdb-query-enable-low-precision-order-by``; values other than True or
+        False raise ``ValueError``. Defaults to False (exact ordering).
+    :keyword bool parse_timestamps: On reads and queries, add a ``_ts_datetime`` key holding
+        the item's ``_ts`` (last-modified epoch seconds) as a UTC ``datetime``. The raw ``_ts``
+        is kept and the stored document is unchanged. Defaults to False.
+    :keyword callable response_hook: Called with a diagnostics dict after each successful
+        response of ``create_item``, ``read_item``, ``upsert_item``, ``replace_item``,
+        ``delete_item`` and of every page fetched by ``query_items_paged`` /
+        ``query_items_page``. The dict has ``status_code`` (None for query pages),
+        ``request_charge``, ``activity_id``, ``session_token``, ``physical_partition_id``
+        (``x-ms-cosmos-physical-partition-id``) and ``partition_key_range_id``
+        (``x-ms-documentdb-partitionkeyrangeid``). The service only returns the partition
+        attribution headers for some operations and API versions, so those keys may be None.
+        ``degraded`` is True when a read was answered at ``fallback_consistency``.
+        Normalized RU consumption is not reported per request; divide ``request_charge`` by the
+        RU/s provisioned per physical partition to approximate it.
+    :keyword bool enable_diagnostics: Make ``create_item``, ``read_item``, ``upsert_item``,
+        ``replace_item`` and ``delete_item`` return ``(result, diagnostics)`` instead of the
+        result alone (``delete_item``'s result is None). ``diagnostics`` holds the same keys
+        as the ``response_hook`` dict except ``correlation_id`` and ``degraded``, notably
+        ``request_charge``, ``activity_id`` and ``status_code``. They are all None for an
+        ``idempotent`` create that returned the stored item, as no write response exists.
+        Defaults to False.
+    :keyword str correlation_id: Caller-defined id for end-to-end tracing. It is sent as the
+        ``x-ms-correlation-id`` header (ignored by the service), included in the
+        ``response_hook`` diagnostics and set as the ``correlation_id`` attribute of any
+        ``CosmosHttpResponseError`` the operation raises, next to the service's
+        ``activity_id``. It does not change how the operation runs.
+    :keyword str session_token: Session token to read at, such as one returned by another
+        client. Merged with the client's ``initial_session_token`` if both are set; use
+        :func:`merge_session_tokens` to combine tokens yourself.
+    :keyword str consistency_level: Consistency for this operation, one of ``"Strong"``,
+        ``"BoundedStaleness"``, ``"Session"``, ``"ConsistentPrefix"`` or ``"Eventual"``
+        (case-insensitive; other values raise ``ValueError``). It is sent as the
+        ``x-ms-consistency-level`` header on reads and queries, where the service only allows
+        relaxing the account's default level; requesting a stronger level is rejected with a
+        400. Strong and BoundedStaleness reads are served by two replicas and cost about twice
+        the RU of the weaker levels. On ``create_item``, ``upsert_item``, ``replace_item`` and
+        ``delete_item`` it is validated and ignored: Cosmos DB has no per-request write
+        concern, and every write is acknowledged only after a majority quorum of replicas in
+        the region has committed it (and, on strong accounts, after the other regions have).
+    
     :param _RustContainerClient client: The underlying Rust container client
     """
     
@@ -147,11 +526,68 @@ class ContainerProxy:
         """Get the container ID."""
         return self._client.id
     
+    def resolve_partition_key(self, body: dict, **kwargs):
+        """Resolve the partition key a write of ``body`` would use, without writing it.
+        
+        Runs the same extraction as :meth:`create_item`, including the ``partition_key``
+        keyword, its mismatch check and ``id_field``, and raises the same errors, so items can
+        be checked before a large load. Reading the container's partition key definition,
+        once per container client, is the only request it may send.
+        
+        :param dict body: The item, or a JSON string with a ``partition_key`` keyword
+        :return: The partition key value, or a list of the components for a hierarchical key
+        :raises ValueError: The key cannot be resolved or does not fit the container
+        """
+        return self._client.resolve_partition_key(body, **kwargs)
+    
+    def get_item_partition_key(self, item: dict):
+        """Return the partition key an item is stored under.
+        
+        The value is read from the item's fields at the container's partition key paths, so
+        items returned by reads and queries show where they are placed, e.g. to verify data
+        placement during a migration. Unlike :meth:`resolve_partition_key`, nothing is
+        validated. Reading the container's partition key definition, once per container
+        client, is the only request it may send.
+        
+        :param dict item: The item, as returned by a read or query
+        :return: The partition key value, or a list of the components for a hierarchical key.
+            A path the item lacks gives None, the undefined partition.
+        """
+        return self._client.get_item_partition_key(item)
+    
     def create_item(self, body: dict, **kwargs) -> dict:
         """Create a new item.
         
+        A ``partition_key`` keyword always wins; ``partition_key=None`` writes to the
+        undefined partition. Without it, the partition key is read from the body field at the
+        container's key path, and for a hierarchical key the components are built from the
+        fields at the key paths, in order; a missing component raises ``ValueError`` naming
+        its path. For hierarchical keys, pass ``partition_key`` as a list of the component
+        values. The same applies to :meth:`upsert_item` and :meth:`replace_item`.
+        
         :param dict body: The item to create
-        :return: The created item
+        :keyword bool auto_partition_key: When the body has no value at the key path, guess
+            the partition key from common field names (``id``, ``category``, ``partitionKey``,
+            ``pk``, ``type``, ``tenantId``) instead of raising ``ValueError``. A guess can
+            place the item in the wrong partition. Defaults to False.
+        :keyword bool idempotent: If the id already exists, read the stored item and return it
+            when it equals ``body`` (ignoring the system properties ``_rid``, ``_self``,
+            ``_etag``, ``_attachments`` and ``_ts``) instead of raising
+            ``CosmosResourceExistsError``; a differing item still raises. The read costs an
+            extra request (about 1 RU per KB) on every conflict. Defaults to False.
+        :keyword bool server_timestamp: Return a copy of ``body`` with ``_ts``, the server's
+            commit time of the write in epoch seconds, so audit fields do not depend on the
+            client's clock. The service returns the written item for this, which adds response
+            bandwidth but no request. With ``parse_timestamps=True``, ``_ts_datetime`` is added
+            too. Defaults to False.
+        :keyword str server_timestamp_field: Like ``server_timestamp``, and also copy ``_ts``
+            into this key of the returned dict. The stored item is not changed.
+        :keyword bool enable_content_response_on_write: Return the written item. Pass False
+            for high-throughput writes that do not use the result: None is returned, and a
+            JSON string body is not parsed back into a dict. The service's response body is
+            only requested for ``server_timestamp``, which cannot be combined with False.
+            Defaults to True. Also accepted by :meth:`upsert_item` and :meth:`replace_item`.
+        :return: The created item, or None with ``enable_content_response_on_write=False``
         :rtype: dict
         """
         return self._client.create_item(body, **kwargs)
@@ -161,16 +597,81 @@ class ContainerProxy:
         
         :param str item: The item ID
         :param partition_key: The partition key value
-        :return: The item
+        :keyword list[str] fields: Only deserialize these top-level fields of the item
+        :keyword str json_path: Return only the subtree at this path, e.g. ``"address.lines[0]"``
+            or ``"$['display name']"``. Dot keys, quoted bracket keys and array indices are
+            supported. The whole item is still fetched and billed; only the returned object
+            is smaller.
+        :keyword bool json_path_missing_ok: Return None instead of raising KeyError when
+            ``json_path`` does not exist in the item. Defaults to False.
+        :keyword str fallback_consistency: Consistency level, typically ``"Eventual"``, for a
+            single retry when the read at the requested consistency times out (a 408 from the
+            service, a transport timeout, or ``fallback_timeout`` elapsing). The retry may be
+            served by any replica, so **the returned item may be stale**; the ``response_hook``
+            diagnostics have ``degraded=True`` when the fallback was used. Only use it where
+            availability matters more than freshness.
+        :keyword fallback_timeout: Seconds (or a ``timedelta``) the first read may take before
+            it is abandoned in favour of the fallback read
+        :keyword bool read_after_write: Retry a 404 up to 3 times, waiting 50, 100 and 200 ms,
+            to tolerate replication lag when reading an item just written under Eventual or
+            Consistent Prefix consistency. **This masks genuine 404s**, which only raise after
+            the retries, so use it only where the item is known to have been written.
+            Defaults to False.
+        :keyword bool include_etag: Add ``_etag`` to the returned dict even when ``fields``,
+            ``json_path`` or ``strip_system_properties`` leave it out. Defaults to False.
+            Whenever ``_etag`` is returned it is the response's ``ETag`` header, so a
+            read-modify-write is ``item = read_item(...)`` then
+            ``replace_item(item["id"], item, etag=item["_etag"])``.
+        :return: The item, or the subtree selected by ``json_path``
         :rtype: dict
+        :raises CosmosDeserializationError: The stored document holds a number beyond the range
+            of a 64-bit float or is nested deeper than 128 levels; ``path`` names the value.
         """
         return self._client.read_item(item, partition_key, **kwargs)
     
+    def read_item_with_etag(self, item: str, partition_key, **kwargs) -> tuple:
+        """Read an item together with its entity tag.
+        
+        The etag is taken from the response's ``ETag`` header, falling back to the item's
+        ``_etag``, so it is returned even when ``fields`` or ``json_path`` leave ``_etag`` out
+        of the item. Takes the same keywords as :meth:`read_item`.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :return: ``(item, etag)``
+        :rtype: tuple[dict, str]
+        """
+        return self._client.read_item_with_etag(item, partition_key, **kwargs)
+    
+    def read_item_bytes(self, item: str, partition_key, **kwargs) -> bytes:
+        """Read an item as the raw JSON response body, without parsing it.
+        
+        This is the fastest way to pass a document through verbatim, e.g. from a proxy. The
+        bytes are exactly what the service returned, system properties included; the caller
+        is responsible for the ``Content-Type`` (``application/json``) of anything it forwards
+        them to. No client-side transformation is applied: ``fields``, ``json_path``,
+        ``parse_timestamps`` and any other read option are ignored.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :return: The UTF-8 encoded JSON document
+        :rtype: bytes
+        """
+        return self._client.read_item_bytes(item, partition_key, **kwargs)
+    
     def upsert_item(self, body: dict, **kwargs) -> dict:
         """Create or replace an item.
         
         :param dict body: The item to upsert
-        :return: The upserted item
+        :keyword bool server_timestamp: Return ``_ts``, the server's commit time, as for
+            :meth:`create_item`
+        :keyword str server_timestamp_field: Also copy ``_ts`` into this key of the returned dict
+        :keyword str etag: Only write if the stored item still has this etag, as returned by
+            :meth:`read_item_with_etag`. Sent as ``If-Match``; if the item has changed, the write
+            raises ``CosmosAccessConditionFailedError``. ``if_match`` is an alias.
+        :keyword bool enable_content_response_on_write: Return the written item, as for
+            :meth:`create_item`. With False, None is returned. Defaults to True.
+        :return: The upserted item, or None with ``enable_content_response_on_write=False``
         :rtype: dict
         """
         return self._client.upsert_item(body, **kwargs)
@@ -180,7 +681,15 @@ class ContainerProxy:
         
         :param str item: The item ID
         :param dict body: The new item data
-        :return: The replaced item
+        :keyword bool server_timestamp: Return ``_ts``, the server's commit time, as for
+            :meth:`create_item`
+        :keyword str server_timestamp_field: Also copy ``_ts`` into this key of the returned dict
+        :keyword str etag: Only write if the stored item still has this etag, as returned by
+            :meth:`read_item_with_etag`. Sent as ``If-Match``; if the item has changed, the write
+            raises ``CosmosAccessConditionFailedError``. ``if_match`` is an alias.
+        :keyword bool enable_content_response_on_write: Return the written item, as for
+            :meth:`create_item`. With False, None is returned. Defaults to True.
+        :return: The replaced item, or None with ``enable_content_response_on_write=False``
         :rtype: dict
         """
         return self._client.replace_item(item, body, **kwargs)
@@ -189,38 +698,327 @@ class ContainerProxy:
         """Delete an item.
         
         :param str item: The item ID
-        :param partition_key: The partition key value
+        :param partition_key: The partition key value; None deletes the item from the
+            undefined partition, which is distinct from an empty-string key
+        :keyword str etag: Only write if the stored item still has this etag, as returned by
+            :meth:`read_item_with_etag`. Sent as ``If-Match``; if the item has changed, the write
+            raises ``CosmosAccessConditionFailedError``. ``if_match`` is an alias.
         """
         return self._client.delete_item(item, partition_key, **kwargs)
     
     def query_items(self, query: str, **kwargs) -> list:
         """Query items with SQL.
         
-        :param str query: SQL query string
-        :return: List of matching items
+        Values should be bound as parameters rather than formatted into the SQL, either with
+        the ``parameters`` keyword or by passing a :class:`QueryBuilder`::
+        
+            container.query_items(
+                "SELECT * FROM c WHERE c.age > @min", parameters=[{"name": "@min", "value": 18}]
+            )
+            container.query_items(
+                QueryBuilder("c").where("c.age > @min", min=18).order_by("c.name").limit(10)
+            )
+        
+        A ``partition_key`` keyword scopes the query to that logical partition regardless of
+        the WHERE clause: ``SELECT * FROM c WHERE c.pk = 'b'`` with ``partition_key="a"``
+        returns no items rather than the items of partition ``"b"``. Leaving ``partition_key``
+        out runs the query across partitions, while an explicit ``partition_key=None`` scopes
+        it to the undefined partition, i.e. items without a partition key value.
+        
+        :param query: SQL query string or :class:`QueryBuilder`
+        :keyword list[dict] parameters: Query parameters as ``{"name": "@x", "value": ...}``.
+            Values (str, int, float, bool, None, or lists and dicts of them) are sent as JSON
+            next to the SQL text, never interpolated into it, so user input cannot change the
+            query. An entry without ``name`` or ``value`` raises ``ValueError``; a parameter
+            the query uses but the list does not bind is rejected by the service with
+            :class:`CosmosHttpResponseError`.
+        :keyword bool strict_partition: Warn (``UserWarning``) when the query compares the
+            partition key path with ``=`` to a literal or parameter that differs from
+            ``partition_key``. Only the plain ``c.path = value`` form is detected; checking
+            reads the container's partition key definition once per container client.
+        :keyword dict schema: Top-level fields mapped to ``int``, ``float``, ``bool``, ``str`` or
+            ``datetime.datetime``; each listed field of every result is coerced to its type.
+            Strings are parsed (``"true"``/``"false"`` for bool, ISO 8601 for datetime), ints
+            are widened to float, and epoch seconds become UTC datetimes. A value that cannot
+            be coerced raises ``ValueError`` naming the field and value, surfacing type drift
+            in the stored data. Missing and ``None`` values, and fields not listed, are left
+            as they are. Also accepted by the read methods.
+        :keyword int max_item_count: Items per page requested from the service; all pages are
+            still collected into the returned list. Smaller pages spread a query's RU cost over
+            more, cheaper requests, smoothing bursts that could be throttled at the price of
+            extra round trips; larger pages finish in fewer requests but charge more at once.
+            Must be positive. Defaults to the service's page size. With ``single_page``, the
+            size of the one page fetched.
+        :keyword bool single_page: Make a single round trip and return what the first page
+            holds, as ``(items, continuation_token)``, instead of draining every page. This
+            bounds the latency of interactive endpoints; the page may hold fewer items than
+            match, or none, while the token is not None. Pass the token as the
+            ``continuation`` of :meth:`query_items_paged` to continue. Unlike that method, no
+            :class:`QueryPage` with RU charge, cursor or index metrics is built. Defaults to
+            False.
+        :return: List of matching items, or ``(items, continuation_token)`` with
+            ``single_page``
         :rtype: list[dict]
         """
         return self._client.query_items(query, **kwargs)
     
+    def query_items_columns(self, query: str, **kwargs) -> dict:
+        """Query items with SQL, returning the results as columns.
+        
+        Each top-level property of the result rows becomes a key mapping to a list with one
+        value per row, in row order; rows without a property hold ``None`` in its column.
+        Heterogeneous rows are handled by taking the union of their properties. The result
+        can be passed straight to ``pandas.DataFrame``::
+        
+            df = pd.DataFrame(container.query_items_columns("SELECT c.id, c.price FROM c", partition_key="a"))
+        
+        Rows must be JSON objects, so ``SELECT VALUE`` of a scalar raises ``ValueError``.
+        Takes the same keywords as :meth:`query_items`, and each row goes through the same
+        ``schema``, ``parse_timestamps`` and ``id_field`` handling before it is pivoted.
+        
+        :param query: SQL query string or :class:`QueryBuilder`
+        :return: Column name to list of values
+        :rtype: dict[str, list]
+        """
+        return self._client.query_items_columns(query, **kwargs)
+    
+    def query_items_paged(
+        self, query: str, max_item_count: int = None, continuation: str = None, **kwargs
+    ) -> QueryPage:
+        """Fetch a single page of query results.
+        
+        Without a ``partition_key`` keyword the query runs across all partitions.
+        
+        :param query: SQL query string or :class:`QueryBuilder`
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :keyword bool populate_index_metrics: Ask the service for index utilization
+            (``x-ms-cosmos-populateindexmetrics``). The ``x-ms-cosmos-index-utilization``
+            response header, base64-encoded JSON, is decoded into ``page.index_metrics``, and
+            ``page.composite_index_used`` reports whether ``UtilizedCompositeIndexes`` is
+            non-empty. Index metrics add RU and latency; enable them in tests, not production.
+        :keyword bool require_index: Populate index metrics and raise ``ValueError`` when the
+            service lists ``PotentialSingleIndexes`` or ``PotentialCompositeIndexes``, i.e.
+            indexes the query would have used but the indexing policy lacks, so it scanned
+            instead. Intended for CI checks of a container's indexing policy.
+        :return: The page, with its items, continuation token, RU charge, activity id and a
+            :class:`QueryCursor` for resuming the query (None on the last page). The cursor
+            keeps the query, its parameters and the ``partition_key`` scope; other keywords
+            are not carried over. ``cursor.to_dict()`` returns a JSON-serializable dict that
+            ``QueryCursor.from_dict`` restores, after which ``cursor.resume(container)``
+            fetches the next page.
+        :rtype: QueryPage
+        :raises CosmosInvalidContinuationTokenError: ``continuation`` is empty, not a valid
+            header value, or rejected by the service, e.g. a token persisted by an incompatible
+            SDK version. The query has to be restarted without a continuation token; the
+            service's error, if any, is the exception's ``__cause__``.
+        """
+        return self._client.query_items_paged(query, max_item_count, continuation, **kwargs)
+    
+    def query_items_page(
+        self, query: str, max_item_count: int = None, continuation: str = None, **kwargs
+    ) -> tuple:
+        """Fetch a single page of query results as ``(items, continuation_token)``.
+        
+        Only the page is held in memory. Pass the token back as ``continuation`` until it is
+        None to walk a large result set; without a ``partition_key`` keyword the query runs
+        across all partitions.
+        
+        :param str query: SQL query string
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page items and the token for the next page, or None when exhausted
+        :rtype: tuple[list[dict], str]
+        """
+        return self._client.query_items_page(query, max_item_count, continuation, **kwargs)
+    
+    def query_items_iter(self, query, max_item_count: int = None, **kwargs) -> QueryIterator:
+        """Iterate over query results, fetching pages lazily.
+        
+        Only one page is held in memory: the next page is requested when the iterator has
+        yielded every item of the current one, so ``for item in container.query_items_iter(q)``
+        scales to result sets of any size. The query and ``max_item_count`` are validated
+        immediately; the first request is sent by the first ``next()``. Takes the same
+        keywords as :meth:`query_items`, e.g. ``parameters`` and ``partition_key``.
+        
+        :param query: SQL query string or a ``QueryBuilder``
+        :param int max_item_count: Maximum number of items per page
+        :return: An iterator of item dicts
+        :rtype: QueryIterator
+        """
+        return self._client.query_items_iter(query, max_item_count, **kwargs)
+    
+    def query_items_foreach(self, query, callback, **kwargs) -> int:
+        """Call ``callback`` with each query result as pages arrive.
+        
+        Only one page is held in memory at a time, and the next page is not requested until
+        the callback has returned for every item of the current one, so a callback that blocks
+        (for example on a full ``queue.Queue``) applies backpressure to the scan. If the
+        callback raises, the scan stops and the exception propagates.
+        
+        The callback runs on the calling thread with the GIL held. The GIL is released while
+        waiting for the next page, so other Python threads (such as the consumer of a queue
+        the callback feeds) can run during network I/O.
+        
+        Without a ``partition_key`` keyword the query runs across all partitions.
+        
+        :param query: SQL query string or :class:`QueryBuilder`
+        :param callable callback: Called with each item
+        :return: The number of items passed to the callback
+        :rtype: int
+        """
+        return self._client.query_items_foreach(query, callback, **kwargs)
+    
+    def partition_key_delete_status(self, partition_key, **kwargs) -> dict:
+        """Check whether a delete of all items in a logical partition has completed.
+        
+        Deleting every item of a partition key is eventual: the service accepts the request
+        and removes the items in the background, so for a while afterwards the partition
+        still returns some of them. This counts the items left in the partition with a
+        ``SELECT VALUE COUNT(1)`` query scoped to it (about 1 RU plus a little per item), so a
+        cleanup script can poll until ``pending`` is False before declaring success. Items
+        written to the partition after the delete also count as remaining.
+        
+        :param partition_key: The partition key value whose delete to check
+        :return: Dict with ``pending`` (bool) and ``remaining_items`` (int)
+        :rtype: dict
+        """
+        return self._client.partition_key_delete_status(partition_key, **kwargs)
+    
+    def query_items_partial(self, query, on_partial, **kwargs):
+        """Run an aggregate query, reporting the running result as partitions complete.
+        
+        The query must be ``SELECT VALUE COUNT(...)``, ``SUM``, ``MIN`` or ``MAX``; ``AVG``
+        raises ``ValueError`` because partial averages cannot be merged. Across partitions the
+        service answers with one partial aggregate per physical partition; they are merged
+        client-side and ``on_partial`` is called with the merged value each time a page adds
+        to it, for progressive feedback on long-running counts and sums.
+        
+        **Intermediate values are partial**: they cover only the partitions that have answered
+        so far and change as more do. Only the return value is the complete aggregate.
+        
+        :param query: SQL query string or :class:`QueryBuilder`
+        :param callable on_partial: Called with the running aggregate
+        :return: The final aggregate; 0 for COUNT and None for the others when nothing matched
+        """
+        return self._client.query_items_partial(query, on_partial, **kwargs)
+    
+    def read_all_items(self, max_item_count: int = None, **kwargs) -> list:
+        """Read every item in the container.
+        
+        Items are fetched with ``SELECT * FROM c`` across all partitions (or within the
+        ``partition_key`` keyword's partition), one page per request, and returned together.
+        
+        :param int max_item_count: Maximum number of items per page, a positive int. Smaller
+            pages spread the RU charge over more, cheaper requests. Defaults to the service's
+            dynamic page size, which fills pages up to 4 MB.
+        :return: All items in the container
+        :rtype: list[dict]
+        """
+        return self._client.read_all_items(max_item_count, **kwargs)
+    
+    def read_all_items_paged(
+        self, max_item_count: int = None, continuation: str = None, **kwargs
+    ) -> QueryPage:
+        """Read a single page of the container's items, e.g. for an export.
+        
+        :param int max_item_count: Maximum number of items on the page, a positive int.
+            Defaults to the service's dynamic page size.
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page and its ``continuation_token``, None after the last page
+        :rtype: QueryPage
+        """
+        return self._client.read_all_items_paged(max_item_count, continuation, **kwargs)
+    
+    def read_items_by_ids(self, ids: list, parameters: list = None, **kwargs) -> list:
+        """Read items by id using cross-partition queries.
+        
+        The ids are sent as parameters of ``SELECT * FROM c WHERE c.id IN (...)`` queries,
+        chunked to stay within query size limits. This avoids one round trip per id, but each
+        query fans out to every physical partition: a point read costs ~1 RU per item, while the
+        query is billed per partition scanned. Prefer point reads for a handful of ids and this
+        method for large id lists spread over few partitions.
+        
+        :param list[str] ids: The item IDs, bound as ``@id0``, ``@id1``, ...
+        :param list parameters: Further ``{"name": "@x", "value": ...}`` parameters bound on
+            every chunk's query, as for :meth:`query_items`; names may not clash with the ids'
+        :return: The matching items, in no particular order
+        :rtype: list[dict]
+        """
+        return self._client.read_items_by_ids(ids, parameters, **kwargs)
+    
     def patch_item(self, item: str, partition_key, patch_operations: list, **kwargs) -> dict:
         """Patch an item.
         
+        The operations are applied atomically by the service, in order::
+        
+            container.patch_item("1", "1", [
+                {"op": "add", "path": "/tags/-", "value": "x"},
+                {"op": "incr", "path": "/count", "value": 1},
+                {"op": "remove", "path": "/old"},
+            ])
+        
+        Supported ops are ``add``, ``set``, ``replace``, ``remove``, ``incr`` (alias
+        ``increment``, with a numeric value) and ``move`` (with the source path as ``from``).
+        
         :param str item: The item ID
         :param partition_key: The partition key value
-        :param list patch_operations: List of patch operations
+        :param list patch_operations: Patch operations as ``{"op", "path", "value"}`` dicts
         :return: The patched item
         :rtype: dict
+        :raises ValueError: The list is empty, or an operation has an unknown ``op`` or lacks
+            a field it needs; the message names the operation's index
         """
         return self._client.patch_item(item, partition_key, patch_operations, **kwargs)
     
+    def patch_item_append_unique(
+        self, item: str, partition_key, path: str, value, **kwargs
+    ) -> dict:
+        """Append a value to an array in an item unless the array already contains it.
+        
+        Sent as one conditional patch: an ``add`` to ``{path}/-`` with a filter predicate
+        requiring ``NOT ARRAY_CONTAINS`` of the value, so concurrent appends of the same value
+        cannot duplicate it and no read-modify-write is needed. Values are compared in full,
+        including every field of objects.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :param str path: Path of the array, e.g. ``"/tags"``; the array must exist
+        :param value: The value to append
+        :raises ValueError: If ``path`` is not an array in the item
+        :return: The patched item, or the unchanged item if the value was already present
+        :rtype: dict
+        """
+        return self._client.patch_item_append_unique(item, partition_key, path, value, **kwargs)
+    
     def read(self, **kwargs) -> dict:
         """Read container properties.
         
+        The properties are returned as stored by the service, e.g. ``id``,
+        ``partitionKey``, ``indexingPolicy`` and ``geospatialConfig``.
+        
         :return: Container properties
         :rtype: dict
         """
         return self._client.read(**kwargs)
     
+    def wait_until_ready(self, timeout=None, **kwargs) -> float:
+        """Block until the container's indexing transformation has finished.
+        
+        After creating a container or replacing its indexing policy, queries can be slow, and
+        may miss items, until the service has (re)built the indexes. This polls the container
+        every second and reads ``x-ms-documentdb-collection-index-transformation-progress``
+        until it reaches 100 (percent); a container with no transformation in progress
+        returns after one read.
+        
+        :param timeout: Give up after this many seconds (or ``datetime.timedelta``);
+            by default wait indefinitely
+        :return: Seconds spent waiting
+        :rtype: float
+        :raises CosmosTimeoutError: The transformation did not finish within ``timeout``
+        """
+        return self._client.wait_until_ready(timeout, **kwargs)
+    
     def delete(self, **kwargs) -> None:
         """Delete this container."""
         return self._client.delete(**kwargs)
@@ -1,10 +1,127 @@
 """Type stubs for Azure Cosmos DB Python SDK."""
 
-from typing import Any, Dict, List, Optional, Union, TypeVar, overload
+from typing import Any, Callable, Dict, List, Optional, Tuple, Union, TypeVar, overload
 from typing_extensions import Self
 
 _T = TypeVar("_T")
 
+class QueryPage:
+    """A single page of query results."""
+    
+    items: List[Dict[str, Any]]
+    continuation_token: Optional[str]
+    request_charge: Optional[float]
+    activity_id: Optional[str]
+    cursor: Optional[QueryCursor]
+    index_metrics: Optional[Dict[str, Any]]
+    """Index utilization, when the page was fetched with ``populate_index_metrics=True``."""
+    composite_index_used: Optional[bool]
+    """Whether a composite index served the page; None without index metrics."""
+    
+    def __len__(self) -> int: ...
+
+
+class QueryCursor:
+    """A resumable position in a query.
+    
+    Wraps the continuation token together with the query, its parameters and partition
+    scope, so a long scan can be persisted with :meth:`to_dict` and resumed later, even in
+    another process.
+    """
+    
+    continuation_token: str
+    query: str
+    max_item_count: Optional[int]
+    database_id: str
+    container_id: str
+    
+    def resume(self, container: Optional[ContainerProxy] = None) -> QueryPage:
+        """Fetch the next page of the query.
+        
+        :param container: The container the query ran against; required for cursors
+            restored with :meth:`from_dict`
+        :raises ValueError: If no container is available or it is a different container
+        :return: The next page
+        :rtype: QueryPage
+        """
+        ...
+    
+    def to_dict(self) -> Dict[str, Any]:
+        """Return the cursor as a JSON-serializable dict."""
+        ...
+    
+    @staticmethod
+    def from_dict(data: Dict[str, Any]) -> QueryCursor:
+        """Restore a cursor saved with :meth:`to_dict`."""
+        ...
+
+
+class QueryIterator:
+    """Iterator over the items of a query that fetches one page at a time."""
+    
+    def __iter__(self) -> QueryIterator: ...
+    def __next__(self) -> Dict[str, Any]: ...
+
+
+def merge_session_tokens(*tokens: str) -> str:
+    """Merge session tokens, keeping the highest LSN per partition key range.
+    
+    The result is at least as recent as each input, so a read using it observes the writes
+    seen by every client that produced one of the tokens.
+    
+    :param str tokens: Composite session tokens such as ``"0:1#100#1=20,1:1#50"``
+    :raises ValueError: If a token is malformed
+    :return: The merged token
+    :rtype: str
+    """
+    ...
+
+
+class DatabaseProperties:
+    """Database properties with typed accessors, returned with ``typed_properties=True``."""
+    
+    id: str
+    etag: Optional[str]
+    
+    def as_dict(self) -> Dict[str, Any]:
+        """Return the properties as a dict, with the service's key names."""
+        ...
+
+
+class ContainerProperties:
+    """Container properties with typed accessors, returned with ``typed_properties=True``."""
+    
+    id: str
+    etag: Optional[str]
+    partition_key: Optional[Dict[str, Any]]
+    default_ttl: Optional[int]
+    indexing_policy: Optional[Dict[str, Any]]
+    
+    def as_dict(self) -> Dict[str, Any]:
+        """Return the properties as a dict, with the service's key names."""
+        ...
+
+
+class QueryBuilder:
+    """Fluent builder for parameterized queries.
+    
+    Supports the subset ``SELECT <fields> FROM <alias> WHERE ... ORDER BY ... OFFSET n LIMIT m``.
+    Conditions from repeated :meth:`where` calls are combined with AND, and keyword arguments
+    of :meth:`where` bind ``@name`` parameters. Joins, aggregates, GROUP BY and subqueries are
+    not covered; write those as SQL with the ``parameters`` keyword of ``query_items``.
+    """
+    
+    query: str
+    parameters: List[Dict[str, Any]]
+    
+    def __init__(self, alias: str = "c") -> None: ...
+    def select(self, *fields: str) -> Self: ...
+    def where(self, condition: str, **parameters: Any) -> Self: ...
+    def order_by(self, expression: str, descending: bool = False) -> Self: ...
+    def offset(self, count: int) -> Self: ...
+    def limit(self, count: int) -> Self: ...
+
+
 class CosmosClient:
     """A client for interacting with Azure Cosmos DB."""
     
@@ -15,6 +132,27 @@ class CosmosClient:
         **kwargs: Any
     ) -> None: ...
     
+    @classmethod
+    def from_emulator(cls, endpoint: str = "https://localhost:8081", **kwargs: Any) -> Self:
+        """Create a client for the local Cosmos DB emulator.
+        
+        Uses the well-known emulator key and disables TLS verification. Only localhost and
+        loopback endpoints are accepted.
+        
+        :param str endpoint: The emulator endpoint
+        :raises ValueError: If the endpoint is not local
+        """
+        ...
+    
+    @classmethod
+    def from_connection_string(cls, conn_str: str, **kwargs: Any) -> Self:
+        """Create a client from an ``AccountEndpoint=...;AccountKey=...;`` connection string.
+        
+        :param str conn_str: The connection string
+        :raises ValueError: If ``AccountEndpoint`` or ``AccountKey`` is missing
+        """
+        ...
+    
     def __enter__(self) -> Self: ...
     
     def __exit__(
@@ -37,6 +175,9 @@ class CosmosClient:
         """Create a new database.
         
         :param str id: The database ID
+        :keyword int offer_throughput: Manual throughput (RU/s) to provision for the database
+        :keyword bool populate_properties: Return a ``(client, properties)`` tuple including the
+            server-assigned properties and the confirmed ``offer_throughput``
         :return: Database properties
         :rtype: Dict[str, Any]
         """
@@ -49,13 +190,56 @@ class CosmosClient:
         """
         ...
     
-    def list_databases(self, **kwargs: Any) -> List[Dict[str, Any]]:
+    def list_databases(self, **kwargs: Any) -> List[Union[Dict[str, Any], DatabaseProperties]]:
         """List all databases.
         
+        Each entry holds the database's ``id`` plus system properties such as ``_rid`` and ``_self``.
+        
         :return: List of database properties
+        :rtype: List[Union[Dict[str, Any], DatabaseProperties]]
+        """
+        ...
+    
+    def get_last_requests(self, n: Optional[int] = None) -> List[Dict[str, Any]]:
+        """Get the most recently sent requests, for reproducing issues.
+        
+        :param int n: Maximum number of requests to return (defaults to all captured)
+        :return: Requests as dicts with method, url, headers and body, oldest first
         :rtype: List[Dict[str, Any]]
         """
         ...
+    
+    def get_metrics(self) -> Dict[str, Any]:
+        """Get the request totals accumulated since the client was created.
+        
+        Request totals require ``track_metrics=True``; query cache stats only require
+        ``enable_query_cache=True``.
+        
+        :return: Dict with total_request_charge, request_count, requests_by_operation and
+            throttled_requests with ``track_metrics=True``, plus query_cache_hits and
+            query_cache_misses with ``enable_query_cache=True``
+        :rtype: Dict[str, Any]
+        """
+        ...
+    
+    def in_flight_count(self) -> int:
+        """Get the number of operations currently running on this client."""
+        ...
+    
+    def cancel_all(self) -> None:
+        """Cancel every running operation; each raises ``asyncio.CancelledError``.
+        
+        Writes already sent may still be applied by the service.
+        """
+        ...
+    
+    @property
+    def circuit_breaker_state(self) -> Optional[str]:
+        """State of the circuit breaker: ``"closed"``, ``"open"`` or ``"half_open"``.
+        
+        None unless the client was created with ``circuit_breaker_threshold``.
+        """
+        ...
 
 
 class DatabaseProxy:
@@ -66,10 +250,19 @@ class DatabaseProxy:
         """Get the database ID."""
         ...
     
-    def get_container_client(self, container: str) -> ContainerProxy:
+    def get_container_client(
+        self,
+        container: str,
+        partition_key_type: Optional[str] = None,
+        validate: bool = False
+    ) -> ContainerProxy:
         """Get a container client.
         
         :param str container: The container ID
+        :param str partition_key_type: The type partition key values are stored as, "string"
+            or "number", to coerce partition key values of the other type
+        :param bool validate: Read the container now, raising ``CosmosResourceNotFoundError``
+            if it does not exist
         :return: A container client
         :rtype: ContainerProxy
         """
@@ -78,15 +271,18 @@ class DatabaseProxy:
     def create_container(
         self,
         id: str,
-        partition_key: Dict[str, Any],
+        partition_key: Union[str, Dict[str, Any]],
         **kwargs: Any
-    ) -> Dict[str, Any]:
+    ) -> ContainerProxy:
         """Create a new container.
         
         :param str id: The container ID
-        :param Dict partition_key: Partition key configuration
-        :return: Container properties
-        :rtype: Dict[str, Any]
+        :param partition_key: The partition key path, e.g. ``"/category"``, or a partition key
+            configuration dict with a 'paths' key
+        :keyword int default_ttl: Time to live of items in seconds
+        :keyword int offer_throughput: Manual throughput (RU/s) to provision for the container
+        :return: A client for the new container
+        :rtype: ContainerProxy
         """
         ...
     
@@ -97,19 +293,51 @@ class DatabaseProxy:
         """
         ...
     
-    def list_containers(self, **kwargs: Any) -> List[Dict[str, Any]]:
+    def list_containers(self, **kwargs: Any) -> List[Union[Dict[str, Any], ContainerProperties]]:
         """List all containers.
         
         :return: List of container properties
+        :rtype: List[Union[Dict[str, Any], ContainerProperties]]
+        """
+        ...
+    
+    def query_containers(
+        self,
+        query: Union[str, QueryBuilder],
+        **kwargs: Any
+    ) -> List[Union[Dict[str, Any], ContainerProperties]]:
+        """Query the containers in this database.
+        
+        :param query: The SQL query string or a ``QueryBuilder``
+        :keyword list parameters: Query parameters as ``{"name": "@x", "value": ...}`` dicts
+        :return: List of container properties
+        :rtype: List[Union[Dict[str, Any], ContainerProperties]]
+        """
+        ...
+    
+    def list_offers(self, **kwargs: Any) -> List[Dict[str, Any]]:
+        """List the provisioned throughput of this database and each of its containers.
+        
+        :return: Offers with ``resource``, ``id``, ``mode`` (``"manual"`` or ``"autoscale"``)
+            and ``throughput`` (RU/s, or the autoscale maximum); empty on serverless accounts
         :rtype: List[Dict[str, Any]]
         """
         ...
     
-    def read(self, **kwargs: Any) -> Dict[str, Any]:
+    def read(self, **kwargs: Any) -> Union[Dict[str, Any], DatabaseProperties]:
         """Read database properties.
         
         :return: Database properties
-        :rtype: Dict[str, Any]
+        :rtype: Union[Dict[str, Any], DatabaseProperties]
+        """
+        ...
+    
+    def run_setup(self, steps: List[Dict[str, Any]]) -> int:
+        """Run a sequence of setup steps in order, e.g. for test fixtures.
+        
+        :param List[Dict] steps: The steps to run
+        :return: The number of steps run
+        :rtype: int
         """
         ...
     
@@ -126,36 +354,111 @@ class ContainerProxy:
         """Get the container ID."""
         ...
     
-    def create_item(self, body: Dict[str, Any], **kwargs: Any) -> Dict[str, Any]:
+    def resolve_partition_key(self, body: Union[Dict[str, Any], str], **kwargs: Any) -> Any:
+        """Resolve the partition key a write of ``body`` would use, without writing it.
+        
+        :param body: The item, or a JSON string with a ``partition_key`` keyword
+        :return: The partition key value, or a list of the components for a hierarchical key
+        :raises ValueError: The key cannot be resolved or does not fit the container
+        """
+        ...
+    
+    def get_item_partition_key(self, item: Dict[str, Any]) -> Any:
+        """Return the partition key an item is stored under, from its fields at the key paths.
+        
+        :param item: The item, as returned by a read or query
+        :return: The partition key value, or a list of the components for a hierarchical key.
+            A path the item lacks gives None, the undefined partition.
+        """
+        ...
+    
+    def create_item(self, body: Dict[str, Any], **kwargs: Any) -> Optional[Dict[str, Any]]:
         """Create a new item.
         
         :param Dict body: The item to create
-        :return: The created item
-        :rtype: Dict[str, Any]
+        :keyword partition_key: Always used when passed; None writes to the undefined partition.
+            Otherwise read from the body at the container's key path
+        :keyword bool auto_partition_key: Guess the key from common field names when the body
+            has no value at the key path, instead of raising ValueError. Defaults to False
+        :keyword bool server_timestamp: Return a copy of ``body`` with ``_ts``, the server's
+            commit time of the write
+        :keyword str server_timestamp_field: Also copy ``_ts`` into this key of the returned dict
+        :keyword bool enable_content_response_on_write: Return the written item; False returns
+            None. Defaults to True.
+        :return: The created item, or None with ``enable_content_response_on_write=False``
+        :rtype: Optional[Dict[str, Any]]
         """
         ...
     
     def read_item(
         self,
         item: str,
-        partition_key: Union[str, int, float],
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
         **kwargs: Any
-    ) -> Dict[str, Any]:
+    ) -> Any:
         """Read an item by ID and partition key.
         
         :param str item: The item ID
         :param partition_key: The partition key value
-        :return: The item
-        :rtype: Dict[str, Any]
+        :keyword List[str] fields: Only deserialize these top-level fields of the item
+        :keyword str json_path: Return only the subtree at this path, e.g. ``"address.lines[0]"``
+        :keyword bool json_path_missing_ok: Return None instead of raising KeyError when
+            ``json_path`` does not exist
+        :keyword bool read_after_write: Retry a 404 a few times with backoff to tolerate
+            replication lag after a write; masks genuine 404s
+        :keyword bool include_etag: Add the response's ETag as ``_etag`` even when a projection
+            leaves it out
+        :return: The item, or the subtree selected by ``json_path``
+        :rtype: Any
         """
         ...
     
-    def upsert_item(self, body: Dict[str, Any], **kwargs: Any) -> Dict[str, Any]:
+    def read_item_with_etag(
+        self,
+        item: str,
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
+        **kwargs: Any
+    ) -> Tuple[Any, Optional[str]]:
+        """Read an item together with its entity tag.
+        
+        The etag comes from the response's ``ETag`` header, falling back to the item's ``_etag``.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :return: ``(item, etag)``
+        :rtype: Tuple[Any, Optional[str]]
+        """
+        ...
+    
+    def read_item_bytes(
+        self,
+        item: str,
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
+        **kwargs: Any
+    ) -> bytes:
+        """Read an item as the raw JSON response body, without parsing it.
+        
+        No read options or transformations are applied.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :return: The UTF-8 encoded JSON document
+        :rtype: bytes
+        """
+        ...
+    
+    def upsert_item(self, body: Dict[str, Any], **kwargs: Any) -> Optional[Dict[str, Any]]:
         """Create or replace an item.
         
         :param Dict body: The item to upsert
-        :return: The upserted item
-        :rtype: Dict[str, Any]
+        :keyword bool server_timestamp: Return ``_ts``, the server's commit time of the write
+        :keyword str server_timestamp_field: Also copy ``_ts`` into this key of the returned dict
+        :keyword str etag: Only write if the stored item still has this etag (``If-Match``);
+            ``if_match`` is an alias
+        :keyword bool enable_content_response_on_write: Return the written item; False returns
+            None. Defaults to True.
+        :return: The upserted item, or None with ``enable_content_response_on_write=False``
+        :rtype: Optional[Dict[str, Any]]
         """
         ...
     
@@ -164,34 +467,195 @@ class ContainerProxy:
         item: str,
         body: Dict[str, Any],
         **kwargs: Any
-    ) -> Dict[str, Any]:
+    ) -> Optional[Dict[str, Any]]:
         """Replace an existing item.
         
         :param str item: The item ID
         :param Dict body: The new item data
-        :return: The replaced item
-        :rtype: Dict[str, Any]
+        :keyword bool server_timestamp: Return ``_ts``, the server's commit time of the write
+        :keyword str server_timestamp_field: Also copy ``_ts`` into this key of the returned dict
+        :keyword str etag: Only write if the stored item still has this etag (``If-Match``);
+            ``if_match`` is an alias
+        :keyword bool enable_content_response_on_write: Return the written item; False returns
+            None. Defaults to True.
+        :return: The replaced item, or None with ``enable_content_response_on_write=False``
+        :rtype: Optional[Dict[str, Any]]
         """
         ...
     
     def delete_item(
         self,
         item: str,
-        partition_key: Union[str, int, float],
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
         **kwargs: Any
     ) -> None:
         """Delete an item.
         
         :param str item: The item ID
         :param partition_key: The partition key value
+        :keyword str etag: Only write if the stored item still has this etag (``If-Match``);
+            ``if_match`` is an alias
         """
         ...
     
-    def query_items(self, query: str, **kwargs: Any) -> List[Dict[str, Any]]:
+    def query_items(
+        self, query: Union[str, QueryBuilder], **kwargs: Any
+    ) -> Union[List[Dict[str, Any]], Tuple[List[Dict[str, Any]], Optional[str]]]:
         """Query items with SQL.
         
+        :param query: SQL query string or QueryBuilder
+        :keyword list parameters: Query parameters as ``{"name": "@x", "value": ...}``
+        :keyword dict schema: Fields mapped to ``int``, ``float``, ``bool``, ``str`` or
+            ``datetime.datetime`` to coerce results to; raises ``ValueError`` on type drift
+        :keyword int max_item_count: Items per page requested from the service; all pages are
+            still returned
+        :keyword bool single_page: Fetch only the first page, returning
+            ``(items, continuation_token)``; continue with ``query_items_paged``
+        :return: List of matching items, or ``(items, continuation_token)`` with ``single_page``
+        """
+        ...
+    
+    def query_items_columns(self, query: Union[str, QueryBuilder], **kwargs: Any) -> Dict[str, List[Any]]:
+        """Query items with SQL, returning the results as columns.
+        
+        Columns are the union of the rows' top-level properties; rows without a property
+        hold ``None`` in its column. Suitable for ``pandas.DataFrame(...)``.
+        
+        :param query: SQL query string or QueryBuilder
+        :return: Column name to list of values, one per row
+        :rtype: Dict[str, List[Any]]
+        """
+        ...
+    
+    def query_items_paged(
+        self,
+        query: str,
+        max_item_count: Optional[int] = None,
+        continuation: Optional[str] = None,
+        **kwargs: Any
+    ) -> QueryPage:
+        """Fetch a single page of query results.
+        
         :param str query: SQL query string
-        :return: List of matching items
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page, with its items, continuation token, RU charge and activity id
+        :rtype: QueryPage
+        """
+        ...
+    
+    def query_items_page(
+        self,
+        query: str,
+        max_item_count: Optional[int] = None,
+        continuation: Optional[str] = None,
+        **kwargs: Any
+    ) -> Tuple[List[Dict[str, Any]], Optional[str]]:
+        """Fetch a single page of query results as ``(items, continuation_token)``.
+        
+        :param str query: SQL query string
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page items and the token for the next page, or None when exhausted
+        :rtype: Tuple[List[Dict[str, Any]], Optional[str]]
+        """
+        ...
+    
+    def query_items_iter(
+        self,
+        query: Union[str, QueryBuilder],
+        max_item_count: Optional[int] = None,
+        **kwargs: Any
+    ) -> QueryIterator:
+        """Iterate over query results, fetching pages lazily.
+        
+        :param query: SQL query string or a ``QueryBuilder``
+        :param int max_item_count: Maximum number of items per page
+        :return: An iterator of item dicts that holds one page in memory at a time
+        :rtype: QueryIterator
+        """
+        ...
+    
+    def query_items_foreach(
+        self,
+        query: Union[str, QueryBuilder],
+        callback: Callable[[Dict[str, Any]], Any],
+        **kwargs: Any
+    ) -> int:
+        """Call ``callback`` with each query result as pages arrive.
+        
+        The callback runs with the GIL held; the GIL is released while waiting for pages.
+        An exception raised by the callback stops the scan and propagates.
+        
+        :param query: SQL query string or QueryBuilder
+        :param callback: Called with each item
+        :return: The number of items passed to the callback
+        :rtype: int
+        """
+        ...
+    
+    def partition_key_delete_status(self, partition_key: Any, **kwargs: Any) -> Dict[str, Any]:
+        """Check whether a delete of all items in a logical partition has completed.
+        
+        The delete is eventual; poll until ``pending`` is False.
+        
+        :param partition_key: The partition key value whose delete to check
+        :return: Dict with ``pending`` and ``remaining_items``
+        :rtype: Dict[str, Any]
+        """
+        ...
+    
+    def query_items_partial(
+        self,
+        query: Union[str, QueryBuilder],
+        on_partial: Callable[[Any], Any],
+        **kwargs: Any
+    ) -> Any:
+        """Run a ``SELECT VALUE`` COUNT, SUM, MIN or MAX query, reporting the running result.
+        
+        Intermediate values passed to ``on_partial`` are partial and may change.
+        
+        :param query: SQL query string or QueryBuilder
+        :param on_partial: Called with the running aggregate as partitions complete
+        :return: The final aggregate
+        """
+        ...
+    
+    def read_all_items(self, max_item_count: Optional[int] = None, **kwargs: Any) -> List[Dict[str, Any]]:
+        """Read every item in the container.
+        
+        :param int max_item_count: Maximum number of items per page
+        :return: All items in the container
+        :rtype: List[Dict[str, Any]]
+        """
+        ...
+    
+    def read_all_items_paged(
+        self,
+        max_item_count: Optional[int] = None,
+        continuation: Optional[str] = None,
+        **kwargs: Any
+    ) -> QueryPage:
+        """Read a single page of the container's items.
+        
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page and its continuation token
+        :rtype: QueryPage
+        """
+        ...
+    
+    def read_items_by_ids(
+        self,
+        ids: List[str],
+        parameters: Optional[List[Dict[str, Any]]] = None,
+        **kwargs: Any
+    ) -> List[Dict[str, Any]]:
+        """Read items by id using cross-partition queries.
+        
+        :param List[str] ids: The item IDs, bound as ``@id0``, ``@id1``, ...
+        :param parameters: Further ``{"name", "value"}`` query parameters bound alongside the ids
+        :return: The matching items, in no particular order
         :rtype: List[Dict[str, Any]]
         """
         ...
@@ -199,7 +663,7 @@ class ContainerProxy:
     def patch_item(
         self,
         item: str,
-        partition_key: Union[str, int, float],
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
         patch_operations: List[Dict[str, Any]],
         **kwargs: Any
     ) -> Dict[str, Any]:
@@ -207,17 +671,48 @@ class ContainerProxy:
         
         :param str item: The item ID
         :param partition_key: The partition key value
-        :param List patch_operations: List of patch operations
+        :param List patch_operations: Patch operations as ``{"op", "path", "value"}`` dicts, with
+            ops ``add``, ``set``, ``replace``, ``remove``, ``incr``/``increment`` and ``move``
         :return: The patched item
         :rtype: Dict[str, Any]
         """
         ...
     
-    def read(self, **kwargs: Any) -> Dict[str, Any]:
+    def patch_item_append_unique(
+        self,
+        item: str,
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
+        path: str,
+        value: Any,
+        **kwargs: Any
+    ) -> Dict[str, Any]:
+        """Append a value to an array in an item unless the array already contains it.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :param str path: Path of the array, e.g. ``"/tags"``
+        :param value: The value to append
+        :raises ValueError: If ``path`` is not an array in the item
+        :return: The patched item, or the unchanged item if the value was already present
+        :rtype: Dict[str, Any]
+        """
+        ...
+    
+    def read(self, **kwargs: Any) -> Union[Dict[str, Any], ContainerProperties]:
         """Read container properties.
         
         :return: Container properties
-        :rtype: Dict[str, Any]
+        :rtype: Union[Dict[str, Any], ContainerProperties]
+        """
+        ...
+    
+    def wait_until_ready(self, timeout: Optional[float] = None, **kwargs: Any) -> float:
+        """Block until the container's indexing transformation reaches 100%.
+        
+        :param timeout: Give up after this many seconds; by default wait indefinitely
+        :return: Seconds spent waiting
+        :rtype: float
+        :raises CosmosTimeoutError: The transformation did not finish within ``timeout``
         """
         ...
     
@@ -4,22 +4,147 @@ from azure.cosmos._rust import (
     CosmosClient as _RustCosmosClient,
     DatabaseClient as _RustDatabaseClient,
     ContainerClient as _RustContainerClient,
+    CancellationToken as _CancellationToken,
 )
 import asyncio
+import functools
 from typing import Optional, Dict, List, Any
 
+# Returned by next() on the executor once a query iterator is drained
+_EXHAUSTED = object()
+
+
+async def _run_cancellable(func, *args, **kwargs):
+    """Run a blocking container operation on the default executor, tied to the awaiting task.
+    
+    Cancelling the awaiting task, or the ``timeout`` keyword (seconds) expiring, aborts the
+    in-flight request and raises ``asyncio.CancelledError`` or ``asyncio.TimeoutError``, as
+    with ``asyncio.wait_for``.
+    """
+    timeout = kwargs.pop("timeout", None)
+    token = _CancellationToken()
+    future = asyncio.get_running_loop().run_in_executor(
+        None, functools.partial(func, *args, cancellation_token=token, **kwargs)
+    )
+    try:
+        return await asyncio.wait_for(future, timeout)
+    except (asyncio.CancelledError, asyncio.TimeoutError):
+        token.cancel()
+        raise
+
 
 class CosmosClient:
     """Async client for interacting with Azure Cosmos DB.
     
     :param str url: The URL of the Cosmos DB account
-    :param credential: The credential for authentication
+    :param credential: The account key string, or an Azure AD credential with a synchronous
+        ``get_token(*scopes)`` method, such as ``azure.identity.DefaultAzureCredential``.
+        Credentials from ``azure.identity.aio`` are not supported.
     :param kwargs: Additional keyword arguments
+    :keyword bool exclude_none_on_write: Recursively drop keys whose value is None from item
+        bodies before they are written. Defaults to False.
+    :keyword int capture_requests: Keep the last N outgoing requests (method, url, headers and
+        body) for :meth:`get_last_requests`. Disabled by default.
+    :keyword bool track_metrics: Accumulate the RU charge, request counts by operation and
+        throttled (429) requests of every request the client sends, for
+        :meth:`get_metrics`. Disabled by default.
+    :keyword str partition_key_mismatch: What to do when a ``partition_key`` keyword passed to
+        an item write differs from the partition key value in the body: ``"ignore"`` (default)
+        sends the keyword value unchecked, ``"raise"`` raises ``ValueError``, ``"warn"`` emits
+        a ``UserWarning`` and writes under the keyword value. Checking reads the container's
+        partition key paths once per container client.
+    :keyword str initial_session_token: Session token obtained from another client (for
+        example passed along by an upstream service). It is sent with every request, merged
+        with any per-operation ``session_token`` by taking the highest LSN per partition key
+        range, so reads observe that client's writes. Only effective with Session consistency.
+    :keyword bool connection_verify: Verify the service's TLS certificate. Defaults to True.
+    :keyword connection_timeout: Timeout for establishing connections, in seconds or as a
+        ``datetime.timedelta``.
+    :keyword bool typed_properties: Return :class:`DatabaseProperties` and
+        :class:`ContainerProperties` objects instead of dicts from ``list_databases``,
+        ``list_containers`` and the database and container ``read`` methods. Their typed
+        accessors catch misspelled keys as ``AttributeError``; ``as_dict()`` returns the dict.
+        Defaults to False.
+    :keyword operation_timeout: Bound, in seconds or as a ``datetime.timedelta``, on each
+        operation, including its retries. An operation exceeding it is abandoned and raises
+        :class:`CosmosTimeoutError`, whose ``operation`` attribute names it; a write already
+        sent may still be applied by the service. Each operation can
+        override it with its own ``operation_timeout`` keyword. The runtime threads are named
+        ``cosmos-<component>-worker-<n>`` so a hung operation shows up in thread dumps.
+    :keyword str id_field: Item property that holds the item's id in application code, such as
+        ``"_id"`` for data migrated from MongoDB-style schemas. Written items have it moved to
+        ``id``, and items returned by reads and queries have ``id`` moved back to it. The
+        service still only knows ``id``: it must be a unique string within the partition,
+        queries must refer to ``c.id``, and a partition key path of ``/id`` reads the mapped
+        value. An item carrying both keys must give them the same value.
+    :keyword int circuit_breaker_threshold: Enable a circuit breaker that opens after this
+        many consecutive 429 or 503 responses within ``circuit_breaker_window`` (default 10
+        seconds). While open, requests fail immediately with
+        :class:`CosmosServiceUnavailableError` instead of adding load; after
+        ``circuit_breaker_cool_down`` (default 30 seconds) one probe request is let through,
+        and its success closes the breaker while another 429 or 503 reopens it. Every attempt
+        is counted, including the SDK's own retries. See :attr:`circuit_breaker_state`.
+        Disabled by default.
+    :keyword bool read_only: Reject every operation that creates, changes or deletes a
+        resource (item writes and deletes, ``patch_item``, creating and deleting containers
+        and databases, ``run_setup``) with :class:`CosmosReadOnlyError` before anything is
+        sent. Reads and queries are unaffected. A client-side safety rail for services that
+        must never write, independent of the credential's permissions; it complements rather
+        than replaces a read-only key or RBAC role. Defaults to False.
+    :keyword int max_response_size_bytes: Largest response body to accept, in bytes. A
+        response declaring a larger body, such as a runaway document or query page, fails
+        with :class:`CosmosResponseTooLargeError` before its body is read; one without a
+        declared size, such as a chunked response, fails as soon as more than the limit has
+        been read, so it is never fully buffered. The service has already executed the request and charged for it, and a
+        write may have been applied. Unlimited by default.
+    :keyword bool enable_query_cache: Serve repeated ``query_items`` and
+        ``query_items_columns`` calls from an in-process cache of their deserialized results,
+        keyed by container, SQL text, parameters and the options that can change the
+        results (``partition_key`` and the container's ``partition_key_type``, ``fields``,
+        ``consistency_level``, ``session_token``, ``enable_scan_in_query`` and
+        ``enable_low_precision_order_by``), without a network call. Writes, including this
+        client's own, do not invalidate entries, so a hit may miss changes made up to
+        ``query_cache_ttl`` ago: cached results bypass the consistency level and session
+        guarantees of the account. Up to 1024 results are cached, evicting the oldest. Meant
+        for read-heavy dashboards that tolerate staleness. Hits and misses are reported by
+        :meth:`get_metrics`. Disabled by default.
+    :keyword query_cache_ttl: How long, in seconds or as a ``datetime.timedelta``, a cached
+        query result is served before the query is sent again. Defaults to 60 seconds.
     """
     
     def __init__(self, url: str, credential=None, **kwargs):
         self._sync_client = _RustCosmosClient(url, credential, **kwargs)
     
+    @classmethod
+    def from_emulator(cls, endpoint: str = "https://localhost:8081", **kwargs) -> "CosmosClient":
+        """Create a client for the local Cosmos DB emulator.
+        
+        Uses the emulator's well-known key, disables TLS verification for its self-signed
+        certificate and sets a 10 second connection timeout unless ``connection_timeout`` is
+        given. Other keyword arguments are passed through as for the constructor.
+        
+        :param str endpoint: The emulator endpoint; must be a localhost or loopback address
+        :raises ValueError: If the endpoint is not local
+        :return: A client connected to the emulator
+        :rtype: CosmosClient
+        """
+        client = cls.__new__(cls)
+        client._sync_client = _RustCosmosClient.from_emulator(endpoint, **kwargs)
+        return client
+    
+    @classmethod
+    def from_connection_string(cls, conn_str: str, **kwargs) -> "CosmosClient":
+        """Create a client from an ``AccountEndpoint=...;AccountKey=...;`` connection string.
+        
+        :param str conn_str: The connection string, as shown in the Azure portal
+        :raises ValueError: If ``AccountEndpoint`` or ``AccountKey`` is missing
+        :return: A client authenticated with the account key
+        :rtype: CosmosClient
+        """
+        client = cls.__new__(cls)
+        client._sync_client = _RustCosmosClient.from_connection_string(conn_str, **kwargs)
+        return client
+    
     async def __aenter__(self):
         return self
     
@@ -65,6 +190,57 @@ class CosmosClient:
         return await asyncio.get_event_loop().run_in_executor(
             None, lambda: self._sync_client.list_databases(**kwargs)
         )
+    
+    def get_last_requests(self, n: int = None) -> list:
+        """Get the most recently sent requests, for reproducing issues.
+        
+        :param int n: Maximum number of requests to return (defaults to all captured)
+        :return: Requests as dicts with method, url, headers and body, oldest first
+        :rtype: list[dict]
+        """
+        return self._sync_client.get_last_requests(n)
+    
+    def get_metrics(self) -> dict:
+        """Get the request totals accumulated since the client was created.
+        
+        Only available when the client was created with ``track_metrics=True``. Every attempt
+        is counted, so a request retried after throttling counts once per attempt. Operation
+        names are derived from the HTTP request, e.g. ``read_item``, ``query_items`` or
+        ``create_database``.
+        
+        :return: Dict with ``total_request_charge``, ``request_count``,
+            ``requests_by_operation`` and ``throttled_requests``
+        :rtype: dict
+        """
+        return self._sync_client.get_metrics()
+    
+    def in_flight_count(self) -> int:
+        """Get the number of operations currently running on this client.
+        
+        Item, query, container, database and account operations are counted, whether
+        called from a thread or through the async client.
+        
+        :return: The number of running operations
+        :rtype: int
+        """
+        return self._sync_client.in_flight_count()
+    
+    def cancel_all(self) -> None:
+        """Cancel every operation currently running on this client, e.g. to drain on shutdown.
+        
+        Each cancelled operation raises ``asyncio.CancelledError``; operations started after
+        the call are not affected. Cancellation drops the request on the client side only: a
+        write whose request was already sent may still be applied by the service.
+        """
+        self._sync_client.cancel_all()
+    
+    @property
+    def circuit_breaker_state(self) -> Optional[str]:
+        """State of the circuit breaker: ``"closed"``, ``"open"`` or ``"half_open"``.
+        
+        ``None`` unless the client was created with ``circuit_breaker_threshold``.
+        """
+        return self._sync_client.circuit_breaker_state
 
 
 class DatabaseProxy:
@@ -81,27 +257,36 @@ class DatabaseProxy:
         """Get the database ID."""
         return self._client.id
     
-    def get_container_client(self, container: str) -> "ContainerProxy":
+    def get_container_client(
+        self, container: str, partition_key_type: str = None
+    ) -> "ContainerProxy":
         """Get a container client.
         
         :param str container: The container ID
+        :param str partition_key_type: The type partition key values are stored as, "string"
+            or "number", to coerce partition key values of the other type
         :return: A container client
         :rtype: ContainerProxy
         """
-        container_client = self._client.get_container_client(container)
+        container_client = self._client.get_container_client(container, partition_key_type)
         return ContainerProxy(container_client)
     
-    async def create_container(self, id: str, partition_key: dict, **kwargs) -> dict:
+    async def create_container(self, id: str, partition_key, **kwargs) -> "ContainerProxy":
         """Create a new container.
         
         :param str id: The container ID
-        :param dict partition_key: Partition key configuration
-        :return: Container properties
-        :rtype: dict
+        :param partition_key: The partition key path, e.g. ``"/category"``, or a partition key
+            configuration dict with a 'paths' key
+        :type partition_key: str or dict
+        :keyword int default_ttl: Time to live of items in seconds
+        :keyword int offer_throughput: Manual throughput (RU/s) to provision for the container
+        :return: A client for the new container
+        :rtype: ContainerProxy
         """
-        return await asyncio.get_event_loop().run_in_executor(
+        container_client = await asyncio.get_event_loop().run_in_executor(
             None, lambda: self._client.create_container(id, partition_key, **kwargs)
         )
+        return ContainerProxy(container_client)
     
     async def delete_container(self, container: str, **kwargs) -> None:
         """Delete a container.
@@ -122,6 +307,29 @@ class DatabaseProxy:
             None, lambda: self._client.list_containers(**kwargs)
         )
     
+    async def query_containers(self, query, **kwargs) -> list:
+        """Query the containers in this database.
+        
+        :param query: The SQL query string or a ``QueryBuilder``
+        :keyword list parameters: Query parameters as ``{"name": "@x", "value": ...}`` dicts
+        :return: List of container properties
+        :rtype: list[dict]
+        """
+        return await asyncio.get_event_loop().run_in_executor(
+            None, lambda: self._client.query_containers(query, **kwargs)
+        )
+    
+    async def list_offers(self, **kwargs) -> list:
+        """List the provisioned throughput of this database and each of its containers.
+        
+        :return: Offers with ``resource``, ``id``, ``mode`` and ``throughput``; empty on
+            serverless accounts
+        :rtype: list[dict]
+        """
+        return await asyncio.get_event_loop().run_in_executor(
+            None, lambda: self._client.list_offers(**kwargs)
+        )
+    
     async def read(self, **kwargs) -> dict:
         """Read database properties.
         
@@ -132,6 +340,19 @@ class DatabaseProxy:
             None, lambda: self._client.read(**kwargs)
         )
     
+    async def run_setup(self, steps: list) -> int:
+        """Run a sequence of setup steps in order, e.g. for test fixtures.
+        
+        See :meth:`azure.cosmos.DatabaseProxy.run_setup` for the step format.
+        
+        :param list[dict] steps: The steps to run
+        :return: The number of steps run
+        :rtype: int
+        """
+        return await asyncio.get_event_loop().run_in_executor(
+            None, lambda: self._client.run_setup(steps)
+        )
+    
     async def delete(self, **kwargs) -> None:
         """Delete this database."""
         return await asyncio.get_event_loop().run_in_executor(
@@ -142,6 +363,15 @@ class DatabaseProxy:
 class ContainerProxy:
     """Async proxy to interact with a specific container.
     
+    Item and query operations accept the same per-operation keyword arguments as
+    :class:`azure.cosmos.ContainerProxy`, such as ``throughput_bucket``.
+    
+    Operations follow asyncio cancellation: cancelling the task awaiting an operation aborts
+    its in-flight request and raises ``asyncio.CancelledError``. They also accept a
+    ``timeout`` keyword in seconds with ``asyncio.wait_for`` semantics: when it expires the
+    request is aborted and ``asyncio.TimeoutError`` is raised. A write aborted after it was
+    sent may still have been applied by the service.
+    
     :param _RustContainerClient client: The underlying Rust container client
     """
     
@@ -153,6 +383,25 @@ class ContainerProxy:
         """Get the container ID."""
         return self._client.id
     
+    async def resolve_partition_key(self, body: dict, **kwargs):
+        """Resolve the partition key a write of ``body`` would use, without writing it.
+        
+        :param dict body: The item
+        :return: The partition key value, or a list of the components for a hierarchical key
+        :raises ValueError: The key cannot be resolved or does not fit the container
+        """
+        return await _run_cancellable(self._client.resolve_partition_key, body, **kwargs)
+    
+    async def get_item_partition_key(self, item: dict):
+        """Return the partition key an item is stored under.
+        
+        :param dict item: The item, as returned by a read or query
+        :return: The partition key value, or a list of the components for a hierarchical key
+        """
+        return await asyncio.get_event_loop().run_in_executor(
+            None, lambda: self._client.get_item_partition_key(item)
+        )
+    
     async def create_item(self, body: dict, **kwargs) -> dict:
         """Create a new item.
         
@@ -160,21 +409,40 @@ class ContainerProxy:
         :return: The created item
         :rtype: dict
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.create_item(body, **kwargs)
-        )
+        return await _run_cancellable(self._client.create_item, body, **kwargs)
     
     async def read_item(self, item: str, partition_key, **kwargs) -> dict:
         """Read an item.
         
         :param str item: The item ID
         :param partition_key: The partition key value
-        :return: The item
+        :keyword list[str] fields: Only deserialize these top-level fields of the item
+        :keyword str json_path: Return only the subtree at this path
+        :keyword bool json_path_missing_ok: Return None instead of raising when the path is missing
+        :return: The item, or the subtree selected by ``json_path``
         :rtype: dict
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.read_item(item, partition_key, **kwargs)
-        )
+        return await _run_cancellable(self._client.read_item, item, partition_key, **kwargs)
+    
+    async def read_item_with_etag(self, item: str, partition_key, **kwargs) -> tuple:
+        """Read an item together with its entity tag.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :return: ``(item, etag)``
+        :rtype: tuple[dict, str]
+        """
+        return await _run_cancellable(self._client.read_item_with_etag, item, partition_key, **kwargs)
+    
+    async def read_item_bytes(self, item: str, partition_key, **kwargs) -> bytes:
+        """Read an item as the raw JSON response body, without parsing it.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :return: The UTF-8 encoded JSON document; read options are not applied
+        :rtype: bytes
+        """
+        return await _run_cancellable(self._client.read_item_bytes, item, partition_key, **kwargs)
     
     async def upsert_item(self, body: dict, **kwargs) -> dict:
         """Upsert an item.
@@ -183,9 +451,7 @@ class ContainerProxy:
         :return: The upserted item
         :rtype: dict
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.upsert_item(body, **kwargs)
-        )
+        return await _run_cancellable(self._client.upsert_item, body, **kwargs)
     
     async def replace_item(self, item: str, body: dict, **kwargs) -> dict:
         """Replace an item.
@@ -195,9 +461,7 @@ class ContainerProxy:
         :return: The replaced item
         :rtype: dict
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.replace_item(item, body, **kwargs)
-        )
+        return await _run_cancellable(self._client.replace_item, item, body, **kwargs)
     
     async def delete_item(self, item: str, partition_key, **kwargs) -> None:
         """Delete an item.
@@ -205,21 +469,140 @@ class ContainerProxy:
         :param str item: The item ID
         :param partition_key: The partition key value
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.delete_item(item, partition_key, **kwargs)
-        )
+        return await _run_cancellable(self._client.delete_item, item, partition_key, **kwargs)
     
     async def query_items(self, query: str, **kwargs) -> list:
         """Query items.
         
         :param str query: SQL query string
+        :keyword bool single_page: Return ``(items, continuation_token)`` for the first page only
         :return: List of items
         :rtype: list[dict]
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.query_items(query, **kwargs)
+        return await _run_cancellable(self._client.query_items, query, **kwargs)
+    
+    async def query_items_columns(self, query: str, **kwargs) -> dict:
+        """Query items, returning the results as columns.
+        
+        :param str query: SQL query string
+        :return: Column name to list of values, ``None`` where a row lacks the property
+        :rtype: dict[str, list]
+        """
+        return await _run_cancellable(self._client.query_items_columns, query, **kwargs)
+    
+    async def query_items_paged(
+        self, query: str, max_item_count: int = None, continuation: str = None, **kwargs
+    ):
+        """Fetch a single page of query results.
+        
+        :param str query: SQL query string
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page, with its items, continuation token, RU charge and activity id
+        :rtype: ~azure.cosmos.QueryPage
+        """
+        return await _run_cancellable(
+            self._client.query_items_paged, query, max_item_count, continuation, **kwargs
+        )
+    
+    async def query_items_page(
+        self, query: str, max_item_count: int = None, continuation: str = None, **kwargs
+    ) -> tuple:
+        """Fetch a single page of query results as ``(items, continuation_token)``.
+        
+        :param str query: SQL query string
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page items and the token for the next page, or None when exhausted
+        :rtype: tuple[list[dict], str]
+        """
+        return await _run_cancellable(
+            self._client.query_items_page, query, max_item_count, continuation, **kwargs
+        )
+    
+    async def query_items_iter(self, query, max_item_count: int = None, **kwargs):
+        """Iterate over query results with ``async for``, fetching pages lazily.
+        
+        Each page is fetched on the default executor when the previous one has been consumed.
+        
+        :param query: SQL query string or a ``QueryBuilder``
+        :param int max_item_count: Maximum number of items per page
+        :return: An async iterator of item dicts
+        """
+        iterator = self._client.query_items_iter(query, max_item_count, **kwargs)
+        loop = asyncio.get_running_loop()
+        while True:
+            item = await loop.run_in_executor(None, next, iterator, _EXHAUSTED)
+            if item is _EXHAUSTED:
+                return
+            yield item
+    
+    async def query_items_foreach(self, query, callback, **kwargs) -> int:
+        """Call ``callback`` with each query result as pages arrive.
+        
+        The callback runs on an executor thread, not on the event loop.
+        
+        :param query: SQL query string or :class:`~azure.cosmos.QueryBuilder`
+        :param callable callback: Called with each item
+        :return: The number of items passed to the callback
+        :rtype: int
+        """
+        return await _run_cancellable(self._client.query_items_foreach, query, callback, **kwargs)
+    
+    async def partition_key_delete_status(self, partition_key, **kwargs) -> dict:
+        """Check whether a delete of all items in a logical partition has completed.
+        
+        :param partition_key: The partition key value whose delete to check
+        :return: Dict with ``pending`` (bool) and ``remaining_items`` (int)
+        :rtype: dict
+        """
+        return await _run_cancellable(self._client.partition_key_delete_status, partition_key, **kwargs)
+    
+    async def query_items_partial(self, query, on_partial, **kwargs):
+        """Run an aggregate query, reporting the running result as partitions complete.
+        
+        ``on_partial`` runs on an executor thread, not on the event loop. Intermediate values
+        are partial and change as more partitions answer.
+        
+        :param query: SQL query string or :class:`~azure.cosmos.QueryBuilder`
+        :param callable on_partial: Called with the running aggregate
+        :return: The final aggregate
+        """
+        return await _run_cancellable(self._client.query_items_partial, query, on_partial, **kwargs)
+    
+    async def read_all_items(self, max_item_count: int = None, **kwargs) -> list:
+        """Read every item in the container.
+        
+        :param int max_item_count: Maximum number of items per page
+        :return: All items in the container
+        :rtype: list[dict]
+        """
+        return await _run_cancellable(self._client.read_all_items, max_item_count, **kwargs)
+    
+    async def read_all_items_paged(
+        self, max_item_count: int = None, continuation: str = None, **kwargs
+    ):
+        """Read a single page of the container's items.
+        
+        :param int max_item_count: Maximum number of items on the page
+        :param str continuation: Continuation token returned with the previous page
+        :return: The page and its continuation token
+        :rtype: ~azure.cosmos.QueryPage
+        """
+        return await _run_cancellable(
+            self._client.read_all_items_paged, max_item_count, continuation, **kwargs
         )
     
+    async def read_items_by_ids(self, ids: list, parameters: list = None, **kwargs) -> list:
+        """Read items by id using cross-partition queries.
+        
+        :param list[str] ids: The item IDs
+        :param list parameters: Further query parameters bound alongside the ids
+        :return: The matching items, in no particular order
+        :rtype: list[dict]
+        """
+        return await _run_cancellable(self._client.read_items_by_ids, ids, parameters, **kwargs)
+    
     async def patch_item(self, item: str, partition_key, patch_operations: list, **kwargs) -> dict:
         """Patch an item.
         
@@ -229,8 +612,24 @@ class ContainerProxy:
         :return: The patched item
         :rtype: dict
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.patch_item(item, partition_key, patch_operations, **kwargs)
+        return await _run_cancellable(
+            self._client.patch_item, item, partition_key, patch_operations, **kwargs
+        )
+    
+    async def patch_item_append_unique(
+        self, item: str, partition_key, path: str, value, **kwargs
+    ) -> dict:
+        """Append a value to an array in an item unless the array already contains it.
+        
+        :param str item: The item ID
+        :param partition_key: The partition key value
+        :param str path: Path of the array, e.g. ``"/tags"``
+        :param value: The value to append
+        :return: The patched item, or the unchanged item if the value was already present
+        :rtype: dict
+        """
+        return await _run_cancellable(
+            self._client.patch_item_append_unique, item, partition_key, path, value, **kwargs
         )
     
     async def read(self, **kwargs) -> dict:
@@ -239,12 +638,18 @@ class ContainerProxy:
         :return: Container properties
         :rtype: dict
         """
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.read(**kwargs)
-        )
+        return await _run_cancellable(self._client.read, **kwargs)
+    
+    async def wait_until_ready(self, timeout=None, **kwargs) -> float:
+        """Wait until the container's indexing transformation has finished.
+        
+        :param timeout: Give up after this many seconds (or ``datetime.timedelta``)
+        :return: Seconds spent waiting
+        :rtype: float
+        :raises CosmosTimeoutError: The transformation did not finish within ``timeout``
+        """
+        return await _run_cancellable(self._client.wait_until_ready, timeout, **kwargs)
     
     async def delete(self, **kwargs) -> None:
         """Delete this container."""
-        return await asyncio.get_event_loop().run_in_executor(
-            None, lambda: self._client.delete(**kwargs)
-        )
+        return await _run_cancellable(self._client.delete, **kwargs)
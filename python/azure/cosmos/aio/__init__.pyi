@@ -1,7 +1,8 @@
 """Type stubs for async Azure Cosmos DB operations."""
 
-from typing import Any, Dict, List, Optional, Union, TypeVar
+from typing import Any, AsyncIterator, Callable, Dict, List, Optional, Tuple, Union, TypeVar
 from typing_extensions import Self
+from azure.cosmos import QueryBuilder, QueryPage
 
 _T = TypeVar("_T")
 
@@ -15,6 +16,16 @@ class CosmosClient:
         **kwargs: Any
     ) -> None: ...
     
+    @classmethod
+    def from_emulator(cls, endpoint: str = "https://localhost:8081", **kwargs: Any) -> Self:
+        """Create a client for the local Cosmos DB emulator."""
+        ...
+    
+    @classmethod
+    def from_connection_string(cls, conn_str: str, **kwargs: Any) -> Self:
+        """Create a client from an ``AccountEndpoint=...;AccountKey=...;`` connection string."""
+        ...
+    
     async def __aenter__(self) -> Self: ...
     
     async def __aexit__(
@@ -39,6 +50,27 @@ class CosmosClient:
     async def list_databases(self, **kwargs: Any) -> List[Dict[str, Any]]:
         """List all databases asynchronously."""
         ...
+    
+    def get_last_requests(self, n: Optional[int] = None) -> List[Dict[str, Any]]:
+        """Get the most recently sent requests."""
+        ...
+    
+    def get_metrics(self) -> Dict[str, Any]:
+        """Get the request totals accumulated since the client was created."""
+        ...
+    
+    def in_flight_count(self) -> int:
+        """Get the number of operations currently running on this client."""
+        ...
+    
+    def cancel_all(self) -> None:
+        """Cancel every operation currently running on this client."""
+        ...
+    
+    @property
+    def circuit_breaker_state(self) -> Optional[str]:
+        """State of the circuit breaker, or None when it is not enabled."""
+        ...
 
 
 class DatabaseProxy:
@@ -49,16 +81,20 @@ class DatabaseProxy:
         """Get the database ID."""
         ...
     
-    def get_container_client(self, container: str) -> ContainerProxy:
+    def get_container_client(
+        self,
+        container: str,
+        partition_key_type: Optional[str] = None
+    ) -> ContainerProxy:
         """Get a container client."""
         ...
     
     async def create_container(
         self,
         id: str,
-        partition_key: Dict[str, Any],
+        partition_key: Union[str, Dict[str, Any]],
         **kwargs: Any
-    ) -> Dict[str, Any]:
+    ) -> ContainerProxy:
         """Create a new container asynchronously."""
         ...
     
@@ -70,10 +106,22 @@ class DatabaseProxy:
         """List all containers asynchronously."""
         ...
     
+    async def query_containers(self, query: Union[str, QueryBuilder], **kwargs: Any) -> List[Dict[str, Any]]:
+        """Query the containers in this database asynchronously."""
+        ...
+    
+    async def list_offers(self, **kwargs: Any) -> List[Dict[str, Any]]:
+        """List the provisioned throughput of the database and its containers asynchronously."""
+        ...
+    
     async def read(self, **kwargs: Any) -> Dict[str, Any]:
         """Read database properties asynchronously."""
         ...
     
+    async def run_setup(self, steps: List[Dict[str, Any]]) -> int:
+        """Run a sequence of setup steps in order asynchronously."""
+        ...
+    
     async def delete(self, **kwargs: Any) -> None:
         """Delete this database asynchronously."""
         ...
@@ -87,20 +135,46 @@ class ContainerProxy:
         """Get the container ID."""
         ...
     
-    async def create_item(self, body: Dict[str, Any], **kwargs: Any) -> Dict[str, Any]:
+    async def resolve_partition_key(self, body: Union[Dict[str, Any], str], **kwargs: Any) -> Any:
+        """Resolve the partition key a write of ``body`` would use asynchronously."""
+        ...
+    
+    async def get_item_partition_key(self, item: Dict[str, Any]) -> Any:
+        """Return the partition key an item is stored under asynchronously."""
+        ...
+    
+    async def create_item(self, body: Dict[str, Any], **kwargs: Any) -> Optional[Dict[str, Any]]:
         """Create a new item asynchronously."""
         ...
     
     async def read_item(
         self,
         item: str,
-        partition_key: Union[str, int, float],
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
         **kwargs: Any
-    ) -> Dict[str, Any]:
+    ) -> Any:
         """Read an item asynchronously."""
         ...
     
-    async def upsert_item(self, body: Dict[str, Any], **kwargs: Any) -> Dict[str, Any]:
+    async def read_item_with_etag(
+        self,
+        item: str,
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
+        **kwargs: Any
+    ) -> Tuple[Any, Optional[str]]:
+        """Read an item and its entity tag asynchronously."""
+        ...
+    
+    async def read_item_bytes(
+        self,
+        item: str,
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
+        **kwargs: Any
+    ) -> bytes:
+        """Read an item as the raw JSON response body asynchronously."""
+        ...
+    
+    async def upsert_item(self, body: Dict[str, Any], **kwargs: Any) -> Optional[Dict[str, Any]]:
         """Upsert an item asynchronously."""
         ...
     
@@ -109,37 +183,123 @@ class ContainerProxy:
         item: str,
         body: Dict[str, Any],
         **kwargs: Any
-    ) -> Dict[str, Any]:
+    ) -> Optional[Dict[str, Any]]:
         """Replace an item asynchronously."""
         ...
     
     async def delete_item(
         self,
         item: str,
-        partition_key: Union[str, int, float],
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
         **kwargs: Any
     ) -> None:
         """Delete an item asynchronously."""
         ...
     
-    async def query_items(self, query: str, **kwargs: Any) -> List[Dict[str, Any]]:
+    async def query_items(
+        self, query: Union[str, QueryBuilder], **kwargs: Any
+    ) -> Union[List[Dict[str, Any]], Tuple[List[Dict[str, Any]], Optional[str]]]:
         """Query items asynchronously."""
         ...
     
+    async def query_items_columns(self, query: Union[str, QueryBuilder], **kwargs: Any) -> Dict[str, List[Any]]:
+        """Query items asynchronously, returning the results as columns."""
+        ...
+    
+    async def query_items_paged(
+        self,
+        query: str,
+        max_item_count: Optional[int] = None,
+        continuation: Optional[str] = None,
+        **kwargs: Any
+    ) -> QueryPage:
+        """Fetch a single page of query results asynchronously."""
+        ...
+    
+    async def query_items_page(
+        self,
+        query: str,
+        max_item_count: Optional[int] = None,
+        continuation: Optional[str] = None,
+        **kwargs: Any
+    ) -> Tuple[List[Dict[str, Any]], Optional[str]]:
+        """Fetch a single page of query results as ``(items, continuation_token)`` asynchronously."""
+        ...
+    
+    def query_items_iter(
+        self, query: Union[str, QueryBuilder], max_item_count: Optional[int] = None, **kwargs: Any
+    ) -> AsyncIterator[Dict[str, Any]]:
+        """Iterate over query results with ``async for``, fetching pages lazily."""
+        ...
+    
+    async def query_items_foreach(
+        self,
+        query: Union[str, QueryBuilder],
+        callback: Callable[[Dict[str, Any]], Any],
+        **kwargs: Any
+    ) -> int:
+        """Call a callback with each query result asynchronously."""
+        ...
+    
+    async def partition_key_delete_status(self, partition_key: Any, **kwargs: Any) -> Dict[str, Any]:
+        """Check whether a partition's delete has completed asynchronously."""
+        ...
+    
+    async def query_items_partial(
+        self,
+        query: Union[str, QueryBuilder],
+        on_partial: Callable[[Any], Any],
+        **kwargs: Any
+    ) -> Any:
+        """Run an aggregate query, reporting the running result asynchronously."""
+        ...
+    
+    async def read_all_items(self, max_item_count: Optional[int] = None, **kwargs: Any) -> List[Dict[str, Any]]:
+        """Read every item in the container asynchronously."""
+        ...
+    
+    async def read_all_items_paged(
+        self,
+        max_item_count: Optional[int] = None,
+        continuation: Optional[str] = None,
+        **kwargs: Any
+    ) -> QueryPage:
+        """Read a single page of the container's items asynchronously."""
+        ...
+    
+    async def read_items_by_ids(self, ids: List[str], **kwargs: Any) -> List[Dict[str, Any]]:
+        """Read items by id using cross-partition queries asynchronously."""
+        ...
+    
     async def patch_item(
         self,
         item: str,
-        partition_key: Union[str, int, float],
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
         patch_operations: List[Dict[str, Any]],
         **kwargs: Any
     ) -> Dict[str, Any]:
         """Patch an item asynchronously."""
         ...
     
+    async def patch_item_append_unique(
+        self,
+        item: str,
+        partition_key: Union[str, int, float, List[Union[str, int, float]]],
+        path: str,
+        value: Any,
+        **kwargs: Any
+    ) -> Dict[str, Any]:
+        """Append a value to an array unless already present, asynchronously."""
+        ...
+    
     async def read(self, **kwargs: Any) -> Dict[str, Any]:
         """Read container properties asynchronously."""
         ...
     
+    async def wait_until_ready(self, timeout: Optional[float] = None, **kwargs: Any) -> float:
+        """Wait until the container's indexing transformation reaches 100% asynchronously."""
+        ...
+    
     async def delete(self, **kwargs: Any) -> None:
         """Delete this container asynchronously."""
         ...
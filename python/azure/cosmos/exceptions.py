@@ -5,6 +5,12 @@ from azure.cosmos._rust import (
     CosmosResourceNotFoundError,
     CosmosResourceExistsError,
     CosmosAccessConditionFailedError,
+    CosmosServiceUnavailableError,
+    CosmosInvalidContinuationTokenError,
+    CosmosResponseTooLargeError,
+    CosmosDeserializationError,
+    CosmosTimeoutError,
+    CosmosReadOnlyError,
 )
 
 __all__ = [
@@ -12,4 +18,10 @@ __all__ = [
     "CosmosResourceNotFoundError",
     "CosmosResourceExistsError",
     "CosmosAccessConditionFailedError",
+    "CosmosServiceUnavailableError",
+    "CosmosInvalidContinuationTokenError",
+    "CosmosResponseTooLargeError",
+    "CosmosDeserializationError",
+    "CosmosTimeoutError",
+    "CosmosReadOnlyError",
 ]
@@ -1,8 +1,25 @@
 """Type stubs for exceptions."""
 
+from typing import Optional
+
 class CosmosHttpResponseError(Exception):
     """Base exception for Cosmos DB HTTP response errors."""
-    ...
+    
+    status_code: Optional[int]
+    """HTTP status code of the failed request's response, e.g. 404. ``None`` when the request
+    failed without a response, or was refused by the client (e.g. an open circuit breaker)."""
+    message: str
+    """The error message."""
+    activity_id: Optional[str]
+    """Service activity id of the failed request, when the response was received."""
+    request_charge: Optional[float]
+    """RU charged for the failed request (``x-ms-request-charge``); throttled (429) and
+    rejected (e.g. 413) requests are still billed."""
+    retry_after_ms: Optional[float]
+    """How long the service asked the client to wait before retrying, in milliseconds
+    (``x-ms-retry-after-ms``); set on throttled (429) responses."""
+    correlation_id: Optional[str]
+    """The ``correlation_id`` keyword passed to the failed operation."""
 
 class CosmosResourceNotFoundError(CosmosHttpResponseError):
     """Exception raised when a resource is not found (404)."""
@@ -13,5 +30,45 @@ class CosmosResourceExistsError(CosmosHttpResponseError):
     ...
 
 class CosmosAccessConditionFailedError(CosmosHttpResponseError):
-    """Exception raised when an access condition fails (412)."""
+    """Exception raised when an access condition fails (412), e.g. a write with an ``etag``
+    that no longer matches the stored item."""
+    
+    etag: Optional[str]
+    """The item's current etag, when the service reported it in the response."""
+
+class CosmosServiceUnavailableError(CosmosHttpResponseError):
+    """Exception raised when the service is unavailable (503), or without sending the request
+    while the client's circuit breaker is open."""
+    ...
+
+class CosmosInvalidContinuationTokenError(CosmosHttpResponseError):
+    """Exception raised when a query cannot resume from its continuation token, e.g. one that
+    was corrupted or saved by an incompatible SDK version. Restart the query without it."""
     ...
+
+class CosmosResponseTooLargeError(CosmosHttpResponseError):
+    """Exception raised when a response body is larger than the client's
+    ``max_response_size_bytes``. At most the limit of the body is read."""
+    ...
+
+class CosmosDeserializationError(ValueError):
+    """Exception raised when a read document cannot be converted, e.g. a number beyond the
+    range of a 64-bit float or nesting deeper than 128 levels."""
+    
+    path: str
+    """JSONPath of the value that could not be converted, e.g. ``$.a.b[0]``."""
+    reason: str
+    """Why the value could not be converted."""
+
+class CosmosTimeoutError(TimeoutError):
+    """Exception raised when an operation does not complete within its ``operation_timeout``."""
+    
+    operation: str
+    """Name of the operation that timed out, e.g. ``read_item``."""
+
+class CosmosReadOnlyError(PermissionError):
+    """Exception raised by a client created with ``read_only=True`` for an operation that
+    would create, change or delete a resource. Nothing is sent to the service."""
+    
+    operation: str
+    """Name of the rejected operation, e.g. ``create_item``."""
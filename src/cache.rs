@@ -0,0 +1,68 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Most query results a client keeps cached
+const MAX_CACHED_QUERIES: usize = 1024;
+
+/// When a query result was fetched, and the result itself
+type CacheEntry = (Instant, Arc<Vec<Value>>);
+
+/// In-process cache of query results, enabled by `enable_query_cache=True`
+/// Entries are keyed by container, SQL text, parameters and result-changing options, and expire
+/// `ttl` after they were fetched. Writes do not invalidate entries, so hits may be stale. At most
+/// `MAX_CACHED_QUERIES` results are kept, evicting the oldest.
+#[derive(Debug)]
+pub struct QueryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl QueryCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Results cached under `key` that have not expired, counting the lookup as a hit or miss
+    pub fn get(&self, key: &str) -> Option<Arc<Vec<Value>>> {
+        let mut entries = self.entries.lock().unwrap();
+        let cached = match entries.get(key) {
+            Some((fetched, results)) if fetched.elapsed() < self.ttl => Some(results.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        let counter = if cached.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// Cache `results` under `key`, dropping expired entries, and the oldest entry when full
+    pub fn insert(&self, key: String, results: Arc<Vec<Value>>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (fetched, _)| fetched.elapsed() < self.ttl);
+        if entries.len() >= MAX_CACHED_QUERIES && !entries.contains_key(&key) {
+            let oldest = entries.iter().min_by_key(|(_, (fetched, _))| *fetched).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (Instant::now(), results));
+    }
+
+    /// Lookups answered from the cache and lookups that had to query, since creation
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits.load(Ordering::Relaxed), self.misses.load(Ordering::Relaxed))
+    }
+}
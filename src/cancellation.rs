@@ -0,0 +1,162 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::sync::watch;
+use crate::config::{positive_duration, ClientConfig};
+use crate::exceptions::timeout_error;
+
+/// Build a module's shared runtime, naming its threads `cosmos-<component>-worker-<n>`
+/// so a hung operation can be attributed from a thread dump
+pub fn build_runtime(component: &'static str) -> Runtime {
+    let next_worker = AtomicUsize::new(0);
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name_fn(move || format!("cosmos-{}-worker-{}", component, next_worker.fetch_add(1, Ordering::Relaxed)))
+        .build()
+        .expect("Failed to create Tokio runtime")
+}
+
+/// Aborts the operations it is passed to as `cancellation_token`
+/// The async client creates one per call and cancels it when the awaiting task is cancelled
+/// or its `timeout` expires, so the in-flight request is dropped instead of running on.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+#[pymethods]
+impl CancellationToken {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            sender: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Abort the operations using this token; operations started afterwards fail immediately
+    pub fn cancel(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Whether `cancel` has been called
+    #[getter]
+    pub fn cancelled(&self) -> bool {
+        *self.sender.borrow()
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    /// The token passed as the `cancellation_token` kwarg, if any
+    pub fn from_kwargs(kwargs: Option<&PyDict>) -> PyResult<Option<Self>> {
+        match kwargs {
+            Some(kw) => kw.get_item("cancellation_token")?.map(|token| token.extract()).transpose(),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolves once the token is cancelled
+    async fn wait(&self) {
+        let mut receiver = self.sender.subscribe();
+        // The sender lives as long as `self`, so waiting cannot fail
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// The operations of a client that are currently running, shared through `ClientConfig`
+#[derive(Debug, Default)]
+pub struct InFlightOperations {
+    count: AtomicUsize,
+    /// Token of the operations started since the last `cancel_all`
+    token: Mutex<CancellationToken>,
+}
+
+impl InFlightOperations {
+    /// Number of operations currently running
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// Cancel every running operation; operations started afterwards are not affected
+    pub fn cancel_all(&self) {
+        let mut token = self.token.lock().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *token).cancel();
+    }
+
+    /// Register a starting operation, returning the token `cancel_all` cancels it through
+    fn begin(&self) -> (InFlightGuard<'_>, CancellationToken) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        let token = self.token.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        (InFlightGuard(&self.count), token)
+    }
+}
+
+/// Unregisters an operation when it finishes or is dropped
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Run an operation to completion on `runtime` with the GIL released
+/// The operation is counted in the client's in-flight operations while it runs. If `kwargs`
+/// carries a `cancellation_token`, cancelling it, or calling `cancel_all` on the client, drops
+/// the operation's future, aborting the request, and raises `asyncio.CancelledError`.
+/// An `operation_timeout` kwarg, or else the client's, bounds the whole operation including
+/// retries; exceeding it drops the future and raises `CosmosTimeoutError` naming `name`.
+pub fn run_operation<T, F>(
+    py: Python,
+    runtime: &Runtime,
+    config: &ClientConfig,
+    name: &str,
+    kwargs: Option<&PyDict>,
+    operation: F,
+) -> PyResult<T>
+where
+    T: Send,
+    F: Future<Output = PyResult<T>> + Send,
+{
+    let token = CancellationToken::from_kwargs(kwargs)?;
+    let timeout = match kwargs.map(|kw| kw.get_item("operation_timeout")).transpose()?.flatten() {
+        Some(value) => Some(positive_duration(value, "operation_timeout")?),
+        None => config.operation_timeout,
+    };
+    let operation = async move {
+        let Some(timeout) = timeout else {
+            return operation.await;
+        };
+        tokio::time::timeout(timeout, operation).await.unwrap_or_else(|_| Err(timeout_error(name, timeout)))
+    };
+    let (_guard, client_token) = config.operations.begin();
+    py.allow_threads(|| {
+        runtime.block_on(async move {
+            let cancelled = async {
+                match token {
+                    Some(token) => tokio::select! {
+                        _ = token.wait() => {},
+                        _ = client_token.wait() => {},
+                    },
+                    None => client_token.wait().await,
+                }
+            };
+            tokio::select! {
+                biased;
+                _ = cancelled => Err(PyErr::new::<pyo3::exceptions::asyncio::CancelledError, _>(
+                    "The operation was cancelled"
+                )),
+                result = operation => result,
+            }
+        })
+    })
+}
@@ -1,23 +1,64 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use azure_data_cosmos::CosmosClient as RustCosmosClient;
+use azure_data_cosmos::models::ThroughputProperties;
+use azure_data_cosmos::CreateDatabaseOptions;
+use serde_json::Value;
 use std::sync::Arc;
+use std::time::Duration;
+use url::{Host, Url};
+use crate::config::ClientConfig;
+use crate::credentials::PyTokenCredential;
 use crate::database::DatabaseClient;
 use crate::exceptions::map_error;
+use crate::properties::{database_properties, database_properties_value};
+use crate::utils::json_to_py_dict;
 use once_cell::sync::Lazy;
 use tokio::runtime::Runtime;
+use crate::cancellation::{build_runtime, run_operation};
 
-static TOKIO_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
-    tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .expect("Failed to create Tokio runtime")
-});
+static TOKIO_RUNTIME: Lazy<Runtime> = Lazy::new(|| build_runtime("client"));
+
+/// Well-known account key of the local Cosmos DB emulator
+const EMULATOR_KEY: &str = "C2y6yDjf5/R+ob0N8A7Cgv30VRDJIWEHLM+4QDU5DE2nQ9nDuVTqobD4b8mGGyPMbIZnqyMsEcaGQy67XIw/Jw==";
+
+/// Connection timeout used by `from_emulator` unless one is given
+const EMULATOR_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Split a connection string into its `AccountEndpoint` and `AccountKey` values
+/// Segments are `name=value` pairs separated by `;`; whitespace around them and empty segments,
+/// such as the one after a trailing `;`, are ignored. Keys keep their `=` padding.
+fn parse_connection_string(conn_str: &str) -> PyResult<(String, String)> {
+    let mut endpoint = None;
+    let mut key = None;
+    let segments = conn_str.split(';').map(str::trim).filter(|segment| !segment.is_empty());
+    for (index, segment) in segments.enumerate() {
+        // The segment is not echoed, as it may be a misplaced key
+        let Some((name, value)) = segment.split_once('=') else {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid connection string segment {}; expected name=value", index
+            )));
+        };
+        match name.trim() {
+            name if name.eq_ignore_ascii_case("AccountEndpoint") => endpoint = Some(value.trim().to_string()),
+            name if name.eq_ignore_ascii_case("AccountKey") => key = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    let missing = |field: &str| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Connection string is missing {}", field))
+    };
+    let endpoint = endpoint.filter(|value| !value.is_empty()).ok_or_else(|| missing("AccountEndpoint"))?;
+    let key = key.filter(|value| !value.is_empty()).ok_or_else(|| missing("AccountKey"))?;
+    Ok((endpoint, key))
+}
 
 #[pyclass(subclass)]
 pub struct CosmosClient {
     inner: Arc<RustCosmosClient>,
     endpoint: String,
+    config: Arc<ClientConfig>,
 }
 
 #[pymethods]
@@ -29,15 +70,21 @@ impl CosmosClient {
         credential: Option<PyObject>,
         kwargs: Option<&PyDict>,
     ) -> PyResult<Self> {
+        let config = ClientConfig::from_kwargs(kwargs)?;
+
         Python::with_gil(|py| {
             let client = if let Some(cred) = credential {
                 // Check if credential is a string (key-based auth)
                 if let Ok(key) = cred.extract::<String>(py) {
-                    RustCosmosClient::with_key(&url, key.into(), None)
+                    RustCosmosClient::with_key(&url, key.into(), Some(config.cosmos_client_options()?))
+                        .map_err(map_error)?
+                } else if let Some(token_credential) = PyTokenCredential::from_object(py, &cred)? {
+                    // Azure AD credentials, such as those from azure.identity
+                    RustCosmosClient::new(&url, Arc::new(token_credential), Some(config.cosmos_client_options()?))
                         .map_err(map_error)?
                 } else {
                     return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                        "Only key-based authentication is currently supported"
+                        "credential must be an account key string or a credential with a get_token method"
                     ));
                 }
             } else {
@@ -49,33 +96,126 @@ impl CosmosClient {
             Ok(Self {
                 inner: Arc::new(client),
                 endpoint: url,
+                config: Arc::new(config),
             })
         })
     }
 
+    /// Create a client for the local emulator, using its well-known key
+    /// TLS verification is disabled for the emulator's self-signed certificate, so only
+    /// loopback endpoints are accepted.
+    #[staticmethod]
+    #[pyo3(signature = (endpoint="https://localhost:8081", **kwargs))]
+    pub fn from_emulator(py: Python, endpoint: &str, kwargs: Option<&PyDict>) -> PyResult<Self> {
+        let url = Url::parse(endpoint).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid emulator endpoint: {}", e))
+        })?;
+        let is_loopback = match url.host() {
+            Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+            Some(Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        };
+        if !is_loopback {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "from_emulator only connects to localhost, got '{}'", endpoint
+            )));
+        }
+
+        let kwargs = match kwargs {
+            Some(kw) => kw.copy()?,
+            None => PyDict::new(py),
+        };
+        kwargs.set_item("connection_verify", false)?;
+        if !kwargs.contains("connection_timeout")? {
+            kwargs.set_item("connection_timeout", EMULATOR_CONNECTION_TIMEOUT.as_secs())?;
+        }
+        Self::new(endpoint.to_string(), Some(EMULATOR_KEY.into_py(py)), Some(kwargs))
+    }
+
+    /// Create a client from a connection string such as "AccountEndpoint=...;AccountKey=...;"
+    /// Segment names are matched case-insensitively; other segments are ignored.
+    #[staticmethod]
+    #[pyo3(signature = (conn_str, **kwargs))]
+    pub fn from_connection_string(py: Python, conn_str: &str, kwargs: Option<&PyDict>) -> PyResult<Self> {
+        let (endpoint, key) = parse_connection_string(conn_str)?;
+        Self::new(endpoint, Some(key.into_py(py)), kwargs)
+    }
+
     /// Create a new database
+    /// `offer_throughput` provisions manual throughput (RU/s) shared by the database's containers.
+    /// With `populate_properties=True` a `(client, properties)` tuple is returned, where the
+    /// properties are the server-assigned ones (`_rid`, `_etag`, ...) plus, when throughput was
+    /// requested, the `offer_throughput` read back from the created offer.
     #[pyo3(signature = (id, **kwargs))]
     pub fn create_database(
         &self,
+        py: Python,
         id: String,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<DatabaseClient> {
+    ) -> PyResult<PyObject> {
+        self.config.check_writable("create_database")?;
         let client = self.inner.clone();
         let id_clone = id.clone();
         
-        let _result = TOKIO_RUNTIME.block_on(async move {
-            client.create_database(&id_clone, None)
+        let mut offer_throughput = None;
+        let mut populate_properties = false;
+        if let Some(kw) = kwargs {
+            if let Some(value) = kw.get_item("offer_throughput")? {
+                offer_throughput = Some(value.extract::<usize>()?);
+            }
+            if let Some(value) = kw.get_item("populate_properties")? {
+                populate_properties = value.extract()?;
+            }
+        }
+        let options = offer_throughput.map(|throughput| CreateDatabaseOptions {
+            throughput: Some(ThroughputProperties::manual(throughput)),
+            ..Default::default()
+        });
+        
+        let (properties, confirmed_throughput) = run_operation(py, &TOKIO_RUNTIME, &self.config, "create_database", kwargs, async move {
+            let properties = client.create_database(&id_clone, options)
                 .await
-                .map_err(map_error)
+                .map_err(map_error)?
+                .into_body()
+                .json::<Value>()
+                .map_err(map_error)?;
+            
+            // Read the offer back so callers can assert it was provisioned as requested
+            let offer = match offer_throughput {
+                Some(_) => client.database_client(&id_clone)
+                    .read_throughput(None)
+                    .await
+                    .map_err(map_error)?,
+                None => None,
+            };
+            let confirmed_throughput = match offer {
+                Some(offer) => offer.into_body()
+                    .json::<ThroughputProperties>()
+                    .map_err(map_error)?
+                    .throughput(),
+                None => None,
+            };
+            
+            Ok::<_, PyErr>((properties, confirmed_throughput))
         })?;
 
         // Return DatabaseClient like V4 does
-        Ok(DatabaseClient::new(self.inner.clone(), id))
+        let database = DatabaseClient::new(self.inner.clone(), self.config.clone(), id).into_py(py);
+        if !populate_properties {
+            return Ok(database);
+        }
+        
+        let properties = json_to_py_dict(py, &properties)?;
+        if offer_throughput.is_some() {
+            properties.as_ref(py).set_item("offer_throughput", confirmed_throughput)?;
+        }
+        Ok((database, properties).into_py(py))
     }
 
     /// Get a database client
     pub fn get_database_client(&self, database_id: String) -> PyResult<DatabaseClient> {
-        Ok(DatabaseClient::new(self.inner.clone(), database_id))
+        Ok(DatabaseClient::new(self.inner.clone(), self.config.clone(), database_id))
     }
 
     /// Delete a database
@@ -86,9 +226,10 @@ impl CosmosClient {
         database_id: String,
         kwargs: Option<&PyDict>,
     ) -> PyResult<()> {
+        self.config.check_writable("delete_database")?;
         let client = self.inner.database_client(&database_id);
         
-        TOKIO_RUNTIME.block_on(async move {
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "delete_database", kwargs, async move {
             client.delete(None)
                 .await
                 .map_err(map_error)
@@ -98,15 +239,17 @@ impl CosmosClient {
     }
 
     /// List all databases
+    /// Each streamed item is serialized back to the service's JSON, so entries carry the plain
+    /// `id` and system properties such as `_rid` and `_self`.
     #[pyo3(signature = (**kwargs))]
     pub fn list_databases<'py>(
         &self,
         py: Python<'py>,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<Vec<&'py PyDict>> {
+    ) -> PyResult<Vec<PyObject>> {
         let client = self.inner.clone();
         
-        let databases = TOKIO_RUNTIME.block_on(async move {
+        let databases = run_operation(py, &TOKIO_RUNTIME, &self.config, "list_databases", kwargs, async move {
             let mut result = Vec::new();
             let mut stream = client.query_databases("SELECT * FROM databases", None).map_err(map_error)?;
             
@@ -121,14 +264,78 @@ impl CosmosClient {
             Ok::<_, PyErr>(result)
         })?;
 
-        let mut py_databases = Vec::new();
-        for db in databases {
+        databases
+            .iter()
+            .map(|db| database_properties(py, &self.config, database_properties_value(db)))
+            .collect()
+    }
+
+    /// Return up to `n` of the most recently sent requests, oldest first
+    /// Requires the client to be created with `capture_requests=N`
+    #[pyo3(signature = (n=None))]
+    pub fn get_last_requests<'py>(&self, py: Python<'py>, n: Option<usize>) -> PyResult<Vec<&'py PyDict>> {
+        let recorder = self.config.request_recorder.as_ref().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Request capture is disabled; create the client with capture_requests=N"
+            )
+        })?;
+
+        let mut py_requests = Vec::new();
+        for request in recorder.last(n.unwrap_or(usize::MAX)) {
             let dict = PyDict::new(py);
-            dict.set_item("id", format!("{:?}", db))?;
-            py_databases.push(dict);
+            dict.set_item("method", request.method)?;
+            dict.set_item("url", request.url)?;
+            dict.set_item("headers", request.headers.into_iter().collect::<std::collections::HashMap<_, _>>())?;
+            dict.set_item("body", request.body)?;
+            py_requests.push(dict);
         }
 
-        Ok(py_databases)
+        Ok(py_requests)
+    }
+
+    /// Return the RU charge, request counts by operation and throttle count since creation
+    /// Request totals require `track_metrics=True`; query cache hits and misses are included
+    /// whenever `enable_query_cache=True`, with or without `track_metrics`
+    pub fn get_metrics<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        if self.config.metrics.is_none() && self.config.query_cache.is_none() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Metrics are disabled; create the client with track_metrics=True"
+            ));
+        }
+
+        let dict = PyDict::new(py);
+        if let Some(metrics) = &self.config.metrics {
+            let metrics = metrics.snapshot();
+            dict.set_item("total_request_charge", metrics.total_request_charge)?;
+            dict.set_item("request_count", metrics.requests_by_operation.values().sum::<u64>())?;
+            dict.set_item("requests_by_operation", metrics.requests_by_operation)?;
+            dict.set_item("throttled_requests", metrics.throttled_requests)?;
+        }
+        if let Some(cache) = &self.config.query_cache {
+            let (hits, misses) = cache.stats();
+            dict.set_item("query_cache_hits", hits)?;
+            dict.set_item("query_cache_misses", misses)?;
+        }
+        Ok(dict)
+    }
+
+    /// Number of operations currently running on this client
+    pub fn in_flight_count(&self) -> usize {
+        self.config.operations.count()
+    }
+
+    /// Cancel every operation currently running on this client
+    /// Each raises `asyncio.CancelledError`; operations started afterwards are not affected.
+    /// A write whose request was already sent may still be applied by the service.
+    pub fn cancel_all(&self) {
+        self.config.operations.cancel_all();
+    }
+
+    /// State of the circuit breaker: "closed", "open" or "half_open"
+    /// `None` unless the client was created with `circuit_breaker_threshold`
+    #[getter]
+    pub fn circuit_breaker_state(&self) -> Option<&'static str> {
+        self.config.circuit_breaker.as_ref().map(|breaker| breaker.state().as_str())
     }
 
     /// Context manager support
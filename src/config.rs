@@ -0,0 +1,243 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use azure_core::http::policies::Policy;
+use azure_core::http::{new_http_client, HttpClient, Transport};
+use azure_data_cosmos::CosmosClientOptions;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cache::QueryCache;
+use crate::cancellation::InFlightOperations;
+use crate::exceptions::read_only_error;
+use crate::policies::{
+    BodyPropertiesPolicy, CircuitBreaker, ClientMetrics, GoneRetryPolicy, OperationHeadersPolicy, RequestRecorder,
+    ResponseSizeLimit, SessionTokenPolicy, UndefinedPartitionKeyPolicy,
+};
+use crate::session;
+
+/// Client-wide settings shared by every database and container client
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    /// Strip keys with `None` values from item bodies before they are sent
+    pub exclude_none_on_write: bool,
+    /// Ring buffer of outgoing requests, enabled by `capture_requests=N`
+    pub request_recorder: Option<Arc<RequestRecorder>>,
+    /// Session-wide RU and request totals, enabled by `track_metrics=True`
+    pub metrics: Option<Arc<ClientMetrics>>,
+    /// What to do when a `partition_key` kwarg disagrees with the item body
+    pub partition_key_mismatch: PartitionKeyMismatch,
+    /// Skip TLS certificate verification, as needed by the emulator's self-signed certificate
+    pub disable_tls_verification: bool,
+    /// Timeout for establishing connections
+    pub connection_timeout: Option<Duration>,
+    /// Session token from another client, sent with every request for read-your-writes
+    pub initial_session_token: Option<String>,
+    /// Return `DatabaseProperties`/`ContainerProperties` objects instead of dicts from reads and lists
+    pub typed_properties: bool,
+    /// Bound on the duration of each operation, including retries
+    pub operation_timeout: Option<Duration>,
+    /// Operations currently running on the client, for `in_flight_count` and `cancel_all`
+    pub operations: Arc<InFlightOperations>,
+    /// Item property that stands in for `id`, moved to and from `id` on write and read
+    pub id_field: Option<String>,
+    /// Fails requests fast after sustained 429/503 responses, enabled by `circuit_breaker_threshold=N`
+    pub circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// Reject every operation that creates, changes or deletes a resource
+    pub read_only: bool,
+    /// Largest response body accepted, in bytes; unlimited when unset
+    pub max_response_size_bytes: Option<u64>,
+    /// Results of repeated queries served from memory, enabled by `enable_query_cache=True`
+    pub query_cache: Option<Arc<QueryCache>>,
+}
+
+/// How long consecutive 429/503 responses may be spread over and still trip the breaker
+const DEFAULT_CIRCUIT_BREAKER_WINDOW: Duration = Duration::from_secs(10);
+/// How long an open breaker fails requests before letting a probe through
+const DEFAULT_CIRCUIT_BREAKER_COOL_DOWN: Duration = Duration::from_secs(30);
+/// How long a cached query result is served before the query is sent again
+const DEFAULT_QUERY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Reaction to a `partition_key` kwarg that differs from the partition key value in the body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PartitionKeyMismatch {
+    /// Send the kwarg without checking it, leaving disagreements for the service to reject
+    #[default]
+    Ignore,
+    Raise,
+    Warn,
+}
+
+impl PartitionKeyMismatch {
+    pub fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "ignore" => Ok(Self::Ignore),
+            "raise" => Ok(Self::Raise),
+            "warn" => Ok(Self::Warn),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "partition_key_mismatch must be 'ignore', 'raise' or 'warn', got '{}'", other
+            ))),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Build the config from the keyword arguments passed to `CosmosClient`
+    pub fn from_kwargs(kwargs: Option<&PyDict>) -> PyResult<Self> {
+        let mut config = Self::default();
+
+        if let Some(kw) = kwargs {
+            if let Some(value) = kw.get_item("exclude_none_on_write")? {
+                config.exclude_none_on_write = value.extract()?;
+            }
+            if let Some(value) = kw.get_item("capture_requests")? {
+                let capacity = value.extract::<usize>()?;
+                if capacity == 0 {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "capture_requests must be a positive number of requests"
+                    ));
+                }
+                config.request_recorder = Some(Arc::new(RequestRecorder::new(capacity)));
+            }
+            if let Some(value) = kw.get_item("track_metrics")? {
+                if value.extract::<bool>()? {
+                    config.metrics = Some(Arc::new(ClientMetrics::default()));
+                }
+            }
+            if let Some(value) = kw.get_item("partition_key_mismatch")? {
+                config.partition_key_mismatch = PartitionKeyMismatch::parse(value.extract()?)?;
+            }
+            if let Some(value) = kw.get_item("initial_session_token")? {
+                // Normalize now so that malformed tokens fail at construction
+                config.initial_session_token = Some(session::merge(&[value.extract()?])?);
+            }
+            if let Some(value) = kw.get_item("connection_verify")? {
+                config.disable_tls_verification = !value.extract::<bool>()?;
+            }
+            if let Some(value) = kw.get_item("connection_timeout")? {
+                config.connection_timeout = Some(positive_duration(value, "connection_timeout")?);
+            }
+            if let Some(value) = kw.get_item("typed_properties")? {
+                config.typed_properties = value.extract()?;
+            }
+            if let Some(value) = kw.get_item("operation_timeout")? {
+                config.operation_timeout = Some(positive_duration(value, "operation_timeout")?);
+            }
+            if let Some(value) = kw.get_item("id_field")? {
+                let field: String = value.extract()?;
+                if field.is_empty() || field == "id" || field.contains('/') {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "id_field must be a top-level property name other than 'id', got '{}'", field
+                    )));
+                }
+                config.id_field = Some(field);
+            }
+            if let Some(value) = kw.get_item("read_only")? {
+                config.read_only = value.extract()?;
+            }
+            if let Some(value) = kw.get_item("max_response_size_bytes")? {
+                let max_bytes = value.extract::<u64>()?;
+                if max_bytes == 0 {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "max_response_size_bytes must be a positive number of bytes"
+                    ));
+                }
+                config.max_response_size_bytes = Some(max_bytes);
+            }
+            if let Some(value) = kw.get_item("enable_query_cache")? {
+                if value.extract::<bool>()? {
+                    let ttl = match kw.get_item("query_cache_ttl")? {
+                        Some(value) => positive_duration(value, "query_cache_ttl")?,
+                        None => DEFAULT_QUERY_CACHE_TTL,
+                    };
+                    config.query_cache = Some(Arc::new(QueryCache::new(ttl)));
+                }
+            }
+            if let Some(value) = kw.get_item("circuit_breaker_threshold")? {
+                let threshold = value.extract::<u32>()?;
+                if threshold == 0 {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "circuit_breaker_threshold must be a positive number of responses"
+                    ));
+                }
+                let window = match kw.get_item("circuit_breaker_window")? {
+                    Some(value) => positive_duration(value, "circuit_breaker_window")?,
+                    None => DEFAULT_CIRCUIT_BREAKER_WINDOW,
+                };
+                let cool_down = match kw.get_item("circuit_breaker_cool_down")? {
+                    Some(value) => positive_duration(value, "circuit_breaker_cool_down")?,
+                    None => DEFAULT_CIRCUIT_BREAKER_COOL_DOWN,
+                };
+                config.circuit_breaker = Some(Arc::new(CircuitBreaker::new(threshold, window, cool_down)));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Raise `CosmosReadOnlyError` if the client is read-only, before `operation` does anything
+    pub fn check_writable(&self, operation: &str) -> PyResult<()> {
+        if self.read_only {
+            return Err(read_only_error(operation));
+        }
+        Ok(())
+    }
+
+    /// Options for the underlying Rust client, with the policies this config enables
+    pub fn cosmos_client_options(&self) -> PyResult<CosmosClientOptions> {
+        let mut options = CosmosClientOptions::default();
+        options.client_options.per_call_policies.push(Arc::new(OperationHeadersPolicy));
+        options.client_options.per_call_policies.push(Arc::new(UndefinedPartitionKeyPolicy));
+        options.client_options.per_call_policies.push(Arc::new(BodyPropertiesPolicy));
+        if let Some(token) = &self.initial_session_token {
+            options.client_options.per_call_policies.push(Arc::new(SessionTokenPolicy::new(token.clone())));
+        }
+        if let Some(breaker) = &self.circuit_breaker {
+            options.client_options.per_try_policies.push(breaker.clone() as Arc<dyn Policy>);
+        }
+        options.client_options.per_try_policies.push(Arc::new(GoneRetryPolicy::default()));
+        // Policies after the Gone retry see each of its attempts
+        if let Some(recorder) = &self.request_recorder {
+            options.client_options.per_try_policies.push(recorder.clone() as Arc<dyn Policy>);
+        }
+        if let Some(metrics) = &self.metrics {
+            options.client_options.per_try_policies.push(metrics.clone() as Arc<dyn Policy>);
+        }
+        let mut http_client: Option<Arc<dyn HttpClient>> = None;
+        if self.disable_tls_verification || self.connection_timeout.is_some() {
+            let mut builder = reqwest::Client::builder()
+                .danger_accept_invalid_certs(self.disable_tls_verification);
+            if let Some(timeout) = self.connection_timeout {
+                builder = builder.connect_timeout(timeout);
+            }
+            let client = builder.build().map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to create HTTP client: {}", e))
+            })?;
+            http_client = Some(Arc::new(client));
+        }
+        if let Some(max_bytes) = self.max_response_size_bytes {
+            let inner = http_client.take().unwrap_or_else(new_http_client);
+            http_client = Some(Arc::new(ResponseSizeLimit::new(inner, max_bytes)));
+        }
+        if let Some(http_client) = http_client {
+            options.client_options.transport = Some(Transport::new(http_client));
+        }
+        Ok(options)
+    }
+}
+
+/// Extract a positive duration given either as seconds or as a `datetime.timedelta`
+pub fn positive_duration(value: &PyAny, name: &str) -> PyResult<Duration> {
+    let seconds = if value.hasattr("total_seconds")? {
+        value.call_method0("total_seconds")?.extract::<f64>()?
+    } else {
+        value.extract::<f64>()?
+    };
+
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("{} must be a positive duration", name)
+        ));
+    }
+    Duration::try_from_secs_f64(seconds).map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{} is too large", name))
+    })
+}
@@ -1,56 +1,139 @@
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyBytes, PyDict, PyList, PyString, PyTuple};
+use azure_core::http::headers::Headers;
 use azure_data_cosmos::CosmosClient as RustCosmosClient;
-use azure_data_cosmos::PartitionKey as RustPartitionKey;
+use azure_data_cosmos::{PartitionKey as RustPartitionKey, PartitionKeyValue};
+use azure_data_cosmos::models::PatchDocument;
+use azure_data_cosmos::{Query, ReadContainerOptions};
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::Arc;
-use serde_json::Value;
-use crate::exceptions::map_error;
-use crate::utils::py_object_to_json;
-use once_cell::sync::Lazy;
+use std::time::{Duration, Instant};
+use serde_json::{Number, Value};
+use crate::cancellation::{build_runtime, run_operation};
+use crate::config::{positive_duration, ClientConfig, PartitionKeyMismatch};
+use crate::diagnostics::{call_read_response_hook, call_response_hook, requested_diagnostics, with_diagnostics};
+use crate::exceptions::{
+    correlate, invalid_continuation_error, is_invalid_continuation, map_error, map_json_error, timeout_error,
+    CosmosAccessConditionFailedError, CosmosResourceExistsError,
+};
+use crate::headers::{
+    self, ACTIVITY_ID, CONTINUATION, ETAG, INDEX_TRANSFORMATION_PROGRESS, INDEX_UTILIZATION, MAX_ITEM_COUNT,
+    POPULATE_INDEX_METRICS, POPULATE_QUOTA_INFO,
+};
+use crate::options::{
+    if_match_etag, item_options, method_options, query_options, query_options_with_headers, write_item_options, FallbackRead,
+    OperationHeaders, ReadOptions, ServerTimestamp,
+};
+use crate::policies::UNDEFINED_PARTITION_KEY;
+use crate::properties::container_properties;
+use crate::query::{
+    build_query, equality_operands, query_parameters, query_parts, Operand, QueryCursor, QueryIterator, QueryPage,
+};
+use crate::types::{PartitionKeyKind, PartitionKeyType};
+use crate::utils::{
+    deserialize_projected, json_to_py, json_to_py_dict, move_id_to_id_field, parse_json_document, py_object_to_json,
+    py_to_json, without_system_properties,
+};
+use once_cell::sync::{Lazy, OnceCell};
 use tokio::runtime::Runtime;
 
 // Global Tokio runtime - reused across all operations for better performance
-static TOKIO_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
-    tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .expect("Failed to create Tokio runtime")
-});
+static TOKIO_RUNTIME: Lazy<Runtime> = Lazy::new(|| build_runtime("container"));
+
+// Number of ids bound into a single `IN` query by `read_items_by_ids`
+const READ_BY_IDS_CHUNK_SIZE: usize = 256;
+
+// Query used to enumerate every item of the container
+const READ_ALL_QUERY: &str = "SELECT * FROM c";
+
+// Query used to count the items of a partition by `partition_key_delete_status`
+const COUNT_QUERY: &str = "SELECT VALUE COUNT(1) FROM c";
+
+// Retries of a `read_after_write` read that found no item, and the delay before the first;
+// the delay doubles with each retry, for 350ms of waiting in total
+const READ_AFTER_WRITE_RETRIES: u32 = 3;
+const READ_AFTER_WRITE_BACKOFF: Duration = Duration::from_millis(50);
+
+// How often `wait_until_ready` polls the indexing transformation progress
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Query kwargs that can change the results, and so are part of the query cache key
+const QUERY_CACHE_KEY_KWARGS: [&str; 6] = [
+    "partition_key",
+    "fields",
+    "consistency_level",
+    "session_token",
+    "enable_scan_in_query",
+    "enable_low_precision_order_by",
+];
 
 #[pyclass(subclass)]
 pub struct ContainerClient {
     cosmos_client: Arc<RustCosmosClient>,
+    config: Arc<ClientConfig>,
     database_id: String,
     container_id: String,
+    partition_key_type: Option<PartitionKeyType>,
+    partition_key_definition: Arc<OnceCell<PartitionKeyDefinition>>,
+}
+
+/// The parts of a container's partition key definition the client interprets keys with
+#[derive(Debug, Clone)]
+struct PartitionKeyDefinition {
+    paths: Vec<String>,
+    kind: PartitionKeyKind,
 }
 
 impl ContainerClient {
-    pub fn new(cosmos_client: Arc<RustCosmosClient>, database_id: String, container_id: String) -> Self {
+    pub fn new(
+        cosmos_client: Arc<RustCosmosClient>,
+        config: Arc<ClientConfig>,
+        database_id: String,
+        container_id: String,
+    ) -> Self {
         Self {
             cosmos_client,
+            config,
             database_id,
             container_id,
+            partition_key_type: None,
+            partition_key_definition: Arc::new(OnceCell::new()),
         }
     }
+
+    pub(crate) fn database_id(&self) -> &str {
+        &self.database_id
+    }
+
+    pub(crate) fn container_id(&self) -> &str {
+        &self.container_id
+    }
+
+    /// Coerce partition key values passed to this client to the given type
+    pub fn with_partition_key_type(mut self, partition_key_type: Option<PartitionKeyType>) -> Self {
+        self.partition_key_type = partition_key_type;
+        self
+    }
 }
 
 #[pymethods]
 impl ContainerClient {
     /// Create a new item
     /// Accepts either a dict or a JSON string for the body
+    /// With `idempotent=True`, a conflict with an item equal to the body (ignoring system
+    /// properties) returns the stored item instead of raising
     #[pyo3(signature = (body, **kwargs))]
-    pub fn create_item<'py>(
-        &self,
-        py: Python<'py>,
-        body: &'py PyAny,
-        kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
+    pub fn create_item(&self, py: Python, body: &PyAny, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        self.config.check_writable("create_item")?;
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
         
         // Convert Python object (dict or string) to JSON using hybrid approach
-        let item_value = py_object_to_json(py, body)?;
+        let item_value = py_object_to_json(py, body, &self.config)?;
+        if let Some(id) = item_value.get("id").and_then(Value::as_str) {
+            check_item_id(id)?;
+        }
         
         // Extract partition key from body or kwargs
         let partition_key = if let Ok(dict) = body.downcast::<PyDict>() {
@@ -60,71 +143,159 @@ impl ContainerClient {
             self.extract_partition_key_from_kwargs(kwargs)?
         };
         
-        let _result = TOKIO_RUNTIME.block_on(async move {
-            container.create_item(partition_key, item_value, None)
+        let idempotent = match kwargs {
+            Some(kw) => kw.get_item("idempotent")?.map(|v| v.extract::<bool>()).transpose()?.unwrap_or(false),
+            None => false,
+        };
+        let retained = idempotent.then(|| (partition_key.clone(), item_value.clone()));
+        
+        let server_timestamp = ServerTimestamp::from_kwargs(kwargs)?;
+        let return_item = return_written_item(kwargs, server_timestamp.as_ref())?;
+        let mut options = write_item_options(kwargs)?;
+        options.enable_content_response_on_write = server_timestamp.is_some();
+        let result = match run_operation(py, &TOKIO_RUNTIME, &self.config, "create_item", kwargs, async move {
+            container.create_item(partition_key, item_value, Some(options))
                 .await
                 .map_err(map_error)
-        })?;
+        }) {
+            Ok(result) => result,
+            Err(err) => {
+                if let Some((partition_key, item_value)) = retained {
+                    if err.is_instance_of::<CosmosResourceExistsError>(py) {
+                        if let Some(existing) = self.read_identical_item(py, partition_key, &item_value, kwargs)? {
+                            // No write response exists, so the diagnostics are all None
+                            let diagnostics = requested_diagnostics(py, kwargs, None, &Headers::new())?;
+                            if !return_item {
+                                return Ok(with_diagnostics(py, py.None(), diagnostics));
+                            }
+                            let existing = json_to_py_dict(py, &self.with_id_field(existing))?;
+                            return Ok(with_diagnostics(py, existing, diagnostics));
+                        }
+                    }
+                }
+                return Err(correlate(py, err, kwargs));
+            }
+        };
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
+        let diagnostics = requested_diagnostics(py, kwargs, Some(result.status()), result.headers())?;
+        if !return_item {
+            return Ok(with_diagnostics(py, py.None(), diagnostics));
+        }
 
-        // Return the created item as dict (convert if it was a string)
-        if let Ok(dict) = body.downcast::<PyDict>() {
-            Ok(dict)
-        } else {
-            // If input was a string, we need to convert it back to dict for return
-            let json_module = py.import("json")?;
-            json_module.call_method1("loads", (body,))?.extract()
+        let written = match server_timestamp {
+            Some(timestamp) => Some((timestamp, result.into_body().json::<Value>().map_err(map_error)?)),
+            None => None,
+        };
+        Ok(with_diagnostics(py, written_item(py, body, written, kwargs)?.into(), diagnostics))
+    }
+
+    /// Resolve the partition key a write of `body` would use, without writing it
+    /// Runs the same extraction and validation as a write, raising the same errors. Returns the
+    /// key value, or the list of components for a hierarchical key.
+    #[pyo3(signature = (body, **kwargs))]
+    pub fn resolve_partition_key(&self, py: Python, body: &PyAny, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        let Ok(dict) = body.downcast::<PyDict>() else {
+            // A JSON string body takes its key from the kwarg, as on write
+            self.extract_partition_key_from_kwargs(kwargs)?;
+            return Ok(kwargs.and_then(|kw| kw.get_item("partition_key").ok().flatten()).into_py(py));
+        };
+        let value = self.partition_key_of(py, dict, kwargs)?;
+        self.python_to_partition_key(py, value.into())?;
+        Ok(value.into())
+    }
+
+    /// The partition key an item is stored under, read from its fields at the container's key paths
+    /// Returns the value, or the list of components for a hierarchical key; a missing path gives
+    /// None, the undefined partition. Unlike `resolve_partition_key`, nothing is validated.
+    pub fn get_item_partition_key(&self, py: Python, item: &PyDict) -> PyResult<PyObject> {
+        let item = self.body_with_id(item)?;
+        let mut components = Vec::new();
+        for path in self.partition_key_paths(py)? {
+            components.push(value_at_path(item, path)?.map_or_else(|| py.None(), Into::into));
         }
+        Ok(match components.len() {
+            1 => components.remove(0),
+            _ => PyList::new(py, components).into(),
+        })
     }
 
     /// Read an item by ID and partition key
+    /// Pass `fields=[...]` to only deserialize the listed top-level fields
+    /// Pass `json_path="a.b[0]"` to return only that subtree of the item
+    /// A returned `_etag` is always the response's `ETag` header, so it can be passed straight
+    /// to a conditional write; `include_etag=True` adds it even when a projection left it out.
+    #[pyo3(signature = (item, partition_key, **kwargs))]
+    pub fn read_item(
+        &self,
+        py: Python,
+        item: String,
+        partition_key: PyObject,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        let include_etag = kwarg_flag(kwargs, "include_etag")?;
+        let (item, etag, diagnostics) = self.read_item_and_etag(py, item, partition_key, kwargs)?;
+        if let (Ok(dict), Some(etag)) = (item.as_ref(py).downcast::<PyDict>(), etag) {
+            if include_etag || dict.contains("_etag")? {
+                dict.set_item("_etag", etag)?;
+            }
+        }
+        Ok(with_diagnostics(py, item, diagnostics))
+    }
+
+    /// Read an item together with its entity tag, for read-modify-write loops
+    /// The etag comes from the response's `ETag` header, falling back to the body's `_etag`,
+    /// so it is returned even when `fields` or `json_path` leave `_etag` out of the item.
+    #[pyo3(signature = (item, partition_key, **kwargs))]
+    pub fn read_item_with_etag(
+        &self,
+        py: Python,
+        item: String,
+        partition_key: PyObject,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<(PyObject, Option<String>)> {
+        self.read_item_and_etag(py, item, partition_key, kwargs).map(|(item, etag, _)| (item, etag))
+    }
+
+    /// Read an item as the raw response body, without deserializing it
+    /// Read options such as `fields`, `json_path` and `parse_timestamps` do not apply.
     #[pyo3(signature = (item, partition_key, **kwargs))]
-    pub fn read_item<'py>(
+    pub fn read_item_bytes<'py>(
         &self,
         py: Python<'py>,
         item: String,
         partition_key: PyObject,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
+    ) -> PyResult<&'py PyBytes> {
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
         
         let pk = self.python_to_partition_key(py, partition_key)?;
-        let item_id = item.clone();
-        
-        let result = TOKIO_RUNTIME.block_on(async move {
-            container.read_item::<Value>(pk, &item_id, None)
+        let options = item_options(kwargs)?;
+        let result = run_operation(py, &TOKIO_RUNTIME, &self.config, "read_item_bytes", kwargs, async move {
+            container.read_item::<Value>(pk, &item, Some(options))
                 .await
                 .map_err(map_error)
-        })?;
+        }).map_err(|e| correlate(py, e, kwargs))?;
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
 
-        // Extract the value from the Response
-        let value = result.into_body().json::<Value>()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Failed to deserialize response: {}", e)))?;
-        
-        let json_str = serde_json::to_string(&value)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("JSON error: {}", e)))?;;
-        
-        let json_module = py.import("json")?;
-        let py_dict = json_module.call_method1("loads", (json_str,))?;
-        py_dict.extract()
+        Ok(PyBytes::new(py, result.into_body().as_ref()))
     }
 
     /// Upsert an item (create or replace)
     /// Accepts either a dict or a JSON string for the body
     #[pyo3(signature = (body, **kwargs))]
-    pub fn upsert_item<'py>(
-        &self,
-        py: Python<'py>,
-        body: &'py PyAny,
-        kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
+    pub fn upsert_item(&self, py: Python, body: &PyAny, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        self.config.check_writable("upsert_item")?;
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
         
         // Convert Python object (dict or string) to JSON using hybrid approach
-        let item_value = py_object_to_json(py, body)?;
+        let item_value = py_object_to_json(py, body, &self.config)?;
+        if let Some(id) = item_value.get("id").and_then(Value::as_str) {
+            check_item_id(id)?;
+        }
         
         // Extract partition key from body or kwargs
         let partition_key = if let Ok(dict) = body.downcast::<PyDict>() {
@@ -133,37 +304,49 @@ impl ContainerClient {
             self.extract_partition_key_from_kwargs(kwargs)?
         };
         
-        let _result = TOKIO_RUNTIME.block_on(async move {
-            container.upsert_item(partition_key, item_value, None)
+        let server_timestamp = ServerTimestamp::from_kwargs(kwargs)?;
+        let return_item = return_written_item(kwargs, server_timestamp.as_ref())?;
+        let mut options = write_item_options(kwargs)?;
+        options.enable_content_response_on_write = server_timestamp.is_some();
+        options.if_match_etag = if_match_etag(kwargs)?;
+        let result = run_operation(py, &TOKIO_RUNTIME, &self.config, "upsert_item", kwargs, async move {
+            container.upsert_item(partition_key, item_value, Some(options))
                 .await
                 .map_err(map_error)
-        })?;
-
-        // Return the created item as dict (convert if it was a string)
-        if let Ok(dict) = body.downcast::<PyDict>() {
-            Ok(dict)
-        } else {
-            let json_module = py.import("json")?;
-            json_module.call_method1("loads", (body,))?.extract()
+        }).map_err(|e| correlate(py, e, kwargs))?;
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
+        let diagnostics = requested_diagnostics(py, kwargs, Some(result.status()), result.headers())?;
+        if !return_item {
+            return Ok(with_diagnostics(py, py.None(), diagnostics));
         }
+
+        let written = match server_timestamp {
+            Some(timestamp) => Some((timestamp, result.into_body().json::<Value>().map_err(map_error)?)),
+            None => None,
+        };
+        Ok(with_diagnostics(py, written_item(py, body, written, kwargs)?.into(), diagnostics))
     }
 
     /// Replace an item
     /// Accepts either a dict or a JSON string for the body
     #[pyo3(signature = (item, body, **kwargs))]
-    pub fn replace_item<'py>(
+    pub fn replace_item(
         &self,
-        py: Python<'py>,
+        py: Python,
         item: String,
-        body: &'py PyAny,
+        body: &PyAny,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
+    ) -> PyResult<PyObject> {
+        self.config.check_writable("replace_item")?;
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
         
         // Convert Python object (dict or string) to JSON using hybrid approach
-        let item_value = py_object_to_json(py, body)?;
+        let item_value = py_object_to_json(py, body, &self.config)?;
+        if let Some(id) = item_value.get("id").and_then(Value::as_str) {
+            check_item_id(id)?;
+        }
         
         // Extract partition key from body or kwargs
         let partition_key = if let Ok(dict) = body.downcast::<PyDict>() {
@@ -171,21 +354,30 @@ impl ContainerClient {
         } else {
             self.extract_partition_key_from_kwargs(kwargs)?
         };
+        check_item_id(&item)?;
         let item_id = item.clone();
         
-        let _result = TOKIO_RUNTIME.block_on(async move {
-            container.replace_item(partition_key, &item_id, item_value, None)
+        let server_timestamp = ServerTimestamp::from_kwargs(kwargs)?;
+        let return_item = return_written_item(kwargs, server_timestamp.as_ref())?;
+        let mut options = write_item_options(kwargs)?;
+        options.enable_content_response_on_write = server_timestamp.is_some();
+        options.if_match_etag = if_match_etag(kwargs)?;
+        let result = run_operation(py, &TOKIO_RUNTIME, &self.config, "replace_item", kwargs, async move {
+            container.replace_item(partition_key, &item_id, item_value, Some(options))
                 .await
                 .map_err(map_error)
-        })?;
-
-        // Return the created item as dict (convert if it was a string)
-        if let Ok(dict) = body.downcast::<PyDict>() {
-            Ok(dict)
-        } else {
-            let json_module = py.import("json")?;
-            json_module.call_method1("loads", (body,))?.extract()
+        }).map_err(|e| correlate(py, e, kwargs))?;
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
+        let diagnostics = requested_diagnostics(py, kwargs, Some(result.status()), result.headers())?;
+        if !return_item {
+            return Ok(with_diagnostics(py, py.None(), diagnostics));
         }
+
+        let written = match server_timestamp {
+            Some(timestamp) => Some((timestamp, result.into_body().json::<Value>().map_err(map_error)?)),
+            None => None,
+        };
+        Ok(with_diagnostics(py, written_item(py, body, written, kwargs)?.into(), diagnostics))
     }
 
     /// Delete an item
@@ -196,7 +388,8 @@ impl ContainerClient {
         item: String,
         partition_key: PyObject,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<()> {
+    ) -> PyResult<PyObject> {
+        self.config.check_writable("delete_item")?;
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
@@ -204,113 +397,505 @@ impl ContainerClient {
         let pk = self.python_to_partition_key(py, partition_key)?;
         let item_id = item.clone();
         
-        TOKIO_RUNTIME.block_on(async move {
-            container.delete_item(pk, &item_id, None)
+        let mut options = write_item_options(kwargs)?;
+        options.if_match_etag = if_match_etag(kwargs)?;
+        let result = run_operation(py, &TOKIO_RUNTIME, &self.config, "delete_item", kwargs, async move {
+            container.delete_item(pk, &item_id, Some(options))
                 .await
                 .map_err(map_error)
-        })?;
+        }).map_err(|e| correlate(py, e, kwargs))?;
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
+        let diagnostics = requested_diagnostics(py, kwargs, Some(result.status()), result.headers())?;
 
-        Ok(())
+        Ok(with_diagnostics(py, py.None(), diagnostics))
     }
 
     /// Query items with SQL
+    /// `query` is a SQL string or a `QueryBuilder`; a `parameters` kwarg binds further parameters
+    /// With `single_page=True`, only the first page is fetched and `(items, continuation)` returned
     #[pyo3(signature = (query, **kwargs))]
-    pub fn query_items<'py>(
+    pub fn query_items(&self, py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        if kwarg_flag(kwargs, "single_page")? {
+            let max_item_count = kwargs
+                .map(|kw| kw.get_item("max_item_count"))
+                .transpose()?
+                .flatten()
+                .map(|count| count.extract::<i64>())
+                .transpose()?;
+            let page = self.fetch_query_page(py, query, max_item_count, None, kwargs)?;
+            return Ok((page.items, page.continuation_token).into_py(py));
+        }
+
+        let read_options = ReadOptions::from_kwargs(&self.config, kwargs)?;
+        let items = self.cached_query_values(py, query, kwargs)?;
+
+        let mut py_items = Vec::new();
+        for item in items.iter() {
+            let py_item = json_to_py(py, item)?;
+            read_options.apply(py, py_item.as_ref(py))?;
+            py_items.push(py_item);
+        }
+
+        Ok(py_items.into_py(py))
+    }
+
+    /// Query items with SQL, returning the results as columns
+    /// The result maps each property name to a list with one value per row, in row order.
+    /// Columns are the union of the rows' top-level properties, ordered by name, and rows
+    /// without a property hold `None` in its column.
+    #[pyo3(signature = (query, **kwargs))]
+    pub fn query_items_columns(&self, py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<PyObject> {
+        let read_options = ReadOptions::from_kwargs(&self.config, kwargs)?;
+        let rows = self.cached_query_values(py, query, kwargs)?;
+
+        let mut py_rows = Vec::new();
+        for row in rows.iter() {
+            let py_row = json_to_py(py, row)?;
+            read_options.apply(py, py_row.as_ref(py))?;
+            py_rows.push(py_row);
+        }
+
+        Ok(rows_to_columns(py, py_rows)?.into())
+    }
+
+    /// Fetch a single page of query results
+    /// Pass the returned `continuation_token` as `continuation` to fetch the next page
+    /// Without a `partition_key` kwarg the query runs across all partitions
+    #[pyo3(signature = (query, max_item_count=None, continuation=None, **kwargs))]
+    pub fn query_items_paged(
+        slf: PyRef<'_, Self>,
+        py: Python,
+        query: &PyAny,
+        max_item_count: Option<i64>,
+        continuation: Option<String>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<QueryPage> {
+        let mut page = slf.fetch_query_page(py, query, max_item_count, continuation, kwargs)?;
+        if let Some(cursor) = &mut page.cursor {
+            cursor.container = Some(slf.into());
+        }
+        Ok(page)
+    }
+
+    /// Iterate over query results, fetching each page only when the previous one is consumed
+    /// The query and `max_item_count` are validated here; requests start on the first `next()`.
+    #[pyo3(signature = (query, max_item_count=None, **kwargs))]
+    pub fn query_items_iter(
+        slf: PyRef<'_, Self>,
+        py: Python,
+        query: &PyAny,
+        max_item_count: Option<i64>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<QueryIterator> {
+        build_query(py, query, kwargs)?;
+        if let Some(count) = max_item_count {
+            push_max_item_count(&mut OperationHeaders::default(), count)?;
+        }
+        Ok(QueryIterator {
+            container: slf.into(),
+            query: query.into(),
+            max_item_count,
+            kwargs: kwargs.map(|kw| kw.copy()).transpose()?.map(Into::into),
+            buffer: VecDeque::new(),
+            continuation: None,
+            exhausted: false,
+        })
+    }
+
+    /// Fetch a single page of query results as `(items, continuation_token)`
+    #[pyo3(signature = (query, max_item_count=None, continuation=None, **kwargs))]
+    pub fn query_items_page(
         &self,
-        py: Python<'py>,
-        query: String,
+        py: Python,
+        query: &PyAny,
+        max_item_count: Option<i64>,
+        continuation: Option<String>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<(Py<PyList>, Option<String>)> {
+        let page = self.fetch_query_page(py, query, max_item_count, continuation, kwargs)?;
+        Ok((page.items, page.continuation_token))
+    }
+
+    /// Call `callback` with each query result as pages arrive, without buffering the results
+    /// The GIL is released while waiting for the next page. An exception raised by the
+    /// callback stops the scan and propagates. Returns the number of items processed.
+    #[pyo3(signature = (query, callback, **kwargs))]
+    pub fn query_items_foreach(
+        &self,
+        py: Python,
+        query: &PyAny,
+        callback: &PyAny,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<Vec<&'py PyDict>> {
+    ) -> PyResult<usize> {
+        self.check_strict_partition(py, query, kwargs)?;
+        let query = build_query(py, query, kwargs)?;
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
         
-        // Extract partition_key from kwargs if provided
-        let partition_key_opt = if let Some(kw) = kwargs {
-            if let Ok(Some(pk)) = kw.get_item("partition_key") {
-                Some(self.python_to_partition_key(py, pk.into())?)
-            } else {
-                None
+        let partition_key = self.query_partition_key(py, kwargs)?;
+        let options = query_options(kwargs)?;
+        let read_options = ReadOptions::from_kwargs(&self.config, kwargs)?;
+        let mut pages = container.query_items::<Value>(query, partition_key, Some(options))
+            .map_err(map_error)?
+            .into_pages();
+        
+        let mut count = 0;
+        loop {
+            let page = run_operation(py, &TOKIO_RUNTIME, &self.config, "query_items_foreach", kwargs, async {
+                use futures::StreamExt;
+                pages.next().await.transpose().map_err(map_error)
+            });
+            let Some(page) = page.map_err(|e| correlate(py, e, kwargs))? else {
+                break;
+            };
+            
+            call_response_hook(py, kwargs, None, page.headers())?;
+            for item in page.items() {
+                let py_item = json_to_py_dict(py, item)?;
+                read_options.apply(py, py_item.as_ref(py))?;
+                callback.call1((py_item,))?;
+                count += 1;
             }
-        } else {
-            None
+        }
+        
+        Ok(count)
+    }
+
+    /// Report whether a delete of every item in a logical partition is still in progress
+    /// Deleting by partition key runs in the background after the request returns, so this counts
+    /// the items still in the partition; the delete is pending until none remain.
+    #[pyo3(signature = (partition_key, **kwargs))]
+    pub fn partition_key_delete_status<'py>(
+        &self,
+        py: Python<'py>,
+        partition_key: PyObject,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<&'py PyDict> {
+        let scoped = match kwargs {
+            Some(kw) => kw.copy()?,
+            None => PyDict::new(py),
         };
+        scoped.set_item("partition_key", partition_key)?;
+        let counts = self.query_values(py, PyString::new(py, COUNT_QUERY), Some(scoped))?;
+        let remaining = counts.first().and_then(Value::as_u64).unwrap_or(0);
+
+        let status = PyDict::new(py);
+        status.set_item("pending", remaining > 0)?;
+        status.set_item("remaining_items", remaining)?;
+        Ok(status)
+    }
+
+    /// Run a `SELECT VALUE` COUNT, SUM, MIN or MAX query, reporting the running aggregate
+    /// Across partitions the service returns one partial aggregate per partition key range as
+    /// each completes; they are merged client-side, and `on_partial` is called with the merged
+    /// value after every page that contributed to it. Returns the final aggregate.
+    #[pyo3(signature = (query, on_partial, **kwargs))]
+    pub fn query_items_partial(
+        &self,
+        py: Python,
+        query: &PyAny,
+        on_partial: &PyAny,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        let (sql, _) = query_parts(py, query, kwargs)?;
+        let aggregate = Aggregate::parse(&sql)?;
+        self.check_strict_partition(py, query, kwargs)?;
+        let query = build_query(py, query, kwargs)?;
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
         
-        let items = TOKIO_RUNTIME.block_on(async move {
-            let mut result = Vec::new();
-            
-            // If no partition key is provided, we need to do a cross-partition query
-            // For now, if partition_key is not specified, return error asking for it
-            let pk = partition_key_opt.ok_or_else(|| {
-                PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                    "partition_key is required for queries. For cross-partition queries, this will be supported in a future update."
-                )
-            })?;
+        let partition_key = self.query_partition_key(py, kwargs)?;
+        let options = query_options(kwargs)?;
+        let mut pages = container.query_items::<Value>(query, partition_key, Some(options))
+            .map_err(map_error)?
+            .into_pages();
+        
+        let mut running = None;
+        loop {
+            let page = run_operation(py, &TOKIO_RUNTIME, &self.config, "query_items_partial", kwargs, async {
+                use futures::StreamExt;
+                pages.next().await.transpose().map_err(map_error)
+            });
+            let Some(page) = page.map_err(|e| correlate(py, e, kwargs))? else {
+                break;
+            };
             
-            let mut stream = container.query_items::<Value>(&query, pk, None).map_err(map_error)?;
+            call_response_hook(py, kwargs, None, page.headers())?;
+            let mut changed = false;
+            for partial in page.items() {
+                // Partitions without matching items report an empty object for MIN, MAX and SUM
+                if partial.is_number() || partial.is_string() {
+                    running = Some(aggregate.merge(running, partial)?);
+                    changed = true;
+                }
+            }
+            if changed {
+                on_partial.call1((json_to_py_dict(py, running.as_ref().unwrap_or(&Value::Null))?,))?;
+            }
+        }
+        
+        let result = running.unwrap_or_else(|| aggregate.empty());
+        json_to_py_dict(py, &result)
+    }
+
+    /// Read every item of the container, one page of `max_item_count` items per request
+    /// Runs across all partitions unless a `partition_key` kwarg scopes it to one
+    #[pyo3(signature = (max_item_count=None, **kwargs))]
+    pub fn read_all_items(
+        &self,
+        py: Python,
+        max_item_count: Option<i64>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<Vec<PyObject>> {
+        let query = PyString::new(py, READ_ALL_QUERY);
+        let mut items = Vec::new();
+        let mut continuation = None;
+        loop {
+            let page = self.fetch_query_page(py, query, max_item_count, continuation, kwargs)?;
+            items.extend(page.items.as_ref(py).iter().map(Into::into));
+            match page.continuation_token {
+                Some(token) => continuation = Some(token),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Read a single page of the container's items
+    /// Pass the returned `continuation_token` as `continuation` to fetch the next page
+    #[pyo3(signature = (max_item_count=None, continuation=None, **kwargs))]
+    pub fn read_all_items_paged(
+        slf: PyRef<'_, Self>,
+        py: Python,
+        max_item_count: Option<i64>,
+        continuation: Option<String>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<QueryPage> {
+        let query = PyString::new(py, READ_ALL_QUERY);
+        Self::query_items_paged(slf, py, query, max_item_count, continuation, kwargs)
+    }
+
+    /// Read items by id with cross-partition `IN` queries instead of per-item point reads
+    /// Ids are bound as query parameters `@id0`, `@id1`, ..., `READ_BY_IDS_CHUNK_SIZE` per query;
+    /// `parameters` are bound on every chunk's query alongside them.
+    #[pyo3(signature = (ids, parameters=None, **kwargs))]
+    pub fn read_items_by_ids(
+        &self,
+        py: Python,
+        ids: Vec<String>,
+        parameters: Option<&PyAny>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<Vec<PyObject>> {
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let parameters = match parameters {
+            Some(parameters) => query_parameters(py, parameters.iter()?.collect::<PyResult<Vec<_>>>()?)?,
+            None => Vec::new(),
+        };
+        let reserved = |name: &str| {
+            name.strip_prefix("@id").is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+        };
+        if let Some((name, _)) = parameters.iter().find(|(name, _)| reserved(name)) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Query parameter {} clashes with the @id0, @id1, ... parameters read_items_by_ids binds the ids to",
+                name
+            )));
+        }
+        let options = query_options(kwargs)?;
+        let read_options = ReadOptions::from_kwargs(&self.config, kwargs)?;
+        let items = run_operation(py, &TOKIO_RUNTIME, &self.config, "read_items_by_ids", kwargs, async move {
+            let mut result = Vec::new();
             
-            use futures::StreamExt;
-            while let Some(response) = stream.next().await {
-                match response {
-                    Ok(item) => {
-                        result.push(item);
-                    },
-                    Err(e) => return Err(map_error(e)),
+            for chunk in ids.chunks(READ_BY_IDS_CHUNK_SIZE) {
+                let query = Self::ids_in_query(chunk, &parameters)?;
+                let mut stream = container.query_items::<Value>(query, (), Some(options.clone())).map_err(map_error)?;
+                
+                use futures::StreamExt;
+                while let Some(response) = stream.next().await {
+                    match response {
+                        Ok(item) => result.push(item),
+                        Err(e) => return Err(map_error(e)),
+                    }
                 }
             }
             
             Ok::<_, PyErr>(result)
-        })?;
+        }).map_err(|e| correlate(py, e, kwargs))?;
 
-        let mut py_items = Vec::new();
-        for item in items {
-            let json_str = serde_json::to_string(&item)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("JSON error: {}", e)))?;
-            
-            let json_module = py.import("json")?;
-            let py_dict = json_module.call_method1("loads", (json_str,))?;
-            py_items.push(py_dict.extract()?);
-        }
-
-        Ok(py_items)
+        items.iter().map(|item| {
+            let py_item = json_to_py_dict(py, item)?;
+            read_options.apply(py, py_item.as_ref(py))?;
+            Ok(py_item)
+        }).collect()
     }
 
-    /// Patch an item
+    /// Patch an item with a list of operation dicts, returning the patched item
+    /// Operations are `{"op": ..., "path": ..., "value": ...}` in the service's patch format;
+    /// see `patch_document` for the supported ops.
     #[pyo3(signature = (item, partition_key, patch_operations, **kwargs))]
-    pub fn patch_item<'py>(
+    pub fn patch_item(
         &self,
-        py: Python<'py>,
+        py: Python,
         item: String,
         partition_key: PyObject,
         patch_operations: &PyList,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
-        // For now, return error as patch is complex
-        Err(PyErr::new::<pyo3::exceptions::PyNotImplementedError, _>(
-            "patch_item is not yet implemented"
-        ))
+    ) -> PyResult<PyObject> {
+        self.config.check_writable("patch_item")?;
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let pk = self.python_to_partition_key(py, partition_key)?;
+        let patch = patch_document(py, patch_operations)?;
+        let item_id = item.clone();
+        
+        let mut options = write_item_options(kwargs)?;
+        options.enable_content_response_on_write = true;
+        let result = run_operation(py, &TOKIO_RUNTIME, &self.config, "patch_item", kwargs, async move {
+            container.patch_item(pk, &item_id, patch, Some(options))
+                .await
+                .map_err(map_error)
+        }).map_err(|e| correlate(py, e, kwargs))?;
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
+        
+        let patched = result.into_body().json::<Value>().map_err(map_error)?;
+        json_to_py_dict(py, &self.with_id_field(patched))
+    }
+
+    /// Append `value` to the array at `path` unless the array already contains it
+    /// Sent as a single patch whose filter predicate requires the value to be absent, so
+    /// concurrent appends of the same value cannot duplicate it. When the value is already
+    /// present the stored item is returned unchanged.
+    #[pyo3(signature = (item, partition_key, path, value, **kwargs))]
+    pub fn patch_item_append_unique(
+        &self,
+        py: Python,
+        item: String,
+        partition_key: PyObject,
+        path: &str,
+        value: &PyAny,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        self.config.check_writable("patch_item_append_unique")?;
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let pk = self.python_to_partition_key(py, partition_key)?;
+        let value = py_to_json(py, value)?;
+        let array = property_reference(path)?;
+        let literal = serde_json::to_string(&value).map_err(map_json_error)?;
+        let patch = PatchDocument::default()
+            .with_condition(format!("FROM c WHERE IS_ARRAY({0}) AND NOT ARRAY_CONTAINS({0}, {1})", array, literal))
+            .with_add(format!("{}/-", path), value)
+            .map_err(map_json_error)?;
+        
+        let mut options = write_item_options(kwargs)?;
+        options.enable_content_response_on_write = true;
+        let read_pk = pk.clone();
+        let item_id = item.clone();
+        let result = run_operation(py, &TOKIO_RUNTIME, &self.config, "patch_item_append_unique", kwargs, async move {
+            container.patch_item(pk, &item_id, patch, Some(options))
+                .await
+                .map_err(map_error)
+        });
+        
+        let result = match result {
+            Ok(result) => result,
+            Err(err) if err.is_instance_of::<CosmosAccessConditionFailedError>(py) => {
+                // The predicate failed: either the value is present or the path is not an array
+                let existing = self.read_item_value(py, read_pk, &item, kwargs)?;
+                if !json_value_at_path(&existing, path).is_some_and(Value::is_array) {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        format!("{} is not an array in item {}", path, item)
+                    ));
+                }
+                return json_to_py_dict(py, &self.with_id_field(existing));
+            }
+            Err(err) => return Err(correlate(py, err, kwargs)),
+        };
+        call_response_hook(py, kwargs, Some(result.status()), result.headers())?;
+        
+        let patched = result.into_body().json::<Value>().map_err(map_error)?;
+        json_to_py_dict(py, &self.with_id_field(patched))
     }
 
     /// Read container properties
+    /// Returns the properties as stored by the service, including `geospatialConfig`
     #[pyo3(signature = (**kwargs))]
     pub fn read<'py>(
         &self,
         py: Python<'py>,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
-        let dict = PyDict::new(py);
-        dict.set_item("id", &self.container_id)?;
-        Ok(dict)
+    ) -> PyResult<PyObject> {
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let properties = run_operation(py, &TOKIO_RUNTIME, &self.config, "read_container", kwargs, async move {
+            container.read(None)
+                .await
+                .map_err(map_error)?
+                .into_body()
+                .json::<Value>()
+                .map_err(map_error)
+        })?;
+
+        container_properties(py, &self.config, properties)
+    }
+
+    /// Wait until the container's indexing transformation has finished
+    /// Polls the container's `x-ms-documentdb-collection-index-transformation-progress` until it
+    /// reaches 100 and returns the elapsed seconds. Raises `CosmosTimeoutError` if `timeout`
+    /// (seconds or a `timedelta`) passes first.
+    #[pyo3(signature = (timeout=None, **kwargs))]
+    pub fn wait_until_ready(&self, py: Python, timeout: Option<&PyAny>, kwargs: Option<&PyDict>) -> PyResult<f64> {
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        let timeout = timeout.map(|t| positive_duration(t, "timeout")).transpose()?;
+        
+        let started = Instant::now();
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "wait_until_ready", kwargs, async move {
+            loop {
+                let mut headers = OperationHeaders::default();
+                headers.push(POPULATE_QUOTA_INFO, "true".to_string());
+                let options = ReadContainerOptions {
+                    method_options: method_options(headers),
+                };
+                let response = container.read(Some(options)).await.map_err(map_error)?;
+                // The header is absent when no transformation has ever run
+                let progress = headers::header_str(response.headers(), &INDEX_TRANSFORMATION_PROGRESS)
+                    .and_then(|p| p.parse::<u32>().ok())
+                    .unwrap_or(100);
+                if progress >= 100 {
+                    return Ok(started.elapsed().as_secs_f64());
+                }
+                
+                let remaining = timeout.map(|t| t.saturating_sub(started.elapsed()));
+                if remaining == Some(Duration::ZERO) {
+                    return Err(timeout_error("wait_until_ready", timeout.unwrap_or_default()));
+                }
+                let pause = remaining.map_or(READY_POLL_INTERVAL, |r| r.min(READY_POLL_INTERVAL));
+                tokio::time::sleep(pause).await;
+            }
+        }).map_err(|e| correlate(py, e, kwargs))
     }
 
     /// Delete this container
     #[pyo3(signature = (**kwargs))]
-    pub fn delete(&self, kwargs: Option<&PyDict>) -> PyResult<()> {
+    pub fn delete(&self, py: Python, kwargs: Option<&PyDict>) -> PyResult<()> {
+        self.config.check_writable("delete_container")?;
         let container = self.cosmos_client
             .database_client(&self.database_id)
             .container_client(&self.container_id);
         
-        TOKIO_RUNTIME.block_on(async move {
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "delete_container", kwargs, async move {
             container.delete(None)
                 .await
                 .map_err(map_error)
@@ -327,40 +912,604 @@ impl ContainerClient {
 
 // Helper methods for ContainerClient
 impl ContainerClient {
+    /// Convert a partition key value, or a list/tuple of hierarchical key components
     fn python_to_partition_key(&self, py: Python, pk: PyObject) -> PyResult<RustPartitionKey> {
-        if let Ok(s) = pk.extract::<String>(py) {
-            Ok(RustPartitionKey::from(s))
-        } else if let Ok(i) = pk.extract::<i64>(py) {
-            Ok(RustPartitionKey::from(i))
-        } else if let Ok(f) = pk.extract::<f64>(py) {
-            Ok(RustPartitionKey::from(f))
+        let pk = pk.as_ref(py);
+        let is_hierarchical = pk.is_instance_of::<PyList>() || pk.is_instance_of::<PyTuple>();
+        // Scalar keys are sent as-is, so the common case costs no container metadata read; a
+        // definition already read for another call still catches a MultiHash mismatch up front
+        if !is_hierarchical {
+            if let Some(definition) = self.partition_key_definition.get() {
+                if definition.kind == PartitionKeyKind::MultiHash {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Container '{}' has a hierarchical (MultiHash) partition key on paths {:?}; \
+                         pass the partition key as a list of values in that order, not {}",
+                        self.container_id, definition.paths, pk.repr()?
+                    )));
+                }
+            }
+            return Ok(RustPartitionKey::from(self.partition_key_value(py, pk)?));
+        }
+        
+        let definition = self.partition_key_definition(py)?;
+        let components = pk.iter()?
+            .map(|component| self.partition_key_value(py, component?))
+            .collect::<PyResult<Vec<_>>>()?;
+        if components.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Partition key for container '{}' is an empty list; pass one value per partition key path {:?}",
+                self.container_id, definition.paths
+            )));
+        }
+        match definition.kind {
+            PartitionKeyKind::Hash if components.len() > 1 => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Container '{}' has a single (Hash) partition key on path {:?}; \
+                     got {} partition key components",
+                    self.container_id, definition.paths.first().map(String::as_str).unwrap_or_default(), components.len()
+                )));
+            }
+            PartitionKeyKind::MultiHash if components.len() > definition.paths.len() => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Container '{}' has {} partition key paths {:?}; got {} partition key components",
+                    self.container_id, definition.paths.len(), definition.paths, components.len()
+                )));
+            }
+            _ => {}
+        }
+        match <[PartitionKeyValue; 3]>::try_from(components) {
+            Ok([a, b, c]) => Ok(RustPartitionKey::from((a, b, c))),
+            Err(components) => match <[PartitionKeyValue; 2]>::try_from(components) {
+                Ok([a, b]) => Ok(RustPartitionKey::from((a, b))),
+                Err(components) => match <[PartitionKeyValue; 1]>::try_from(components) {
+                    Ok([a]) => Ok(RustPartitionKey::from(a)),
+                    Err(_) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "Hierarchical partition keys must have between 1 and 3 components"
+                    )),
+                },
+            },
+        }
+    }
+
+    fn partition_key_value(&self, py: Python, value: &PyAny) -> PyResult<PartitionKeyValue> {
+        // An explicit None addresses items that have no value at the partition key path
+        if value.is_none() {
+            return Ok(PartitionKeyValue::from(UNDEFINED_PARTITION_KEY));
+        }
+        // UUIDs are stored as their canonical string form
+        let value = if value.is_instance(py.import("uuid")?.getattr("UUID")?)? {
+            value.str()?.as_ref()
+        } else {
+            value
+        };
+        let value = match self.partition_key_type {
+            Some(pk_type) => pk_type.coerce(py, value)?,
+            None => value,
+        };
+        
+        if let Ok(s) = value.extract::<String>() {
+            Ok(PartitionKeyValue::from(s))
+        } else if let Ok(i) = value.extract::<i64>() {
+            Ok(PartitionKeyValue::from(i))
+        } else if let Ok(f) = value.extract::<f64>() {
+            Ok(PartitionKeyValue::from(f))
         } else {
             Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-                "Partition key must be string, int, or float"
+                "Partition key must be string, int, float, uuid.UUID or None"
             ))
         }
     }
 
-    fn extract_partition_key(&self, py: Python, body: &PyDict, kwargs: Option<&PyDict>) -> PyResult<RustPartitionKey> {
-        // Try to get partition_key from kwargs first
+    /// With `strict_partition=True`, warn when the query filters the partition key path on a
+    /// value other than the `partition_key` kwarg, which scopes the query regardless
+    fn check_strict_partition(&self, py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<()> {
+        let Some(kw) = kwargs else {
+            return Ok(());
+        };
+        let strict = kw.get_item("strict_partition")?.map(|v| v.extract::<bool>()).transpose()?.unwrap_or(false);
+        let Some(pk) = kw.get_item("partition_key")?.filter(|_| strict) else {
+            return Ok(());
+        };
+        let [path] = self.partition_key_paths(py)? else {
+            return Ok(());
+        };
+        
+        let (sql, parameters) = query_parts(py, query, kwargs)?;
+        let field = path.trim_start_matches('/').replace('/', ".");
+        let partition_key = py_to_json(py, pk)?;
+        for operand in equality_operands(&sql, &field) {
+            let value = match operand {
+                Operand::Literal(value) => value,
+                Operand::Parameter(name) => match parameters.iter().find(|(n, _)| *n == name) {
+                    Some((_, value)) => value.clone(),
+                    None => continue,
+                },
+            };
+            if value != partition_key {
+                PyErr::warn(
+                    py,
+                    py.get_type::<pyo3::exceptions::PyUserWarning>(),
+                    &format!(
+                        "Query filters {} = {} but partition_key={} scopes it to another partition; \
+                         it can only return items of partition {}",
+                        path, value, partition_key, partition_key
+                    ),
+                    1,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The partition key scoping a query, from the `partition_key` kwarg
+    /// Without one, the empty key makes the SDK send the query across all partitions
+    fn query_partition_key(&self, py: Python, kwargs: Option<&PyDict>) -> PyResult<RustPartitionKey> {
         if let Some(kw) = kwargs {
-            if let Ok(Some(pk)) = kw.get_item("partition_key") {
+            if let Some(pk) = kw.get_item("partition_key")? {
                 return self.python_to_partition_key(py, pk.into());
             }
         }
+        Ok(RustPartitionKey::EMPTY)
+    }
+
+    pub(crate) fn fetch_query_page(
+        &self,
+        py: Python,
+        query: &PyAny,
+        max_item_count: Option<i64>,
+        continuation: Option<String>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<QueryPage> {
+        self.check_strict_partition(py, query, kwargs)?;
+        let (sql, parameters) = query_parts(py, query, kwargs)?;
+        let query = build_query(py, query, kwargs)?;
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
         
-        // Otherwise, try common partition key fields from the body
-        // Try common partition key field names (including "id" which is very common)
-        let common_pk_fields = ["id", "category", "partitionKey", "pk", "type", "tenantId"];
-        for field in &common_pk_fields {
-            if let Ok(Some(value)) = body.get_item(field) {
-                return self.python_to_partition_key(py, value.into());
+        let partition_key = self.query_partition_key(py, kwargs)?;
+        let mut operation_headers = OperationHeaders::from_kwargs(kwargs)?;
+        if let Some(count) = max_item_count {
+            push_max_item_count(&mut operation_headers, count)?;
+        }
+        let resuming = continuation.is_some();
+        if let Some(token) = continuation {
+            // Tokens are opaque, but must be non-empty header values
+            if token.trim().is_empty() || !token.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+                return Err(invalid_continuation_error(py, None));
             }
+            operation_headers.push(CONTINUATION, token);
+        }
+        let require_index = kwarg_flag(kwargs, "require_index")?;
+        if require_index || kwarg_flag(kwargs, "populate_index_metrics")? {
+            operation_headers.push(POPULATE_INDEX_METRICS, "true".to_string());
         }
+        let options = query_options_with_headers(kwargs, operation_headers)?;
+        let read_options = ReadOptions::from_kwargs(&self.config, kwargs)?;
         
-        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-            "Partition key not found in body or kwargs"
-        ))
+        let page = run_operation(py, &TOKIO_RUNTIME, &self.config, "query_items_paged", kwargs, async move {
+            let mut pages = container.query_items::<Value>(query, partition_key, Some(options))
+                .map_err(map_error)?
+                .into_pages();
+            
+            use futures::StreamExt;
+            pages.next().await.transpose().map_err(map_error)
+        }).map_err(|e| {
+            let e = correlate(py, e, kwargs);
+            if resuming && is_invalid_continuation(py, &e) {
+                invalid_continuation_error(py, Some(e))
+            } else {
+                e
+            }
+        })?;
+
+        let items = PyList::empty(py);
+        let Some(page) = page else {
+            return Ok(QueryPage {
+                items: items.into(),
+                continuation_token: None,
+                request_charge: None,
+                activity_id: None,
+                cursor: None,
+                index_metrics: None,
+            });
+        };
+        
+        let response_headers: &Headers = page.headers();
+        call_response_hook(py, kwargs, None, response_headers)?;
+        let continuation_token = page.continuation().map(str::to_string);
+        let request_charge = headers::request_charge(response_headers);
+        let activity_id = headers::header_str(response_headers, &ACTIVITY_ID);
+        let index_metrics = decode_index_metrics(py, response_headers)?;
+        if require_index {
+            check_index_used(py, &sql, index_metrics.as_ref())?;
+        }
+        for item in page.items() {
+            let py_item = json_to_py_dict(py, item)?;
+            read_options.apply(py, py_item.as_ref(py))?;
+            items.append(py_item)?;
+        }
+
+        let cursor = match &continuation_token {
+            Some(token) => Some(QueryCursor {
+                continuation_token: token.clone(),
+                query: sql,
+                parameters,
+                partition_key: self.partition_key_json(py, kwargs)?,
+                max_item_count,
+                database_id: self.database_id.clone(),
+                container_id: self.container_id.clone(),
+                container: None,
+            }),
+            None => None,
+        };
+
+        Ok(QueryPage {
+            items: items.into(),
+            continuation_token,
+            request_charge,
+            activity_id,
+            cursor,
+            index_metrics,
+        })
+    }
+
+    /// The `partition_key` kwarg as JSON, with UUIDs in their string form
+    fn partition_key_json(&self, py: Python, kwargs: Option<&PyDict>) -> PyResult<Option<Value>> {
+        let Some(pk) = kwargs.map(|kw| kw.get_item("partition_key")).transpose()?.flatten() else {
+            return Ok(None);
+        };
+        if pk.is_instance(py.import("uuid")?.getattr("UUID")?)? {
+            return Ok(Some(Value::String(pk.str()?.to_string())));
+        }
+        py_to_json(py, pk).map(Some)
+    }
+
+    fn ids_in_query(ids: &[String], parameters: &[(String, Value)]) -> PyResult<Query> {
+        let names: Vec<String> = (0..ids.len()).map(|i| format!("@id{}", i)).collect();
+        let mut query = Query::from(format!("SELECT * FROM c WHERE c.id IN ({})", names.join(", ")));
+        let ids = ids.iter().map(|id| Value::String(id.clone()));
+        for (name, value) in names.into_iter().zip(ids).chain(parameters.iter().cloned()) {
+            query = query.with_parameter(name, value)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid query parameter: {}", e)))?;
+        }
+        Ok(query)
+    }
+
+    fn extract_partition_key(&self, py: Python, body: &PyDict, kwargs: Option<&PyDict>) -> PyResult<RustPartitionKey> {
+        let value = self.partition_key_of(py, body, kwargs)?;
+        self.python_to_partition_key(py, value.into())
+    }
+    
+    /// The partition key value a write of `body` uses, before conversion: the `partition_key`
+    /// kwarg, or the body's value at each of the container's key paths
+    /// Guessing from common field names is opt-in with `auto_partition_key=True`, as a guess can
+    /// silently place an item in the wrong partition.
+    fn partition_key_of<'a>(&self, py: Python<'a>, body: &'a PyDict, kwargs: Option<&'a PyDict>) -> PyResult<&'a PyAny> {
+        let body = self.body_with_id(body)?;
+        
+        // An explicit kwarg always wins, including None for the undefined partition
+        if let Some(kw) = kwargs {
+            if let Some(pk) = kw.get_item("partition_key")? {
+                self.check_partition_key_matches(py, body, pk)?;
+                return Ok(pk);
+            }
+        }
+        
+        // Hierarchical keys are built from the body in the order of the container's paths
+        let paths = self.partition_key_paths(py)?;
+        if paths.len() > 1 {
+            let components = PyList::empty(py);
+            for path in paths {
+                let value = value_at_path(body, path)?.ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Item body is missing hierarchical partition key path '{}'", path
+                    ))
+                })?;
+                components.append(value)?;
+            }
+            return Ok(components.as_ref());
+        }
+        if let Some(path) = paths.first() {
+            if let Some(value) = value_at_path(body, path)? {
+                return Ok(value);
+            }
+        }
+        
+        if kwarg_flag(kwargs, "auto_partition_key")? {
+            let common_pk_fields = ["id", "category", "partitionKey", "pk", "type", "tenantId"];
+            for field in &common_pk_fields {
+                if let Some(value) = body.get_item(field)? {
+                    return Ok(value);
+                }
+            }
+        }
+        
+        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Item body has no value at partition key path '{}'; pass partition_key (None for the \
+             undefined partition), or auto_partition_key=True to guess it from common field names",
+            paths.first().map(String::as_str).unwrap_or_default()
+        )))
+    }
+    
+    /// Point-read an item for Python, applying the read options, along with its etag and the
+    /// diagnostics requested with `enable_diagnostics`
+    fn read_item_and_etag(
+        &self,
+        py: Python,
+        item: String,
+        partition_key: PyObject,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<(PyObject, Option<String>, Option<Py<PyDict>>)> {
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let pk = self.python_to_partition_key(py, partition_key)?;
+        let item_id = item.clone();
+        let fields = match kwargs {
+            Some(kw) => kw.get_item("fields")?.map(|f| f.extract::<Vec<String>>()).transpose()?,
+            None => None,
+        };
+        let read_options = ReadOptions::from_kwargs(&self.config, kwargs)?;
+        
+        let options = item_options(kwargs)?;
+        let fallback = FallbackRead::from_kwargs(kwargs)?;
+        let read_after_write = kwarg_flag(kwargs, "read_after_write")?;
+        let (result, degraded) = run_operation(py, &TOKIO_RUNTIME, &self.config, "read_item", kwargs, async move {
+            let mut retries = 0;
+            let first = loop {
+                let read = container.read_item::<Value>(pk.clone(), &item_id, Some(options.clone()));
+                let first = match fallback.as_ref().and_then(|f| f.timeout) {
+                    Some(timeout) => tokio::time::timeout(timeout, read).await.unwrap_or_else(|_| {
+                        let elapsed = std::io::Error::from(std::io::ErrorKind::TimedOut);
+                        Err(typespec::error::Error::new(typespec::error::ErrorKind::Io, elapsed))
+                    }),
+                    None => read.await,
+                };
+                match first {
+                    // The write may not have replicated to the replica that served the read yet
+                    Err(e) if read_after_write && retries < READ_AFTER_WRITE_RETRIES && is_not_found(&e) => {
+                        tokio::time::sleep(READ_AFTER_WRITE_BACKOFF * 2u32.pow(retries)).await;
+                        retries += 1;
+                    }
+                    first => break first,
+                }
+            };
+            let fallback = match (first, fallback) {
+                (Ok(response), _) => return Ok((response, false)),
+                // Timed out, either past `fallback_timeout` or as reported by the service or transport
+                (Err(e), Some(fallback)) if is_timeout(&e) => fallback,
+                (Err(e), _) => return Err(map_error(e)),
+            };
+            container.read_item::<Value>(pk, &item_id, Some(fallback.options))
+                .await
+                .map(|response| (response, true))
+                .map_err(map_error)
+        }).map_err(|e| correlate(py, e, kwargs))?;
+        call_read_response_hook(py, kwargs, Some(result.status()), result.headers(), degraded)?;
+        let diagnostics = requested_diagnostics(py, kwargs, Some(result.status()), result.headers())?;
+        let header_etag = headers::header_str(result.headers(), &ETAG);
+
+        // Extract the value from the Response, skipping unprojected fields when requested
+        let body = result.into_body();
+        let value = match fields {
+            Some(fields) => deserialize_projected(body.as_ref(), &fields)?,
+            None => parse_json_document(body.as_ref())?,
+        };
+        let etag = header_etag.or_else(|| value.get("_etag").and_then(Value::as_str).map(str::to_string));
+        let Some(value) = read_options.select(value)? else {
+            return Ok((py.None(), etag, diagnostics));
+        };
+        
+        let item = json_to_py(py, &value)?;
+        read_options.apply(py, item.as_ref(py))?;
+        Ok((item, etag, diagnostics))
+    }
+
+    /// `query_values`, answered from the client's query cache when it is enabled
+    /// Entries are keyed by container, SQL text, parameters, the client's partition key type and
+    /// the kwargs in `QUERY_CACHE_KEY_KWARGS`.
+    fn cached_query_values(&self, py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<Vec<Value>> {
+        let Some(cache) = &self.config.query_cache else {
+            return self.query_values(py, query, kwargs);
+        };
+        let (sql, parameters) = query_parts(py, query, kwargs)?;
+        let mut options = serde_json::Map::new();
+        if let Some(kw) = kwargs {
+            for name in QUERY_CACHE_KEY_KWARGS {
+                if let Some(value) = kw.get_item(name)? {
+                    options.insert(name.to_string(), Value::String(value.repr()?.to_string()));
+                }
+            }
+        }
+        // The declared key type changes how the partition_key kwarg is coerced
+        let partition_key_type = self.partition_key_type.map(|pk_type| format!("{:?}", pk_type));
+        let key = serde_json::json!([
+            self.database_id, self.container_id, sql, parameters, options, partition_key_type
+        ]).to_string();
+        
+        if let Some(results) = cache.get(&key) {
+            return Ok(results.as_ref().clone());
+        }
+        let results = self.query_values(py, query, kwargs)?;
+        cache.insert(key, Arc::new(results.clone()));
+        Ok(results)
+    }
+
+    /// Run a query to completion, returning the raw result rows
+    fn query_values(&self, py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<Vec<Value>> {
+        self.check_strict_partition(py, query, kwargs)?;
+        let query = build_query(py, query, kwargs)?;
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        // Without a partition_key kwarg the query fans out across all partitions
+        let partition_key = self.query_partition_key(py, kwargs)?;
+        
+        // Only the page size of each request changes; every page is still collected
+        let mut operation_headers = OperationHeaders::from_kwargs(kwargs)?;
+        if let Some(count) = kwargs.map(|kw| kw.get_item("max_item_count")).transpose()?.flatten() {
+            push_max_item_count(&mut operation_headers, count.extract()?)?;
+        }
+        let options = query_options_with_headers(kwargs, operation_headers)?;
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "query_items", kwargs, async move {
+            let mut result = Vec::new();
+            let mut stream = container.query_items::<Value>(query, partition_key, Some(options)).map_err(map_error)?;
+            
+            use futures::StreamExt;
+            while let Some(response) = stream.next().await {
+                match response {
+                    Ok(item) => {
+                        result.push(item);
+                    },
+                    Err(e) => return Err(map_error(e)),
+                }
+            }
+            
+            Ok::<_, PyErr>(result)
+        }).map_err(|e| correlate(py, e, kwargs))
+    }
+
+    /// Point-read an item as JSON
+    fn read_item_value(&self, py: Python, partition_key: RustPartitionKey, item: &str, kwargs: Option<&PyDict>) -> PyResult<Value> {
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let options = item_options(kwargs)?;
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "read_item", kwargs, async move {
+            let response = container.read_item::<Value>(partition_key, item, Some(options))
+                .await
+                .map_err(map_error)?;
+            parse_json_document(response.into_body().as_ref())
+        }).map_err(|e| correlate(py, e, kwargs))
+    }
+    
+    /// Read the item a create conflicted with, returning it if it equals the created body
+    fn read_identical_item(
+        &self,
+        py: Python,
+        partition_key: RustPartitionKey,
+        item_value: &Value,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<Option<Value>> {
+        let Some(item_id) = item_value.get("id").and_then(Value::as_str).map(str::to_string) else {
+            return Ok(None);
+        };
+        let container = self.cosmos_client
+            .database_client(&self.database_id)
+            .container_client(&self.container_id);
+        
+        let existing = run_operation(py, &TOKIO_RUNTIME, &self.config, "read_item", kwargs, async move {
+            let response = container.read_item::<Value>(partition_key, &item_id, None)
+                .await
+                .map_err(map_error)?;
+            parse_json_document(response.into_body().as_ref())
+        })?;
+        
+        let identical = without_system_properties(&existing) == without_system_properties(item_value);
+        Ok(identical.then_some(existing))
+    }
+
+    /// Partition key definition of the container, read from the service once per client
+    /// The read is bounded by the client's `operation_timeout` and counted as in flight.
+    fn partition_key_definition(&self, py: Python) -> PyResult<&PartitionKeyDefinition> {
+        self.partition_key_definition.get_or_try_init(|| {
+            let container = self.cosmos_client
+                .database_client(&self.database_id)
+                .container_client(&self.container_id);
+            
+            run_operation(py, &TOKIO_RUNTIME, &self.config, "read_container", None, async move {
+                let properties = container.read(None)
+                    .await
+                    .map_err(map_error)?
+                    .into_body()
+                    .json::<Value>()
+                    .map_err(map_error)?;
+                let definition = properties.get("partitionKey");
+                let paths = definition
+                    .and_then(|d| d.get("paths"))
+                    .and_then(Value::as_array)
+                    .map(|paths| paths.iter().filter_map(Value::as_str).map(str::to_string).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let kind = definition.and_then(|d| d.get("kind")).and_then(Value::as_str);
+                Ok(PartitionKeyDefinition {
+                    kind: PartitionKeyKind::from_definition(kind, paths.len()),
+                    paths,
+                })
+            })
+        })
+    }
+
+    /// Partition key paths from the container definition
+    fn partition_key_paths(&self, py: Python) -> PyResult<&[String]> {
+        self.partition_key_definition(py).map(|definition| definition.paths.as_slice())
+    }
+
+    /// Check that a `partition_key` kwarg agrees with the partition key value in the body
+    /// Bodies missing a partition key path are left for the service to reject
+    fn check_partition_key_matches(&self, py: Python, body: &PyDict, pk: &PyAny) -> PyResult<()> {
+        if self.config.partition_key_mismatch == PartitionKeyMismatch::Ignore {
+            return Ok(());
+        }
+        
+        let mut body_values = Vec::new();
+        for path in self.partition_key_paths(py)? {
+            match value_at_path(body, path)? {
+                Some(value) => body_values.push(value),
+                None => return Ok(()),
+            }
+        }
+        
+        let body_pk: &PyAny = match body_values.as_slice() {
+            [value] => value,
+            values => PyList::new(py, values),
+        };
+        let kwarg_pk: &PyAny = match pk.downcast::<PyTuple>() {
+            Ok(tuple) => PyList::new(py, tuple),
+            Err(_) => pk,
+        };
+        if body_pk.eq(kwarg_pk)? {
+            return Ok(());
+        }
+        
+        let message = format!(
+            "partition_key {} does not match {} in the item body", pk.repr()?, body_pk.repr()?
+        );
+        match self.config.partition_key_mismatch {
+            PartitionKeyMismatch::Ignore => Ok(()),
+            PartitionKeyMismatch::Raise => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(message)),
+            PartitionKeyMismatch::Warn => PyErr::warn(
+                py,
+                py.get_type::<pyo3::exceptions::PyUserWarning>(),
+                &message,
+                1,
+            ),
+        }
+    }
+    
+    /// The item body as the service will see it, with the client's `id_field` moved to `id`,
+    /// so that partition key paths such as `/id` resolve against it
+    fn body_with_id<'py>(&self, body: &'py PyDict) -> PyResult<&'py PyDict> {
+        let Some(field) = &self.config.id_field else {
+            return Ok(body);
+        };
+        let Some(logical_id) = body.get_item(field)? else {
+            return Ok(body);
+        };
+        let copy = body.copy()?;
+        copy.set_item("id", logical_id)?;
+        Ok(copy)
+    }
+    
+    /// An item returned by the service with its `id` moved to the client's `id_field`
+    fn with_id_field(&self, mut item: Value) -> Value {
+        if let Some(field) = &self.config.id_field {
+            move_id_to_id_field(&mut item, field);
+        }
+        item
     }
     
     fn extract_partition_key_from_kwargs(&self, kwargs: Option<&PyDict>) -> PyResult<RustPartitionKey> {
@@ -377,3 +1526,328 @@ impl ContainerClient {
         })
     }
 }
+
+/// The aggregate function of a `SELECT VALUE` query whose partial results can be merged
+/// AVG is not mergeable, as the partial averages do not carry their item counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Aggregate {
+    Count,
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn parse(sql: &str) -> PyResult<Self> {
+        let unsupported = || PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "query_items_partial requires a SELECT VALUE COUNT, SUM, MIN or MAX query, got {:?}", sql
+        ));
+        let mut words = sql.split_whitespace();
+        let (Some(select), Some(value), Some(call)) = (words.next(), words.next(), words.next()) else {
+            return Err(unsupported());
+        };
+        if !select.eq_ignore_ascii_case("SELECT") || !value.eq_ignore_ascii_case("VALUE") {
+            return Err(unsupported());
+        }
+        let function = call.split('(').next().unwrap_or_default().to_ascii_uppercase();
+        match function.as_str() {
+            "COUNT" => Ok(Self::Count),
+            "SUM" => Ok(Self::Sum),
+            "MIN" => Ok(Self::Min),
+            "MAX" => Ok(Self::Max),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// The result when no partition reported a value
+    fn empty(self) -> Value {
+        match self {
+            Self::Count => Value::from(0),
+            // Undefined in the service's terms, as for a single partition with no matches
+            Self::Sum | Self::Min | Self::Max => Value::Null,
+        }
+    }
+
+    /// Merge a partition's partial aggregate into the running one
+    fn merge(self, running: Option<Value>, partial: &Value) -> PyResult<Value> {
+        let Some(running) = running else {
+            return Ok(partial.clone());
+        };
+        match self {
+            Self::Count | Self::Sum => match (running.as_i64(), partial.as_i64()) {
+                (Some(a), Some(b)) => Ok(a.checked_add(b).map_or_else(|| Value::from(a as f64 + b as f64), Value::from)),
+                _ => {
+                    let total = running.as_f64().unwrap_or_default() + partial.as_f64().unwrap_or_default();
+                    Ok(Number::from_f64(total).map_or(Value::Null, Value::Number))
+                }
+            },
+            Self::Min | Self::Max => {
+                let ordering = match (&running, partial) {
+                    (Value::String(a), Value::String(b)) => a.cmp(b),
+                    (a, b) if a.is_number() && b.is_number() => {
+                        a.as_f64().unwrap_or_default().total_cmp(&b.as_f64().unwrap_or_default())
+                    }
+                    // The service orders numbers before strings
+                    (a, _) if a.is_number() => std::cmp::Ordering::Less,
+                    _ => std::cmp::Ordering::Greater,
+                };
+                let keep_running = (self == Self::Min) == ordering.is_le();
+                Ok(if keep_running { running } else { partial.clone() })
+            }
+        }
+    }
+}
+
+/// Set the number of items the service returns per query page
+fn push_max_item_count(headers: &mut OperationHeaders, count: i64) -> PyResult<()> {
+    if count <= 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "max_item_count must be a positive integer"
+        ));
+    }
+    headers.push(MAX_ITEM_COUNT, count.to_string());
+    Ok(())
+}
+
+/// Characters the service does not allow in an item id, as they clash with resource URLs
+const FORBIDDEN_ID_CHARACTERS: [char; 4] = ['/', '\\', '#', '?'];
+
+/// Reject an item id the service would refuse before it is sent
+/// The service answers such writes with errors that do not name the problem, or routes the
+/// request to the wrong URL altogether.
+fn check_item_id(id: &str) -> PyResult<()> {
+    if let Some(c) = id.chars().find(|c| FORBIDDEN_ID_CHARACTERS.contains(c)) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Item id {:?} contains {:?}; ids cannot contain '/', '\\', '#' or '?'", id, c
+        )));
+    }
+    if id.ends_with(' ') {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Item id {:?} ends with a space, which the service does not allow", id
+        )));
+    }
+    Ok(())
+}
+
+/// The dict a write returns: the body, converted from JSON if it was a string
+/// With `written`, the write's `ServerTimestamp` and the item the service returned, a copy of the
+/// body is returned with the server's `_ts`, so the caller's dict is left untouched.
+fn written_item<'py>(
+    py: Python<'py>,
+    body: &'py PyAny,
+    written: Option<(ServerTimestamp, Value)>,
+    kwargs: Option<&PyDict>,
+) -> PyResult<&'py PyDict> {
+    let item: &PyDict = match body.downcast::<PyDict>() {
+        Ok(dict) => dict,
+        Err(_) => py.import("json")?.call_method1("loads", (body,))?.extract()?,
+    };
+    let Some((timestamp, written)) = written else {
+        return Ok(item);
+    };
+    let Some(ts) = written.get("_ts").and_then(Value::as_i64) else {
+        return Ok(item);
+    };
+
+    let item = item.copy()?;
+    item.set_item("_ts", ts)?;
+    if let Some(field) = &timestamp.field {
+        item.set_item(field, ts)?;
+    }
+    let read_options = ReadOptions {
+        parse_timestamps: kwarg_flag(kwargs, "parse_timestamps")?,
+        ..Default::default()
+    };
+    read_options.apply(py, item)?;
+    Ok(item)
+}
+
+/// Look up a partition key path such as "/address/city" in an item body
+fn value_at_path<'py>(body: &'py PyDict, path: &str) -> PyResult<Option<&'py PyAny>> {
+    let mut current: &PyAny = body;
+    for segment in path.trim_start_matches('/').split('/') {
+        let Ok(dict) = current.downcast::<PyDict>() else {
+            return Ok(None);
+        };
+        match dict.get_item(segment)? {
+            Some(value) => current = value,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current))
+}
+
+/// Look up a path such as "/address/lines" in an item as JSON
+fn json_value_at_path<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.trim_start_matches('/').split('/').try_fold(item, |current, segment| current.get(segment))
+}
+
+/// Convert a patch path such as "/address/lines" to a query reference, `c["address"]["lines"]`
+fn property_reference(path: &str) -> PyResult<String> {
+    let segments: Vec<&str> = path.strip_prefix('/').map(|p| p.split('/').collect()).unwrap_or_default();
+    if segments.is_empty() || segments.iter().any(|s| s.is_empty()) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Invalid path '{}'; expected a path such as '/tags'", path)
+        ));
+    }
+    let mut reference = String::from("c");
+    for segment in segments {
+        // JSON string syntax is also a valid quoted property name in the query language
+        reference.push_str(&format!("[{}]", Value::String(segment.to_string())));
+    }
+    Ok(reference)
+}
+
+/// Operations accepted by `patch_item`, named as in the service's patch format
+const PATCH_OPERATIONS: [&str; 6] = ["add", "set", "replace", "remove", "incr", "move"];
+
+/// Build a patch document from operation dicts such as `{"op": "add", "path": "/tags/-", "value": "x"}`
+/// `increment` is accepted as an alias of `incr`, and `move` takes the source path as `from`.
+/// Malformed operations raise `ValueError` naming the operation's index before anything is sent.
+fn patch_document(py: Python, operations: &PyList) -> PyResult<PatchDocument> {
+    let invalid = |index: usize, reason: String| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Patch operation {}: {}", index, reason))
+    };
+    if operations.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "patch_operations must contain at least one operation"
+        ));
+    }
+    
+    let mut patch = PatchDocument::default();
+    for (index, operation) in operations.iter().enumerate() {
+        let operation = operation.downcast::<PyDict>()
+            .map_err(|_| invalid(index, "expected a dict with 'op' and 'path'".to_string()))?;
+        let field = |name: &str| {
+            operation.get_item(name)?.ok_or_else(|| invalid(index, format!("missing '{}'", name)))
+        };
+        let op: String = field("op")?.extract()?;
+        let path: String = field("path")?.extract()?;
+        if !path.starts_with('/') {
+            return Err(invalid(index, format!("path '{}' must start with '/'", path)));
+        }
+        
+        patch = match op.as_str() {
+            "add" => patch.with_add(path, py_to_json(py, field("value")?)?).map_err(map_json_error)?,
+            "set" => patch.with_set(path, py_to_json(py, field("value")?)?).map_err(map_error)?,
+            "replace" => patch.with_replace(path, py_to_json(py, field("value")?)?).map_err(map_error)?,
+            "remove" => patch.with_remove(path).map_err(map_error)?,
+            "incr" | "increment" => {
+                let Value::Number(amount) = py_to_json(py, field("value")?)? else {
+                    return Err(invalid(index, format!("'{}' needs a numeric value", op)));
+                };
+                patch.with_increment(path, amount).map_err(map_error)?
+            }
+            "move" => patch.with_move(field("from")?.extract::<String>()?, path).map_err(map_error)?,
+            other => {
+                return Err(invalid(index, format!(
+                    "unsupported op '{}'; expected one of {}", other, PATCH_OPERATIONS.join(", ")
+                )));
+            }
+        };
+    }
+    Ok(patch)
+}
+
+/// Pivot query rows into columns keyed by property name, filling missing properties with None
+fn rows_to_columns<'py>(py: Python<'py>, rows: Vec<PyObject>) -> PyResult<&'py PyDict> {
+    let mut columns: BTreeMap<String, Vec<PyObject>> = BTreeMap::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        let Ok(row) = row.as_ref(py).downcast::<PyDict>() else {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Row {} is not a JSON object; columnar results need queries that select objects, \
+                 not SELECT VALUE of a scalar", index
+            )));
+        };
+        for (name, value) in row {
+            let column = columns.entry(name.extract()?).or_insert_with(|| vec![py.None(); index]);
+            column.push(value.into());
+        }
+        // Pad the columns this row did not have
+        for values in columns.values_mut() {
+            values.resize(index + 1, py.None());
+        }
+    }
+
+    let dict = PyDict::new(py);
+    for (name, values) in columns {
+        dict.set_item(name, PyList::new(py, values))?;
+    }
+    Ok(dict)
+}
+
+/// Read a boolean kwarg, `false` when absent
+fn kwarg_flag(kwargs: Option<&PyDict>, name: &str) -> PyResult<bool> {
+    match kwargs {
+        Some(kw) => Ok(kw.get_item(name)?.map(|v| v.extract::<bool>()).transpose()?.unwrap_or(false)),
+        None => Ok(false),
+    }
+}
+
+/// Whether a write returns the item, from the `enable_content_response_on_write` kwarg (default True)
+/// `False` returns None and skips building the returned dict; it cannot be combined with the
+/// `server_timestamp` kwargs, which read the service's response body.
+fn return_written_item(kwargs: Option<&PyDict>, server_timestamp: Option<&ServerTimestamp>) -> PyResult<bool> {
+    let Some(value) = kwargs.map(|kw| kw.get_item("enable_content_response_on_write")).transpose()?.flatten() else {
+        return Ok(true);
+    };
+    let enabled = value.extract::<bool>()?;
+    if !enabled && server_timestamp.is_some() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "server_timestamp requires enable_content_response_on_write=True"
+        ));
+    }
+    Ok(enabled)
+}
+
+/// Decode the `x-ms-cosmos-index-utilization` header, base64-encoded JSON, into a dict
+fn decode_index_metrics(py: Python, response_headers: &Headers) -> PyResult<Option<PyObject>> {
+    let Some(encoded) = headers::header_str(response_headers, &INDEX_UTILIZATION) else {
+        return Ok(None);
+    };
+    let decoded = py.import("base64")?.call_method1("b64decode", (encoded,))?;
+    Ok(Some(py.import("json")?.call_method1("loads", (decoded,))?.into()))
+}
+
+/// Fail a `require_index=True` query for which the service suggested indexes it could not use
+fn check_index_used(py: Python, sql: &str, index_metrics: Option<&PyObject>) -> PyResult<()> {
+    let Some(metrics) = index_metrics.map(|m| m.as_ref(py)) else {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "require_index=True but the service returned no index metrics"
+        ));
+    };
+    let mut missing = Vec::new();
+    for key in ["PotentialSingleIndexes", "PotentialCompositeIndexes"] {
+        if let Ok(indexes) = metrics.get_item(key) {
+            for index in indexes.iter()? {
+                missing.push(index?.repr()?.to_string());
+            }
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "Query '{}' is not fully served by the indexing policy; missing indexes: {}",
+        sql, missing.join(", ")
+    )))
+}
+
+/// Whether a request failed because the resource does not exist (404)
+fn is_not_found(err: &typespec::error::Error) -> bool {
+    matches!(
+        err.kind(),
+        typespec::error::ErrorKind::HttpResponse { status, .. } if *status == azure_core::http::StatusCode::NotFound
+    )
+}
+
+/// Whether a request failed by timing out, on the service (408) or in transport
+fn is_timeout(err: &typespec::error::Error) -> bool {
+    if err.http_status() == Some(azure_core::http::StatusCode::RequestTimeout) {
+        return true;
+    }
+    if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+        return err.is_timeout();
+    }
+    err.downcast_ref::<std::io::Error>().is_some_and(|err| err.kind() == std::io::ErrorKind::TimedOut)
+}
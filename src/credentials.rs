@@ -0,0 +1,55 @@
+use pyo3::prelude::*;
+use async_trait::async_trait;
+use azure_core::credentials::{AccessToken, TokenCredential, TokenRequestOptions};
+use azure_core::time::OffsetDateTime;
+use typespec::error::{Error as TypeSpecError, ErrorKind};
+
+/// An `azure.identity`-style Python credential used as a Rust `TokenCredential`
+/// Any object with a synchronous `get_token(*scopes)` returning an `AccessToken` (a `token`
+/// string and an `expires_on` epoch time) works, e.g. `DefaultAzureCredential` or
+/// `ManagedIdentityCredential`. The Rust SDK caches the token and asks again before it expires.
+/// Requests may authenticate on a runtime worker thread, which takes the GIL to call
+/// `get_token`, so operations must release the GIL while they wait (see `run_operation`).
+#[derive(Debug)]
+pub struct PyTokenCredential {
+    credential: PyObject,
+}
+
+impl PyTokenCredential {
+    /// Wrap `credential` if it exposes `get_token`, as Azure AD credentials do
+    pub fn from_object(py: Python, credential: &PyObject) -> PyResult<Option<Self>> {
+        if !credential.as_ref(py).hasattr("get_token")? {
+            return Ok(None);
+        }
+        Ok(Some(Self { credential: credential.clone_ref(py) }))
+    }
+
+    fn request_token(&self, scopes: &[String]) -> PyResult<(String, i64)> {
+        Python::with_gil(|py| {
+            let token = self.credential.as_ref(py).call_method1("get_token", pyo3::types::PyTuple::new(py, scopes))?;
+            if token.hasattr("__await__")? {
+                return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "Async credentials are not supported; pass a credential from azure.identity rather than azure.identity.aio"
+                ));
+            }
+            Ok((token.getattr("token")?.extract()?, token.getattr("expires_on")?.extract()?))
+        })
+    }
+}
+
+#[async_trait]
+impl TokenCredential for PyTokenCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        _options: Option<TokenRequestOptions<'_>>,
+    ) -> azure_core::Result<AccessToken> {
+        let scopes: Vec<String> = scopes.iter().map(|scope| scope.to_string()).collect();
+        // The Python credential may block on network I/O, so keep it off the runtime's workers
+        let (token, expires_on) = tokio::task::block_in_place(|| self.request_token(&scopes))
+            .map_err(|e| TypeSpecError::new(ErrorKind::Credential, format!("Failed to get an access token: {}", e)))?;
+        let expires_on = OffsetDateTime::from_unix_timestamp(expires_on)
+            .map_err(|e| TypeSpecError::new(ErrorKind::Credential, format!("Invalid token expiry: {}", e)))?;
+        Ok(AccessToken::new(token, expires_on))
+    }
+}
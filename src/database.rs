@@ -1,30 +1,88 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
-use azure_data_cosmos::{CosmosClient as RustCosmosClient, models::{ContainerProperties, PartitionKeyDefinition}};
-use std::sync::Arc;
+use azure_core::http::{ClientMethodOptions, Context};
+use azure_data_cosmos::{CosmosClient as RustCosmosClient, CreateContainerOptions, Query, models::{ContainerProperties, PartitionKeyDefinition, ThroughputProperties}};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use crate::config::ClientConfig;
 use crate::container::ContainerClient;
 use crate::exceptions::map_error;
+use crate::options::BodyProperties;
+use crate::properties::{container_properties, database_properties, to_properties_value};
+use crate::query::build_query;
+use crate::types::PartitionKeyType;
 use once_cell::sync::Lazy;
 use tokio::runtime::Runtime;
+use crate::cancellation::{build_runtime, run_operation};
 
-static TOKIO_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
-    tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .expect("Failed to create Tokio runtime")
-});
+static TOKIO_RUNTIME: Lazy<Runtime> = Lazy::new(|| build_runtime("database"));
+
+/// Most container clients a database client keeps cached
+const MAX_CACHED_CONTAINERS: usize = 256;
+
+type ContainerKey = (String, Option<PartitionKeyType>);
+
+/// Container clients handed out by `get_container_client`, keyed by id and partition key type
+/// Holds at most `MAX_CACHED_CONTAINERS` clients, evicting the least recently used
+#[derive(Default)]
+struct ContainerCache {
+    clients: HashMap<ContainerKey, Py<ContainerClient>>,
+    /// Keys from least to most recently used
+    order: VecDeque<ContainerKey>,
+}
+
+impl ContainerCache {
+    fn get(&mut self, py: Python, key: &ContainerKey) -> Option<Py<ContainerClient>> {
+        let client = self.clients.get(key)?.clone_ref(py);
+        self.touch(key);
+        Some(client)
+    }
+
+    /// Cache `client` under `key`, returning the client already cached there if there is one
+    fn insert(&mut self, py: Python, key: ContainerKey, client: Py<ContainerClient>) -> Py<ContainerClient> {
+        if let Some(existing) = self.get(py, &key) {
+            return existing;
+        }
+        if self.clients.len() == MAX_CACHED_CONTAINERS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.clients.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.clients.insert(key, client.clone_ref(py));
+        client
+    }
+
+    /// Drop the clients of `container_id`, whatever their partition key type
+    fn remove(&mut self, container_id: &str) {
+        self.clients.retain(|(id, _), _| id != container_id);
+        self.order.retain(|(id, _)| id != container_id);
+    }
+
+    fn touch(&mut self, key: &ContainerKey) {
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(position).unwrap();
+            self.order.push_back(key);
+        }
+    }
+}
 
 #[pyclass(subclass)]
 pub struct DatabaseClient {
     cosmos_client: Arc<RustCosmosClient>,
+    config: Arc<ClientConfig>,
     database_id: String,
+    containers: Mutex<ContainerCache>,
 }
 
 impl DatabaseClient {
-    pub fn new(cosmos_client: Arc<RustCosmosClient>, database_id: String) -> Self {
+    pub fn new(cosmos_client: Arc<RustCosmosClient>, config: Arc<ClientConfig>, database_id: String) -> Self {
         Self {
             cosmos_client,
+            config,
             database_id,
+            containers: Mutex::new(ContainerCache::default()),
         }
     }
 }
@@ -32,31 +90,86 @@ impl DatabaseClient {
 #[pymethods]
 impl DatabaseClient {
     /// Create a new container
+    /// `partition_key` is a single path such as `"/category"`, or a dict with its `paths`.
+    /// `default_ttl` sets the item time to live in positive seconds, and `offer_throughput`
+    /// provisions dedicated manual throughput (RU/s).
     #[pyo3(signature = (id, partition_key, **kwargs))]
     pub fn create_container(
         &self,
+        py: Python,
         id: String,
-        partition_key: &PyDict,
+        partition_key: &PyAny,
         kwargs: Option<&PyDict>,
     ) -> PyResult<ContainerClient> {
+        self.config.check_writable("create_container")?;
         let db_client = self.cosmos_client.database_client(&self.database_id);
         
         // Extract partition key path
-        let paths = partition_key.get_item("paths")?
-            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("partition_key must have 'paths'"))?;
-        let path_list = paths.extract::<Vec<String>>()?;
-        let partition_key_path = path_list.get(0)
-            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("partition_key paths cannot be empty"))?
-            .clone();
+        let path_list = match partition_key.extract::<String>() {
+            Ok(path) => vec![path],
+            Err(_) => {
+                let partition_key = partition_key.downcast::<PyDict>().map_err(|_| PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                    "partition_key must be a path string such as '/category' or a dict with 'paths'"
+                ))?;
+                let paths = partition_key.get_item("paths")?
+                    .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>("partition_key must have 'paths'"))?;
+                paths.extract::<Vec<String>>()?
+            }
+        };
+        if path_list.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("partition_key paths cannot be empty"));
+        }
+        if let Some(path) = path_list.iter().find(|path| !path.starts_with('/')) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Partition key path '{}' must start with '/'", path
+            )));
+        }
+        
+        let mut default_ttl = None;
+        let mut offer_throughput = None;
+        // `ContainerProperties` has no geospatial field, so it is merged into the body on the way out
+        let mut extra_properties = serde_json::Map::new();
+        if let Some(kw) = kwargs {
+            if let Some(config) = kw.get_item("geospatial_config")? {
+                extra_properties.insert("geospatialConfig".to_string(), geospatial_config(config)?);
+            }
+            if let Some(value) = kw.get_item("default_ttl")? {
+                let ttl = value.extract::<i64>()?;
+                // The Rust SDK models the TTL as an unsigned duration, so -1 cannot be sent
+                if ttl == -1 {
+                    return Err(PyErr::new::<pyo3::exceptions::PyNotImplementedError, _>(
+                        "default_ttl=-1 is not supported yet: the Rust SDK cannot send a negative TTL"
+                    ));
+                }
+                // Zero and other negative values are rejected by the service
+                if ttl <= 0 {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "default_ttl must be a positive number of seconds, got {}", ttl
+                    )));
+                }
+                default_ttl = Some(std::time::Duration::from_secs(ttl as u64));
+            }
+            if let Some(value) = kw.get_item("offer_throughput")? {
+                offer_throughput = Some(value.extract::<usize>()?);
+            }
+        }
+        let options = Some(CreateContainerOptions {
+            method_options: ClientMethodOptions {
+                context: Context::new().with_value(BodyProperties(extra_properties)),
+            },
+            throughput: offer_throughput.map(ThroughputProperties::manual),
+        });
         
         let container_id = id.clone();
-        TOKIO_RUNTIME.block_on(async move {
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "create_container", kwargs, async move {
+            // Several paths define a hierarchical (MultiHash) partition key
             let props = ContainerProperties {
                 id: container_id.into(),
-                partition_key: PartitionKeyDefinition::from(partition_key_path),
+                partition_key: PartitionKeyDefinition::new(path_list),
+                default_ttl,
                 ..Default::default()
             };
-            db_client.create_container(props, None)
+            db_client.create_container(props, options)
                 .await
                 .map_err(map_error)
         })?;
@@ -64,18 +177,47 @@ impl DatabaseClient {
         // Return ContainerClient like V4 does
         Ok(ContainerClient::new(
             self.cosmos_client.clone(),
+            self.config.clone(),
             self.database_id.clone(),
             id,
         ))
     }
 
     /// Get a container client
-    pub fn get_container_client(&self, container_id: String) -> PyResult<ContainerClient> {
-        Ok(ContainerClient::new(
-            self.cosmos_client.clone(),
-            self.database_id.clone(),
-            container_id,
-        ))
+    /// `partition_key_type` ("string" or "number") coerces partition key values to the stored type
+    /// With `validate=True` the container is read once, so a missing container fails here
+    /// Clients are cached, so repeated calls return the same client and share its cached
+    /// container metadata such as the partition key definition. The least recently used
+    /// client is evicted once `MAX_CACHED_CONTAINERS` are cached.
+    #[pyo3(signature = (container_id, partition_key_type=None, validate=false))]
+    pub fn get_container_client(
+        &self,
+        py: Python,
+        container_id: String,
+        partition_key_type: Option<&str>,
+        validate: bool,
+    ) -> PyResult<Py<ContainerClient>> {
+        let partition_key_type = partition_key_type.map(PartitionKeyType::parse).transpose()?;
+        let key = (container_id, partition_key_type);
+        let cached = self.containers.lock().unwrap().get(py, &key);
+        let container = match cached {
+            Some(container) => container,
+            None => {
+                let container = Py::new(py, ContainerClient::new(
+                    self.cosmos_client.clone(),
+                    self.config.clone(),
+                    self.database_id.clone(),
+                    key.0.clone(),
+                ).with_partition_key_type(partition_key_type))?;
+                // Another thread may have cached a client meanwhile; keep the first one
+                self.containers.lock().unwrap().insert(py, key, container)
+            }
+        };
+
+        if validate {
+            container.borrow(py).read(py, None)?;
+        }
+        Ok(container)
     }
 
     /// Delete a container
@@ -86,9 +228,12 @@ impl DatabaseClient {
         container_id: String,
         kwargs: Option<&PyDict>,
     ) -> PyResult<()> {
+        self.config.check_writable("delete_container")?;
         let db_client = self.cosmos_client.database_client(&self.database_id);
+        // A container created again under this id may have a different partition key
+        self.containers.lock().unwrap().remove(&container_id);
         
-        TOKIO_RUNTIME.block_on(async move {
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "delete_container", kwargs, async move {
             let container = db_client.container_client(&container_id);
             container.delete(None)
                 .await
@@ -104,18 +249,19 @@ impl DatabaseClient {
         &self,
         py: Python<'py>,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<&'py PyDict> {
+    ) -> PyResult<PyObject> {
         let db_client = self.cosmos_client.database_client(&self.database_id);
         
-        TOKIO_RUNTIME.block_on(async move {
+        let properties = run_operation(py, &TOKIO_RUNTIME, &self.config, "read_database", kwargs, async move {
             db_client.read(None)
                 .await
+                .map_err(map_error)?
+                .into_body()
+                .json::<Value>()
                 .map_err(map_error)
         })?;
 
-        let dict = PyDict::new(py);
-        dict.set_item("id", &self.database_id)?;
-        Ok(dict)
+        database_properties(py, &self.config, properties)
     }
 
     /// List all containers
@@ -124,40 +270,108 @@ impl DatabaseClient {
         &self,
         py: Python<'py>,
         kwargs: Option<&PyDict>,
-    ) -> PyResult<Vec<&'py PyDict>> {
+    ) -> PyResult<Vec<PyObject>> {
+        self.fetch_containers(py, Query::from("SELECT * FROM containers"), kwargs)
+    }
+
+    /// List the containers matching a query over the database's container resources
+    /// `query` is a SQL string or `QueryBuilder`, with a `parameters` kwarg as for item queries,
+    /// e.g. `"SELECT * FROM c WHERE STARTSWITH(c.id, @prefix)"`.
+    #[pyo3(signature = (query, **kwargs))]
+    pub fn query_containers<'py>(
+        &self,
+        py: Python<'py>,
+        query: &PyAny,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<Vec<PyObject>> {
+        let query = build_query(py, query, kwargs)?;
+        self.fetch_containers(py, query, kwargs)
+    }
+
+    /// List the offers (provisioned throughput) of the database and each of its containers
+    /// Each offer is a dict with "resource" ("database" or "container"), "id", "mode"
+    /// ("manual" or "autoscale") and "throughput": the manual RU/s, or the autoscale maximum.
+    /// Resources without their own offer, such as containers sharing database throughput, are
+    /// left out, so serverless accounts return an empty list.
+    #[pyo3(signature = (**kwargs))]
+    pub fn list_offers(&self, py: Python, kwargs: Option<&PyDict>) -> PyResult<Vec<PyObject>> {
         let db_client = self.cosmos_client.database_client(&self.database_id);
+        let database_id = self.database_id.clone();
         
-        let containers = TOKIO_RUNTIME.block_on(async move {
-            let mut result = Vec::new();
+        let offers = run_operation(py, &TOKIO_RUNTIME, &self.config, "list_offers", kwargs, async move {
+            let mut container_ids = Vec::new();
             let mut stream = db_client.query_containers("SELECT * FROM containers", None).map_err(map_error)?;
             
             use futures::StreamExt;
-            while let Some(response) = stream.next().await {
-                match response {
-                    Ok(container) => result.push(container),
-                    Err(e) => return Err(map_error(e)),
-                }
+            while let Some(container) = stream.next().await {
+                container_ids.push(container.map_err(map_error)?.id.to_string());
             }
             
-            Ok::<_, PyErr>(result)
+            // Offers are read concurrently, one request per resource
+            let database_offer = async {
+                let offer = db_client.read_throughput(None).await.map_err(map_error)?;
+                let offer = offer.map(|offer| offer.into_body().json::<ThroughputProperties>()).transpose().map_err(map_error)?;
+                Ok::<_, PyErr>(offer.map(|offer| ("database", database_id, offer)))
+            };
+            let container_offers = futures::future::try_join_all(container_ids.into_iter().map(|id| {
+                let container = db_client.container_client(&id);
+                async move {
+                    let offer = container.read_throughput(None).await.map_err(map_error)?;
+                    let offer = offer.map(|offer| offer.into_body().json::<ThroughputProperties>()).transpose().map_err(map_error)?;
+                    Ok::<_, PyErr>(offer.map(|offer| ("container", id, offer)))
+                }
+            }));
+            let (database_offer, container_offers) = futures::try_join!(database_offer, container_offers)?;
+            Ok::<_, PyErr>(database_offer.into_iter().chain(container_offers.into_iter().flatten()).collect::<Vec<_>>())
         })?;
 
-        let mut py_containers = Vec::new();
-        for container in containers {
-            let dict = PyDict::new(py);
-            dict.set_item("id", format!("{:?}", container))?;
-            py_containers.push(dict);
-        }
+        offers
+            .into_iter()
+            .map(|(resource, id, offer)| {
+                let dict = PyDict::new(py);
+                dict.set_item("resource", resource)?;
+                dict.set_item("id", id)?;
+                match offer.autoscale_maximum() {
+                    Some(maximum) => {
+                        dict.set_item("mode", "autoscale")?;
+                        dict.set_item("throughput", maximum)?;
+                    }
+                    None => {
+                        dict.set_item("mode", "manual")?;
+                        dict.set_item("throughput", offer.throughput())?;
+                    }
+                }
+                Ok(dict.into())
+            })
+            .collect()
+    }
 
-        Ok(py_containers)
+    /// Run setup steps in order, stopping at the first failure
+    /// Each step is a dict with a "type" ("create_container", "delete_container", "create_item"
+    /// or "upsert_item") and that operation's parameters. Steps are not transactional: steps
+    /// completed before a failure are left in place.
+    pub fn run_setup(&self, py: Python, steps: Vec<&PyDict>) -> PyResult<usize> {
+        self.config.check_writable("run_setup")?;
+        for (index, step) in steps.iter().enumerate() {
+            if let Err(err) = self.run_setup_step(py, step) {
+                let step_type = step.get_item("type")?.map(|t| t.to_string()).unwrap_or_default();
+                let setup_err = PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                    format!("Setup step {} ({}) failed: {}", index, step_type, err)
+                );
+                setup_err.set_cause(py, Some(err));
+                return Err(setup_err);
+            }
+        }
+        Ok(steps.len())
     }
 
     /// Delete this database
     #[pyo3(signature = (**kwargs))]
-    pub fn delete(&self, kwargs: Option<&PyDict>) -> PyResult<()> {
+    pub fn delete(&self, py: Python, kwargs: Option<&PyDict>) -> PyResult<()> {
+        self.config.check_writable("delete_database")?;
         let db_client = self.cosmos_client.database_client(&self.database_id);
         
-        TOKIO_RUNTIME.block_on(async move {
+        run_operation(py, &TOKIO_RUNTIME, &self.config, "delete_database", kwargs, async move {
             db_client.delete(None)
                 .await
                 .map_err(map_error)
@@ -171,3 +385,86 @@ impl DatabaseClient {
         Ok(self.database_id.clone())
     }
 }
+
+// Helper methods for DatabaseClient
+impl DatabaseClient {
+    /// Drain a container query into properties, as dicts or `ContainerProperties`
+    fn fetch_containers(&self, py: Python, query: Query, kwargs: Option<&PyDict>) -> PyResult<Vec<PyObject>> {
+        let db_client = self.cosmos_client.database_client(&self.database_id);
+        
+        let containers = run_operation(py, &TOKIO_RUNTIME, &self.config, "list_containers", kwargs, async move {
+            let mut result = Vec::new();
+            let mut stream = db_client.query_containers(query, None).map_err(map_error)?;
+            
+            use futures::StreamExt;
+            while let Some(response) = stream.next().await {
+                match response {
+                    Ok(container) => result.push(container),
+                    Err(e) => return Err(map_error(e)),
+                }
+            }
+            
+            Ok::<_, PyErr>(result)
+        })?;
+
+        containers
+            .iter()
+            .map(|container| container_properties(py, &self.config, to_properties_value(container)?))
+            .collect()
+    }
+
+    fn run_setup_step<'py>(&self, py: Python<'py>, step: &'py PyDict) -> PyResult<()> {
+        fn param<'py>(step: &'py PyDict, name: &str) -> PyResult<&'py PyAny> {
+            step.get_item(name)?.ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyKeyError, _>(format!("setup step is missing '{}'", name))
+            })
+        }
+        let param = |name: &str| param(step, name);
+        
+        match param("type")?.extract::<&str>()? {
+            "create_container" => {
+                self.create_container(py, param("id")?.extract()?, param("partition_key")?.downcast::<PyDict>()?, None)?;
+            }
+            "delete_container" => {
+                self.delete_container(py, param("id")?.extract()?, None)?;
+            }
+            step_type @ ("create_item" | "upsert_item") => {
+                let container = self.get_container_client(py, param("container")?.extract()?, None, false)?;
+                let container = container.borrow(py);
+                let kwargs = PyDict::new(py);
+                if let Some(pk) = step.get_item("partition_key")? {
+                    kwargs.set_item("partition_key", pk)?;
+                }
+                if step_type == "create_item" {
+                    container.create_item(py, param("body")?, Some(kwargs))?;
+                } else {
+                    container.upsert_item(py, param("body")?, Some(kwargs))?;
+                }
+            }
+            other => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Unknown setup step type '{}'", other)
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `geospatialConfig` container property for a `geospatial_config` kwarg given as a type
+/// string or as `{"type": ...}`
+fn geospatial_config(config: &PyAny) -> PyResult<Value> {
+    let geospatial_type = match config.downcast::<PyDict>() {
+        Ok(dict) => dict.get_item("type")?.ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("geospatial_config must have a 'type'")
+        })?.extract::<String>()?,
+        Err(_) => config.extract::<String>()?,
+    };
+
+    match geospatial_type.as_str() {
+        "Geography" | "Geometry" => Ok(serde_json::json!({ "type": geospatial_type })),
+        other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "geospatial_config type must be 'Geography' or 'Geometry', got '{}'", other
+        ))),
+    }
+}
@@ -0,0 +1,90 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use azure_core::http::headers::Headers;
+use azure_core::http::StatusCode;
+use crate::headers::{self, ACTIVITY_ID, PARTITION_KEY_RANGE_ID, PHYSICAL_PARTITION_ID, SESSION_TOKEN};
+
+/// Build the diagnostics dict for a single response, as passed to `response_hook`
+/// Partition attribution headers are only returned by the service for some operations,
+/// so their keys are None when absent.
+pub fn response_diagnostics<'py>(
+    py: Python<'py>,
+    status: Option<StatusCode>,
+    response_headers: &Headers,
+) -> PyResult<&'py PyDict> {
+    let dict = PyDict::new(py);
+    dict.set_item("status_code", status.map(u16::from))?;
+    dict.set_item("request_charge", headers::request_charge(response_headers))?;
+    dict.set_item("activity_id", headers::header_str(response_headers, &ACTIVITY_ID))?;
+    dict.set_item("session_token", headers::header_str(response_headers, &SESSION_TOKEN))?;
+    dict.set_item(
+        "physical_partition_id",
+        headers::header_str(response_headers, &PHYSICAL_PARTITION_ID),
+    )?;
+    dict.set_item(
+        "partition_key_range_id",
+        headers::header_str(response_headers, &PARTITION_KEY_RANGE_ID),
+    )?;
+    Ok(dict)
+}
+
+/// Call the `response_hook` kwarg, if one was given, with the diagnostics of a response
+pub fn call_response_hook(
+    py: Python,
+    kwargs: Option<&PyDict>,
+    status: Option<StatusCode>,
+    response_headers: &Headers,
+) -> PyResult<()> {
+    call_read_response_hook(py, kwargs, status, response_headers, false)
+}
+
+/// Call the `response_hook` kwarg for a read, with `degraded` set when the read was answered
+/// at `fallback_consistency` after the read at the requested consistency timed out
+pub fn call_read_response_hook(
+    py: Python,
+    kwargs: Option<&PyDict>,
+    status: Option<StatusCode>,
+    response_headers: &Headers,
+    degraded: bool,
+) -> PyResult<()> {
+    let Some(hook) = kwargs.map(|kw| kw.get_item("response_hook")).transpose()?.flatten() else {
+        return Ok(());
+    };
+    if hook.is_none() {
+        return Ok(());
+    }
+    let diagnostics = response_diagnostics(py, status, response_headers)?;
+    diagnostics.set_item("correlation_id", kwargs.and_then(|kw| kw.get_item("correlation_id").ok().flatten()))?;
+    diagnostics.set_item("degraded", degraded)?;
+    hook.call1((diagnostics,))?;
+    Ok(())
+}
+
+/// The diagnostics of a response to return with the operation's result, when the caller passed
+/// `enable_diagnostics=True`
+pub fn requested_diagnostics(
+    py: Python,
+    kwargs: Option<&PyDict>,
+    status: Option<StatusCode>,
+    response_headers: &Headers,
+) -> PyResult<Option<Py<PyDict>>> {
+    let enabled = kwargs
+        .map(|kw| kw.get_item("enable_diagnostics"))
+        .transpose()?
+        .flatten()
+        .map(|value| value.extract::<bool>())
+        .transpose()?
+        .unwrap_or(false);
+    if !enabled {
+        return Ok(None);
+    }
+    Ok(Some(response_diagnostics(py, status, response_headers)?.into()))
+}
+
+/// Pair `result` with its diagnostics as `(result, diagnostics)`, if they were requested
+pub fn with_diagnostics(py: Python, result: PyObject, diagnostics: Option<Py<PyDict>>) -> PyObject {
+    match diagnostics {
+        Some(diagnostics) => (result, diagnostics).into_py(py),
+        None => result,
+    }
+}
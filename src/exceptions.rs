@@ -1,6 +1,10 @@
 use pyo3::prelude::*;
-use pyo3::exceptions::PyException;
-use typespec::error::Error as TypeSpecError;
+use pyo3::exceptions::{PyException, PyPermissionError, PyTimeoutError, PyValueError};
+use pyo3::types::PyDict;
+use std::time::Duration;
+use azure_core::http::headers::Headers;
+use typespec::error::{Error as TypeSpecError, ErrorKind};
+use crate::headers::{self, ACTIVITY_ID, ETAG, RETRY_AFTER_MS};
 
 // Define custom exceptions matching the existing Python SDK
 
@@ -8,27 +12,167 @@ pyo3::create_exception!(azure.cosmos.exceptions, CosmosHttpResponseError, PyExce
 pyo3::create_exception!(azure.cosmos.exceptions, CosmosResourceNotFoundError, CosmosHttpResponseError);
 pyo3::create_exception!(azure.cosmos.exceptions, CosmosResourceExistsError, CosmosHttpResponseError);
 pyo3::create_exception!(azure.cosmos.exceptions, CosmosAccessConditionFailedError, CosmosHttpResponseError);
+pyo3::create_exception!(azure.cosmos.exceptions, CosmosServiceUnavailableError, CosmosHttpResponseError);
+pyo3::create_exception!(azure.cosmos.exceptions, CosmosInvalidContinuationTokenError, CosmosHttpResponseError);
+pyo3::create_exception!(azure.cosmos.exceptions, CosmosResponseTooLargeError, CosmosHttpResponseError);
+// A ValueError, as failures to deserialize a response were before it was introduced
+pyo3::create_exception!(azure.cosmos.exceptions, CosmosDeserializationError, PyValueError);
+// Raised client-side when an operation exceeds its `operation_timeout`, so no response exists
+pyo3::create_exception!(azure.cosmos.exceptions, CosmosTimeoutError, PyTimeoutError);
+// Raised client-side by a `read_only=True` client before a mutation sends anything
+pyo3::create_exception!(azure.cosmos.exceptions, CosmosReadOnlyError, PyPermissionError);
 
 pub fn register_exceptions(m: &PyModule) -> PyResult<()> {
     m.add("CosmosHttpResponseError", m.py().get_type::<CosmosHttpResponseError>())?;
     m.add("CosmosResourceNotFoundError", m.py().get_type::<CosmosResourceNotFoundError>())?;
     m.add("CosmosResourceExistsError", m.py().get_type::<CosmosResourceExistsError>())?;
     m.add("CosmosAccessConditionFailedError", m.py().get_type::<CosmosAccessConditionFailedError>())?;
+    m.add("CosmosServiceUnavailableError", m.py().get_type::<CosmosServiceUnavailableError>())?;
+    m.add("CosmosInvalidContinuationTokenError", m.py().get_type::<CosmosInvalidContinuationTokenError>())?;
+    m.add("CosmosResponseTooLargeError", m.py().get_type::<CosmosResponseTooLargeError>())?;
+    m.add("CosmosDeserializationError", m.py().get_type::<CosmosDeserializationError>())?;
+    m.add("CosmosTimeoutError", m.py().get_type::<CosmosTimeoutError>())?;
+    m.add("CosmosReadOnlyError", m.py().get_type::<CosmosReadOnlyError>())?;
     Ok(())
 }
 
+/// Map a JSON (de)serialization failure to `ValueError`, as the inline conversions do
+pub fn map_json_error(err: serde_json::Error) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(format!("JSON error: {}", err))
+}
+
 pub fn map_error(err: TypeSpecError) -> PyErr {
     // Map Rust SDK errors to Python exceptions
     let error_msg = format!("{}", err);
+    let response_headers = error_response_headers(&err);
+    let activity_id = response_headers.and_then(|h| headers::header_str(h, &ACTIVITY_ID));
+    let request_charge = response_headers.and_then(headers::request_charge);
+    let retry_after_ms = response_headers
+        .and_then(|h| h.get_optional_str(&RETRY_AFTER_MS))
+        .and_then(|v| v.parse::<f64>().ok());
+    
+    let status_code = match err.kind() {
+        ErrorKind::HttpResponse { status, .. } => Some(u16::from(*status)),
+        _ => None,
+    };
     
-    // Check for HTTP status codes in the error
-    if error_msg.contains("404") || error_msg.contains("NotFound") {
-        CosmosResourceNotFoundError::new_err(error_msg)
+    // Responses are classified by their status code; errors raised before a response, such as
+    // those of the client's own policies, by their message. The size guard's message carries
+    // byte counts that could look like status codes, so it is matched first.
+    let py_err = if error_msg.contains("ResponseTooLarge") {
+        CosmosResponseTooLargeError::new_err(error_msg.clone())
+    } else if let Some(status_code) = status_code {
+        match status_code {
+            404 => CosmosResourceNotFoundError::new_err(error_msg.clone()),
+            409 => CosmosResourceExistsError::new_err(error_msg.clone()),
+            412 => CosmosAccessConditionFailedError::new_err(error_msg.clone()),
+            503 => CosmosServiceUnavailableError::new_err(error_msg.clone()),
+            _ => CosmosHttpResponseError::new_err(error_msg.clone()),
+        }
+    } else if error_msg.contains("404") || error_msg.contains("NotFound") {
+        CosmosResourceNotFoundError::new_err(error_msg.clone())
     } else if error_msg.contains("409") || error_msg.contains("Conflict") {
-        CosmosResourceExistsError::new_err(error_msg)
+        CosmosResourceExistsError::new_err(error_msg.clone())
     } else if error_msg.contains("412") || error_msg.contains("PreconditionFailed") {
-        CosmosAccessConditionFailedError::new_err(error_msg)
+        CosmosAccessConditionFailedError::new_err(error_msg.clone())
+    } else if error_msg.contains("503") || error_msg.contains("ServiceUnavailable") {
+        CosmosServiceUnavailableError::new_err(error_msg.clone())
     } else {
-        CosmosHttpResponseError::new_err(error_msg)
+        CosmosHttpResponseError::new_err(error_msg.clone())
+    };
+    
+    Python::with_gil(|py| {
+        let value = py_err.value(py);
+        // Attributes are best effort; the exception is raised either way
+        let _ = value.setattr("status_code", status_code);
+        let _ = value.setattr("message", error_msg);
+        let _ = value.setattr("activity_id", activity_id);
+        let _ = value.setattr("request_charge", request_charge);
+        let _ = value.setattr("retry_after_ms", retry_after_ms);
+        let _ = value.setattr("correlation_id", py.None());
+        // A failed If-Match may report the item's current etag, saving a re-read before retrying
+        if py_err.is_instance_of::<CosmosAccessConditionFailedError>(py) {
+            let _ = value.setattr("etag", response_headers.and_then(|h| headers::header_str(h, &ETAG)));
+        }
+    });
+    py_err
+}
+
+/// Headers of the failed request's response, when the error carries the raw response
+fn error_response_headers(err: &TypeSpecError) -> Option<&Headers> {
+    match err.kind() {
+        ErrorKind::HttpResponse { raw_response: Some(response), .. } => Some(response.headers()),
+        _ => None,
+    }
+}
+
+/// A `CosmosDeserializationError` for a document that cannot be converted at `path`
+pub fn deserialization_error(path: String, reason: String) -> PyErr {
+    let py_err = CosmosDeserializationError::new_err(format!("Cannot deserialize document at {}: {}", path, reason));
+    Python::with_gil(|py| {
+        let value = py_err.value(py);
+        let _ = value.setattr("path", path);
+        let _ = value.setattr("reason", reason);
+    });
+    py_err
+}
+
+/// A `CosmosInvalidContinuationTokenError` for a continuation token a query cannot resume from
+/// `cause` is the service's rejection of the token, whose response attributes are carried over;
+/// it is `None` for tokens rejected before sending, which have no response.
+pub fn invalid_continuation_error(py: Python, cause: Option<PyErr>) -> PyErr {
+    let message = "The continuation token is not valid for this query; it may be corrupted or come from an \
+                   incompatible SDK version. Restart the query without a continuation token.";
+    let py_err = CosmosInvalidContinuationTokenError::new_err(message);
+    let value = py_err.value(py);
+    let _ = value.setattr("message", message);
+    for attribute in ["status_code", "activity_id", "request_charge", "retry_after_ms", "correlation_id"] {
+        let carried = cause.as_ref().and_then(|cause| cause.value(py).getattr(attribute).ok());
+        let _ = value.setattr(attribute, carried.map_or_else(|| py.None(), Into::into));
+    }
+    py_err.set_cause(py, cause);
+    py_err
+}
+
+/// Whether an error resuming a query is the service rejecting its continuation token
+/// The token is the only part of a resumed request that can be malformed, so any 400 response
+/// is taken as a rejection; `status_code` is only set from an HTTP response by `map_error`.
+pub fn is_invalid_continuation(py: Python, err: &PyErr) -> bool {
+    if !err.is_instance_of::<CosmosHttpResponseError>(py) {
+        return false;
+    }
+    err.value(py)
+        .getattr("status_code")
+        .and_then(|status| status.extract::<Option<u16>>())
+        .is_ok_and(|status| status == Some(400))
+}
+
+/// A `CosmosTimeoutError` for an operation that exceeded its `operation_timeout`
+pub fn timeout_error(operation: &str, timeout: Duration) -> PyErr {
+    let py_err = CosmosTimeoutError::new_err(format!(
+        "{} did not complete within {:.3}s", operation, timeout.as_secs_f64()
+    ));
+    Python::with_gil(|py| {
+        let _ = py_err.value(py).setattr("operation", operation);
+    });
+    py_err
+}
+
+/// A `CosmosReadOnlyError` for a mutating operation attempted on a `read_only=True` client
+pub fn read_only_error(operation: &str) -> PyErr {
+    let py_err = CosmosReadOnlyError::new_err(format!("{} is not allowed on a read-only client", operation));
+    Python::with_gil(|py| {
+        let _ = py_err.value(py).setattr("operation", operation);
+    });
+    py_err
+}
+
+/// Record the caller's `correlation_id` kwarg on a Cosmos exception raised by an operation
+pub fn correlate(py: Python, err: PyErr, kwargs: Option<&PyDict>) -> PyErr {
+    if err.is_instance_of::<CosmosHttpResponseError>(py) {
+        if let Some(Ok(Some(correlation_id))) = kwargs.map(|kw| kw.get_item("correlation_id")) {
+            let _ = err.value(py).setattr("correlation_id", correlation_id);
+        }
     }
+    err
 }
@@ -0,0 +1,31 @@
+use azure_core::http::headers::{HeaderName, Headers};
+
+// Cosmos DB response and request headers used by the binding
+pub const REQUEST_CHARGE: HeaderName = HeaderName::from_static("x-ms-request-charge");
+pub const ACTIVITY_ID: HeaderName = HeaderName::from_static("x-ms-activity-id");
+pub const SESSION_TOKEN: HeaderName = HeaderName::from_static("x-ms-session-token");
+pub const PHYSICAL_PARTITION_ID: HeaderName = HeaderName::from_static("x-ms-cosmos-physical-partition-id");
+pub const PARTITION_KEY_RANGE_ID: HeaderName = HeaderName::from_static("x-ms-documentdb-partitionkeyrangeid");
+pub const RETRY_AFTER_MS: HeaderName = HeaderName::from_static("x-ms-retry-after-ms");
+pub const ETAG: HeaderName = HeaderName::from_static("etag");
+pub const PARTITION_KEY: HeaderName = HeaderName::from_static("x-ms-documentdb-partitionkey");
+pub const INDEX_UTILIZATION: HeaderName = HeaderName::from_static("x-ms-cosmos-index-utilization");
+pub const INDEX_TRANSFORMATION_PROGRESS: HeaderName =
+    HeaderName::from_static("x-ms-documentdb-collection-index-transformation-progress");
+pub const CONTINUATION: &str = "x-ms-continuation";
+pub const MAX_ITEM_COUNT: &str = "x-ms-max-item-count";
+pub const CORRELATION_ID: &str = "x-ms-correlation-id";
+pub const CONSISTENCY_LEVEL: &str = "x-ms-consistency-level";
+pub const POPULATE_INDEX_METRICS: &str = "x-ms-cosmos-populateindexmetrics";
+pub const POPULATE_QUOTA_INFO: &str = "x-ms-documentdb-populatequotainfo";
+pub const LOW_PRECISION_ORDER_BY: &str = "x-ms-documentdb-query-enable-low-precision-order-by";
+
+/// Read a header as a string, if present
+pub fn header_str(headers: &Headers, name: &HeaderName) -> Option<String> {
+    headers.get_optional_str(name).map(str::to_string)
+}
+
+/// Read the request charge in RU, if present
+pub fn request_charge(headers: &Headers) -> Option<f64> {
+    headers.get_optional_str(&REQUEST_CHARGE).and_then(|v| v.parse().ok())
+}
@@ -1,15 +1,29 @@
 use pyo3::prelude::*;
 
+mod cache;
+mod cancellation;
 mod client;
+mod config;
+mod credentials;
 mod database;
 mod container;
+mod diagnostics;
 mod exceptions;
+mod headers;
+mod options;
+mod policies;
+mod properties;
+mod query;
+mod session;
 mod types;
 mod utils;
 
+use cancellation::CancellationToken;
 use client::CosmosClient;
 use database::DatabaseClient;
 use container::ContainerClient;
+use properties::{ContainerProperties, DatabaseProperties};
+use query::{QueryBuilder, QueryCursor, QueryIterator, QueryPage};
 
 /// Azure Cosmos DB Python SDK - Rust native extension
 #[pymodule]
@@ -18,6 +32,15 @@ fn _rust(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<CosmosClient>()?;
     m.add_class::<DatabaseClient>()?;
     m.add_class::<ContainerClient>()?;
+    m.add_class::<QueryPage>()?;
+    m.add_class::<QueryCursor>()?;
+    m.add_class::<QueryIterator>()?;
+    m.add_class::<CancellationToken>()?;
+    m.add_class::<QueryBuilder>()?;
+    m.add_class::<DatabaseProperties>()?;
+    m.add_class::<ContainerProperties>()?;
+    
+    m.add_function(wrap_pyfunction!(session::merge_session_tokens, m)?)?;
     
     // Register exceptions
     exceptions::register_exceptions(m)?;
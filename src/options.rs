@@ -0,0 +1,396 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyLong, PyString};
+use azure_core::http::{ClientMethodOptions, Context, Etag};
+use azure_data_cosmos::{ItemOptions, QueryOptions};
+use serde_json::Value;
+use std::time::Duration;
+use crate::config::{positive_duration, ClientConfig};
+use crate::headers::{CONSISTENCY_LEVEL, CORRELATION_ID, LOW_PRECISION_ORDER_BY};
+use crate::utils::extract_json_path;
+
+/// Extra request headers for a single operation
+/// Carried through the request context to `OperationHeadersPolicy`, which sets them on the request
+#[derive(Debug, Clone, Default)]
+pub struct OperationHeaders(pub Vec<(&'static str, String)>);
+
+/// Top-level properties merged into the JSON body of a single request
+/// Carried through the request context to `BodyPropertiesPolicy`, for properties the Rust SDK's
+/// models do not have
+#[derive(Debug, Clone, Default)]
+pub struct BodyProperties(pub serde_json::Map<String, Value>);
+
+/// Throughput buckets supported by the service
+const THROUGHPUT_BUCKETS: std::ops::RangeInclusive<u8> = 1..=5;
+
+/// Consistency levels accepted by the service, in the casing of the `x-ms-consistency-level` header
+const CONSISTENCY_LEVELS: [&str; 5] = ["Strong", "BoundedStaleness", "Session", "ConsistentPrefix", "Eventual"];
+
+impl OperationHeaders {
+    /// Collect the per-operation headers requested through keyword arguments
+    pub fn from_kwargs(kwargs: Option<&PyDict>) -> PyResult<Self> {
+        let mut headers = Vec::new();
+
+        if let Some(kw) = kwargs {
+            if let Some(value) = kw.get_item("throughput_bucket")? {
+                let bucket = value.extract::<u8>().ok().filter(|b| THROUGHPUT_BUCKETS.contains(b)).ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "throughput_bucket must be an integer between {} and {}",
+                        THROUGHPUT_BUCKETS.start(),
+                        THROUGHPUT_BUCKETS.end()
+                    ))
+                })?;
+                headers.push(("x-ms-cosmos-throughput-bucket", bucket.to_string()));
+            }
+            if let Some(value) = kw.get_item("enable_scan_in_query")? {
+                if value.extract::<bool>()? {
+                    headers.push(("x-ms-documentdb-query-enable-scan", "true".to_string()));
+                }
+            }
+            if let Some(value) = kw.get_item("session_token")? {
+                headers.push(("x-ms-session-token", value.extract::<String>()?));
+            }
+            if let Some(value) = kw.get_item("correlation_id")? {
+                headers.push((CORRELATION_ID, value.extract::<String>()?));
+            }
+            if let Some(value) = kw.get_item("consistency_level")? {
+                let level = consistency_level(value, "consistency_level")?;
+                headers.push((CONSISTENCY_LEVEL, level.to_string()));
+            }
+        }
+
+        Ok(Self(headers))
+    }
+
+    pub fn push(&mut self, name: &'static str, value: String) {
+        self.0.push((name, value));
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|(existing, _)| *existing != name);
+    }
+}
+
+/// Validate a consistency level kwarg case-insensitively, returning the service's casing
+fn consistency_level(value: &PyAny, name: &str) -> PyResult<&'static str> {
+    let requested = value.extract::<String>()?;
+    CONSISTENCY_LEVELS
+        .into_iter()
+        .find(|level| level.eq_ignore_ascii_case(&requested))
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} must be one of {}, got '{}'", name, CONSISTENCY_LEVELS.join(", "), requested
+        )))
+}
+
+/// Retry of a point read at a weaker consistency, requested with `fallback_consistency`
+pub struct FallbackRead {
+    /// Options of the retry, with the consistency header replaced by the fallback level
+    pub options: ItemOptions<'static>,
+    /// How long the first read may take before it is abandoned, from `fallback_timeout`
+    pub timeout: Option<Duration>,
+}
+
+impl FallbackRead {
+    pub fn from_kwargs(kwargs: Option<&PyDict>) -> PyResult<Option<Self>> {
+        let Some(kw) = kwargs else {
+            return Ok(None);
+        };
+        let Some(value) = kw.get_item("fallback_consistency")? else {
+            return Ok(None);
+        };
+        let level = consistency_level(value, "fallback_consistency")?;
+        let timeout = kw.get_item("fallback_timeout")?
+            .map(|value| positive_duration(value, "fallback_timeout"))
+            .transpose()?;
+
+        let mut headers = OperationHeaders::from_kwargs(kwargs)?;
+        headers.remove(CONSISTENCY_LEVEL);
+        headers.push(CONSISTENCY_LEVEL, level.to_string());
+        Ok(Some(Self {
+            options: ItemOptions {
+                method_options: method_options(headers),
+                ..Default::default()
+            },
+            timeout,
+        }))
+    }
+}
+
+/// Return the server's commit timestamp from a write, requested by the `server_timestamp` or
+/// `server_timestamp_field` kwargs
+/// The written item is returned in the response, and its `_ts` is copied into the dict the write
+/// returns, and into `field` if one is named; the stored item is not changed.
+#[derive(Debug, Clone, Default)]
+pub struct ServerTimestamp {
+    pub field: Option<String>,
+}
+
+impl ServerTimestamp {
+    pub fn from_kwargs(kwargs: Option<&PyDict>) -> PyResult<Option<Self>> {
+        let Some(kw) = kwargs else {
+            return Ok(None);
+        };
+        if let Some(field) = kw.get_item("server_timestamp_field")? {
+            return Ok(Some(Self { field: Some(field.extract()?) }));
+        }
+        match kw.get_item("server_timestamp")? {
+            Some(value) if value.extract::<bool>()? => Ok(Some(Self::default())),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Client-side transformations applied to items returned by read and query operations
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// Add `_ts_datetime`, the `_ts` epoch seconds as a UTC `datetime`
+    pub parse_timestamps: bool,
+    /// Return only the subtree at this JSONPath instead of the whole item
+    pub json_path: Option<String>,
+    /// Return `None` instead of raising when `json_path` does not exist in the item
+    pub json_path_missing_ok: bool,
+    /// The client's `id_field`, which receives the item's `id`
+    pub id_field: Option<String>,
+    /// Top-level fields and the type their values are coerced to, from the `schema` kwarg
+    pub schema: Vec<(String, SchemaType)>,
+}
+
+/// A type that the `schema` read option coerces a field to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaType {
+    Int,
+    Float,
+    Bool,
+    Str,
+    DateTime,
+}
+
+impl SchemaType {
+    /// The schema type for a Python type object such as `int` or `datetime.datetime`
+    fn from_type(py: Python, field: &str, value: &PyAny) -> PyResult<Self> {
+        let builtins = py.import("builtins")?;
+        let candidates = [
+            // bool before int, as bool is a subclass of int
+            (builtins.getattr("bool")?, Self::Bool),
+            (builtins.getattr("int")?, Self::Int),
+            (builtins.getattr("float")?, Self::Float),
+            (builtins.getattr("str")?, Self::Str),
+            (py.import("datetime")?.getattr("datetime")?, Self::DateTime),
+        ];
+        for (candidate, schema_type) in candidates {
+            if value.is(candidate) {
+                return Ok(schema_type);
+            }
+        }
+        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "schema type for '{}' must be int, float, bool, str or datetime.datetime, got {}", field, value.repr()?
+        )))
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::Str => "str",
+            Self::DateTime => "datetime",
+        }
+    }
+
+    /// Coerce a field value, or `None` if it cannot be represented as this type
+    /// Values already of the type are returned unchanged; strings are parsed, and ints are
+    /// widened to float. Epoch seconds become UTC datetimes, as with `parse_timestamps`.
+    fn coerce<'py>(self, py: Python<'py>, value: &'py PyAny) -> PyResult<Option<&'py PyAny>> {
+        let builtins = py.import("builtins")?;
+        let is_bool = value.is_instance_of::<PyBool>();
+        let is_int = value.is_instance_of::<PyLong>() && !is_bool;
+        let text = value.downcast::<PyString>().ok().map(|s| s.to_str()).transpose()?.map(str::trim);
+        let converted = match (self, text) {
+            (Self::Int, _) if is_int => Some(value),
+            (Self::Int, Some(text)) => builtins.getattr("int")?.call1((text,)).ok(),
+            (Self::Float, _) if value.is_instance_of::<PyFloat>() => Some(value),
+            (Self::Float, _) if is_int => Some(builtins.getattr("float")?.call1((value,))?),
+            (Self::Float, Some(text)) => builtins.getattr("float")?.call1((text,)).ok(),
+            (Self::Bool, _) if is_bool => Some(value),
+            (Self::Bool, Some(text)) if text.eq_ignore_ascii_case("true") => Some(PyBool::new(py, true).into()),
+            (Self::Bool, Some(text)) if text.eq_ignore_ascii_case("false") => Some(PyBool::new(py, false).into()),
+            (Self::Str, Some(_)) => Some(value),
+            (Self::DateTime, _) => {
+                let datetime = py.import("datetime")?;
+                let utc = datetime.getattr("timezone")?.getattr("utc")?;
+                let class = datetime.getattr("datetime")?;
+                if value.is_instance(class)? {
+                    Some(value)
+                } else if is_int || value.is_instance_of::<PyFloat>() {
+                    class.call_method1("fromtimestamp", (value, utc)).ok()
+                } else if let Some(text) = text {
+                    // `fromisoformat` only accepts the `Z` suffix from Python 3.11
+                    let text = match text.strip_suffix('Z') {
+                        Some(stripped) => format!("{}+00:00", stripped),
+                        None => text.to_string(),
+                    };
+                    class.call_method1("fromisoformat", (text,)).ok()
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        Ok(converted)
+    }
+}
+
+impl ReadOptions {
+    pub fn from_kwargs(config: &ClientConfig, kwargs: Option<&PyDict>) -> PyResult<Self> {
+        let mut options = Self {
+            id_field: config.id_field.clone(),
+            ..Self::default()
+        };
+
+        if let Some(kw) = kwargs {
+            if let Some(value) = kw.get_item("parse_timestamps")? {
+                options.parse_timestamps = value.extract()?;
+            }
+            if let Some(value) = kw.get_item("json_path")? {
+                options.json_path = Some(value.extract()?);
+            }
+            if let Some(value) = kw.get_item("json_path_missing_ok")? {
+                options.json_path_missing_ok = value.extract()?;
+            }
+            if let Some(value) = kw.get_item("schema")? {
+                let schema = value.downcast::<PyDict>()?;
+                for (field, schema_type) in schema.iter() {
+                    let field: String = field.extract()?;
+                    let schema_type = SchemaType::from_type(kw.py(), &field, schema_type)?;
+                    options.schema.push((field, schema_type));
+                }
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Narrow a returned item to the `json_path` subtree, if one was requested
+    /// `None` means the path does not exist and `json_path_missing_ok` is set
+    pub fn select(&self, item: Value) -> PyResult<Option<Value>> {
+        let Some(path) = &self.json_path else {
+            return Ok(Some(item));
+        };
+        match extract_json_path(item, path)? {
+            Some(subtree) => Ok(Some(subtree)),
+            None if self.json_path_missing_ok => Ok(None),
+            None => Err(PyErr::new::<pyo3::exceptions::PyKeyError, _>(
+                format!("json_path '{}' does not exist in the item", path)
+            )),
+        }
+    }
+
+    /// Apply the transformations to a returned item
+    pub fn apply(&self, py: Python, item: &PyAny) -> PyResult<()> {
+        let Ok(dict) = item.downcast::<PyDict>() else {
+            return Ok(());
+        };
+
+        // A `json_path` subtree is not an item, so its `id` keys are left alone
+        if let (Some(field), None) = (&self.id_field, &self.json_path) {
+            if let Some(id) = dict.get_item("id")? {
+                dict.set_item(field, id)?;
+                dict.del_item("id")?;
+            }
+        }
+
+        if self.parse_timestamps {
+            if let Some(ts) = dict.get_item("_ts")? {
+                let datetime = py.import("datetime")?;
+                let utc = datetime.getattr("timezone")?.getattr("utc")?;
+                let parsed = datetime.getattr("datetime")?.call_method1("fromtimestamp", (ts, utc))?;
+                dict.set_item("_ts_datetime", parsed)?;
+            }
+        }
+
+        // `None` is left alone: a missing value is not type drift
+        for (field, schema_type) in &self.schema {
+            let Some(value) = dict.get_item(field.as_str())? else {
+                continue;
+            };
+            if value.is_none() {
+                continue;
+            }
+            match schema_type.coerce(py, value)? {
+                Some(coerced) => dict.set_item(field.as_str(), coerced)?,
+                None => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Cannot coerce field '{}' value {} to {}", field, value.repr()?, schema_type.name()
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Method options carrying the given per-operation headers
+pub fn method_options(headers: OperationHeaders) -> ClientMethodOptions<'static> {
+    ClientMethodOptions {
+        context: Context::new().with_value(headers),
+    }
+}
+
+/// Item request options built from kwargs
+pub fn item_options(kwargs: Option<&PyDict>) -> PyResult<ItemOptions<'static>> {
+    Ok(ItemOptions {
+        method_options: method_options(OperationHeaders::from_kwargs(kwargs)?),
+        ..Default::default()
+    })
+}
+
+/// Item request options for a write, built from kwargs
+/// `consistency_level` is validated but not sent: writes are always committed by a quorum of
+/// replicas, and the service only honours a consistency override on reads.
+pub fn write_item_options(kwargs: Option<&PyDict>) -> PyResult<ItemOptions<'static>> {
+    let mut headers = OperationHeaders::from_kwargs(kwargs)?;
+    headers.remove(CONSISTENCY_LEVEL);
+    Ok(ItemOptions {
+        method_options: method_options(headers),
+        ..Default::default()
+    })
+}
+
+/// The etag a conditional write requires the stored item to still have, from the `etag` or
+/// `if_match` kwarg
+/// It is sent as `If-Match`, so the write fails with a 412 if the item changed since it was read.
+pub fn if_match_etag(kwargs: Option<&PyDict>) -> PyResult<Option<Etag>> {
+    let Some(kw) = kwargs else {
+        return Ok(None);
+    };
+    let etag = kw.get_item("etag")?.filter(|value| !value.is_none());
+    let if_match = kw.get_item("if_match")?.filter(|value| !value.is_none());
+    match (etag, if_match) {
+        (Some(_), Some(_)) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Pass either etag or if_match, not both"
+        )),
+        (Some(value), None) | (None, Some(value)) => Ok(Some(Etag::from(value.extract::<String>()?))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Query request options built from kwargs
+pub fn query_options(kwargs: Option<&PyDict>) -> PyResult<QueryOptions<'static>> {
+    query_options_with_headers(kwargs, OperationHeaders::from_kwargs(kwargs)?)
+}
+
+/// Query request options carrying the given per-operation headers, plus the query-only headers
+/// requested through kwargs
+pub fn query_options_with_headers(kwargs: Option<&PyDict>, mut headers: OperationHeaders) -> PyResult<QueryOptions<'static>> {
+    if let Some(value) = kwargs.map(|kw| kw.get_item("enable_low_precision_order_by")).transpose()?.flatten() {
+        let enabled = value.extract::<bool>().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("enable_low_precision_order_by must be True or False")
+        })?;
+        if enabled {
+            headers.push(LOW_PRECISION_ORDER_BY, "true".to_string());
+        }
+    }
+    Ok(QueryOptions {
+        method_options: method_options(headers),
+    })
+}
@@ -0,0 +1,524 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use azure_core::http::policies::{Policy, PolicyResult};
+use azure_core::http::headers::HeaderName;
+use azure_core::http::{AsyncRawResponse, Body, Context, HttpClient, Request, StatusCode};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use typespec::error::{Error as TypeSpecError, ErrorKind};
+use crate::headers;
+use crate::options::{BodyProperties, OperationHeaders};
+use crate::session;
+
+const SUB_STATUS: HeaderName = HeaderName::from_static("x-ms-substatus");
+const IS_QUERY: HeaderName = HeaderName::from_static("x-ms-documentdb-isquery");
+const IS_UPSERT: HeaderName = HeaderName::from_static("x-ms-documentdb-is-upsert");
+const CONTENT_LENGTH: HeaderName = HeaderName::from_static("content-length");
+
+/// A request as it was handed to the transport
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// Records the last `capacity` outgoing requests into a ring buffer
+/// Installed as a per-try policy after `GoneRetryPolicy`, so every attempt, including the SDK's
+/// retries and 410 Gone retries, is captured as a separate request
+#[derive(Debug)]
+pub struct RequestRecorder {
+    capacity: usize,
+    requests: Mutex<VecDeque<RecordedRequest>>,
+}
+
+impl RequestRecorder {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            requests: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Return up to `n` of the most recent requests, oldest first
+    pub fn last(&self, n: usize) -> Vec<RecordedRequest> {
+        let requests = self.requests.lock().unwrap();
+        let skip = requests.len().saturating_sub(n);
+        requests.iter().skip(skip).cloned().collect()
+    }
+
+    fn record(&self, request: &Request) {
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = if name.as_str().eq_ignore_ascii_case("authorization") {
+                    "<redacted>".to_string()
+                } else {
+                    value.as_str().to_string()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+        let body = match request.body() {
+            Body::Bytes(bytes) if !bytes.is_empty() => Some(String::from_utf8_lossy(bytes).into_owned()),
+            _ => None,
+        };
+
+        let mut requests = self.requests.lock().unwrap();
+        if requests.len() == self.capacity {
+            requests.pop_front();
+        }
+        requests.push_back(RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers,
+            body,
+        });
+    }
+}
+
+#[async_trait]
+impl Policy for RequestRecorder {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        self.record(request);
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+/// Retries requests that fail with 410 Gone while partitions are being split or migrated
+/// The gateway owns partition routing, so re-sending the request is enough for it to be
+/// routed to the partitions that replaced the gone one; the binding caches no routing state.
+#[derive(Debug)]
+pub struct GoneRetryPolicy {
+    max_retries: u32,
+    delay: Duration,
+}
+
+impl GoneRetryPolicy {
+    // Sub-statuses of 410 that indicate a transient routing change rather than a deleted resource
+    const RETRYABLE_SUB_STATUSES: [&'static str; 4] = [
+        "1000", // name cache is stale
+        "1002", // partition key range gone (split)
+        "1007", // completing split
+        "1008", // completing partition migration
+    ];
+
+    pub fn new(max_retries: u32, delay: Duration) -> Self {
+        Self { max_retries, delay }
+    }
+}
+
+impl Default for GoneRetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(100))
+    }
+}
+
+#[async_trait]
+impl Policy for GoneRetryPolicy {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        let mut attempt = 0;
+        loop {
+            let response = next[0].send(ctx, request, &next[1..]).await?;
+            let retryable = response.status() == StatusCode::Gone
+                && response
+                    .headers()
+                    .get_optional_str(&SUB_STATUS)
+                    .is_some_and(|sub_status| Self::RETRYABLE_SUB_STATUSES.contains(&sub_status));
+
+            if !retryable || attempt >= self.max_retries {
+                return Ok(response);
+            }
+
+            attempt += 1;
+            tokio::time::sleep(self.delay * attempt).await;
+        }
+    }
+}
+
+/// Sets the per-operation headers placed in the request context by `options::method_options`
+#[derive(Debug, Default)]
+pub struct OperationHeadersPolicy;
+
+#[async_trait]
+impl Policy for OperationHeadersPolicy {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        if let Some(OperationHeaders(headers)) = ctx.value::<OperationHeaders>() {
+            for (name, value) in headers {
+                request.insert_header(HeaderName::from_static(name), value.clone());
+            }
+        }
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+/// Merges the properties placed in the request context as `BodyProperties` into the JSON body
+#[derive(Debug, Default)]
+pub struct BodyPropertiesPolicy;
+
+#[async_trait]
+impl Policy for BodyPropertiesPolicy {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        if let Some(BodyProperties(properties)) = ctx.value::<BodyProperties>() {
+            let body = match request.body() {
+                Body::Bytes(bytes) => serde_json::from_slice::<serde_json::Value>(bytes).ok(),
+                _ => None,
+            };
+            if let Some(serde_json::Value::Object(mut body)) = body {
+                body.extend(properties.clone());
+                request.set_json(&body)?;
+            }
+        }
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+/// Partition key component standing for the undefined partition (items without a key value)
+/// The Rust SDK cannot express it, so this string is sent in its place and rewritten to `{}` by
+/// `UndefinedPartitionKeyPolicy`. The control characters keep it from colliding with real keys.
+pub const UNDEFINED_PARTITION_KEY: &str = "\u{1}undefined\u{1}";
+
+/// Rewrites `UNDEFINED_PARTITION_KEY` components of the partition key header to `{}`
+#[derive(Debug, Default)]
+pub struct UndefinedPartitionKeyPolicy;
+
+#[async_trait]
+impl Policy for UndefinedPartitionKeyPolicy {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        let placeholder = format!("\"{}\"", UNDEFINED_PARTITION_KEY);
+        let rewritten = request.headers()
+            .get_optional_str(&headers::PARTITION_KEY)
+            .filter(|value| value.contains(&placeholder))
+            .map(|value| value.replace(&placeholder, "{}"));
+        if let Some(value) = rewritten {
+            request.insert_header(headers::PARTITION_KEY, value);
+        }
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+/// Totals accumulated by `ClientMetrics`
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    pub total_request_charge: f64,
+    pub requests_by_operation: HashMap<String, u64>,
+    pub throttled_requests: u64,
+}
+
+/// Accumulates RU charge, request counts and throttles over the client's lifetime
+/// Installed as a per-try policy, so every attempt of a retried request is counted
+#[derive(Debug, Default)]
+pub struct ClientMetrics {
+    totals: Mutex<MetricsSnapshot>,
+}
+
+impl ClientMetrics {
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.totals.lock().unwrap().clone()
+    }
+
+    /// Name the operation a request performs, such as "read_item" or "query_items"
+    fn operation_name(request: &Request) -> String {
+        let segments: Vec<&str> = request
+            .url()
+            .path_segments()
+            .map_or_else(Vec::new, |segments| segments.filter(|s| !s.is_empty()).collect());
+        // Paths alternate resource types and ids: /dbs/{db}/colls/{coll}/docs/{doc}
+        let has_id = segments.len() % 2 == 0 && !segments.is_empty();
+        let resource_type = match (has_id, segments.len()) {
+            (_, 0) => "",
+            (true, len) => segments[len - 2],
+            (false, len) => segments[len - 1],
+        };
+        let resource = match resource_type {
+            "docs" => "item",
+            "colls" => "container",
+            "dbs" => "database",
+            "offers" => "offer",
+            "pkranges" => "partition_key_range",
+            "" => "account",
+            other => other,
+        };
+
+        let flag = |name: &HeaderName| {
+            request
+                .headers()
+                .get_optional_str(name)
+                .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+        };
+        let method = request.method().to_string().to_ascii_lowercase();
+        let (action, plural) = match method.as_str() {
+            "get" if has_id || resource == "account" => ("read", false),
+            "get" => ("list", true),
+            "post" if flag(&IS_QUERY) => ("query", true),
+            "post" if flag(&IS_UPSERT) => ("upsert", false),
+            "post" => ("create", false),
+            "put" => ("replace", false),
+            "delete" => ("delete", false),
+            "patch" => ("patch", false),
+            other => (other, false),
+        };
+        format!("{}_{}{}", action, resource, if plural { "s" } else { "" })
+    }
+}
+
+#[async_trait]
+impl Policy for ClientMetrics {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        let operation = Self::operation_name(request);
+        let response = next[0].send(ctx, request, &next[1..]).await;
+
+        let mut totals = self.totals.lock().unwrap();
+        *totals.requests_by_operation.entry(operation).or_default() += 1;
+        if let Ok(response) = &response {
+            totals.total_request_charge += headers::request_charge(response.headers()).unwrap_or(0.0);
+            if response.status() == StatusCode::TooManyRequests {
+                totals.throttled_requests += 1;
+            }
+        }
+        drop(totals);
+
+        response
+    }
+}
+
+/// Sends the client's `initial_session_token`, merged with any per-operation `session_token`
+/// Runs after `OperationHeadersPolicy`, which sets the per-operation token
+#[derive(Debug)]
+pub struct SessionTokenPolicy {
+    initial_session_token: String,
+}
+
+impl SessionTokenPolicy {
+    pub fn new(initial_session_token: String) -> Self {
+        Self { initial_session_token }
+    }
+}
+
+#[async_trait]
+impl Policy for SessionTokenPolicy {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        let token = match request.headers().get_optional_str(&headers::SESSION_TOKEN) {
+            Some(operation_token) => session::merge(&[&self.initial_session_token, operation_token])
+                .unwrap_or_else(|_| operation_token.to_string()),
+            None => self.initial_session_token.clone(),
+        };
+        request.insert_header(headers::SESSION_TOKEN, token);
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+/// State of a `CircuitBreaker`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent; throttled and unavailable responses are counted
+    Closed,
+    /// Requests fail fast until the cool-down ends
+    Open,
+    /// The cool-down has ended and one probe request is allowed through
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    /// Consecutive 429/503 responses, and when the first of them arrived
+    failures: u32,
+    first_failure: Option<Instant>,
+    /// When the breaker tripped; `None` while closed
+    opened_at: Option<Instant>,
+    /// Whether the half-open probe has been sent and not yet answered
+    probing: bool,
+}
+
+/// Fails requests fast after sustained throttling or unavailability, so retries do not add
+/// to the load of a struggling account
+/// After `threshold` consecutive 429 or 503 responses within `window`, the breaker opens and
+/// requests fail with 503 without being sent. After `cool_down` it half-opens and lets one
+/// probe through: a success closes it, another 429 or 503 opens it again. Installed as the
+/// first per-try policy, so every attempt, including the SDK's own retries, is counted.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    window: Duration,
+    cool_down: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, window: Duration, cool_down: Duration) -> Self {
+        Self {
+            threshold,
+            window,
+            cool_down,
+            state: Mutex::new(BreakerState {
+                failures: 0,
+                first_failure: None,
+                opened_at: None,
+                probing: false,
+            }),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.cool_down => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Admit a request, or return how much of the cool-down remains
+    /// Returns whether the admitted request is the half-open probe.
+    fn admit(&self) -> Result<bool, Duration> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(false);
+        };
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.cool_down {
+            return Err(self.cool_down - elapsed);
+        }
+        if state.probing {
+            // Only one probe at a time; the others wait for its outcome like the open state
+            return Err(Duration::ZERO);
+        }
+        state.probing = true;
+        Ok(true)
+    }
+
+    /// Record the outcome of an admitted request: whether it was a 429 or 503, or `None` if it
+    /// failed without a response, which says nothing about throttling
+    fn record(&self, probe: bool, failed: Option<bool>) {
+        let mut state = self.state.lock().unwrap();
+        if probe {
+            state.probing = false;
+        }
+        let Some(failed) = failed else {
+            return;
+        };
+        if !failed {
+            // A success while open comes from a request admitted before the breaker tripped
+            if probe || state.opened_at.is_none() {
+                state.failures = 0;
+                state.first_failure = None;
+                state.opened_at = None;
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        if probe {
+            state.opened_at = Some(now);
+            return;
+        }
+        match state.first_failure {
+            Some(first) if now.duration_since(first) <= self.window => state.failures += 1,
+            _ => {
+                state.failures = 1;
+                state.first_failure = Some(now);
+            }
+        }
+        if state.failures >= self.threshold && state.opened_at.is_none() {
+            state.opened_at = Some(now);
+        }
+    }
+}
+
+#[async_trait]
+impl Policy for CircuitBreaker {
+    async fn send(&self, ctx: &Context, request: &mut Request, next: &[Arc<dyn Policy>]) -> PolicyResult {
+        let probe = match self.admit() {
+            Ok(probe) => probe,
+            Err(remaining) => {
+                return Err(TypeSpecError::new(
+                    ErrorKind::HttpResponse {
+                        status: StatusCode::ServiceUnavailable,
+                        error_code: Some("CircuitBreakerOpen".to_string()),
+                        raw_response: None,
+                    },
+                    format!(
+                        "ServiceUnavailable: circuit breaker is open after repeated 429/503 responses; \
+                         failing fast for another {:.1}s",
+                        remaining.as_secs_f64()
+                    ),
+                ));
+            }
+        };
+
+        let response = next[0].send(ctx, request, &next[1..]).await;
+        let failed = response.as_ref().ok().map(|response| {
+            matches!(response.status(), StatusCode::TooManyRequests | StatusCode::ServiceUnavailable)
+        });
+        self.record(probe, failed);
+        response
+    }
+}
+
+/// Fails responses whose body exceeds `max_response_size_bytes`
+/// Wraps the HTTP client rather than running as a policy, because the pipeline buffers the whole
+/// body in its transport. A declared Content-Length over the limit fails before the body is read;
+/// bodies without one, such as chunked or decompressed responses, are counted as they stream and
+/// fail once the limit is passed, so an oversized document or query page is never fully buffered.
+#[derive(Debug)]
+pub struct ResponseSizeLimit {
+    inner: Arc<dyn HttpClient>,
+    max_bytes: u64,
+}
+
+impl ResponseSizeLimit {
+    pub fn new(inner: Arc<dyn HttpClient>, max_bytes: u64) -> Self {
+        Self { inner, max_bytes }
+    }
+}
+
+fn response_too_large(size: impl std::fmt::Display, max_bytes: u64) -> TypeSpecError {
+    TypeSpecError::new(
+        ErrorKind::Other,
+        format!(
+            "ResponseTooLarge: response body of {} bytes exceeds max_response_size_bytes of {}",
+            size, max_bytes
+        ),
+    )
+}
+
+#[async_trait]
+impl HttpClient for ResponseSizeLimit {
+    async fn execute_request(&self, request: &Request) -> typespec::error::Result<AsyncRawResponse> {
+        let response = self.inner.execute_request(request).await?;
+        let max_bytes = self.max_bytes;
+        let size = response
+            .headers()
+            .get_optional_str(&CONTENT_LENGTH)
+            .and_then(|v| v.parse::<u64>().ok());
+        if let Some(size) = size.filter(|size| *size > max_bytes) {
+            return Err(response_too_large(size, max_bytes));
+        }
+
+        // The header is only a fast path: count what is actually read
+        let (status, headers, body) = response.deconstruct();
+        let mut read = 0u64;
+        let body = body.map(move |chunk| {
+            let chunk = chunk?;
+            read += chunk.len() as u64;
+            if read > max_bytes {
+                return Err(response_too_large(format!("more than {}", max_bytes), max_bytes));
+            }
+            Ok(chunk)
+        });
+        Ok(AsyncRawResponse::new(status, headers, Box::pin(body)))
+    }
+}
@@ -0,0 +1,137 @@
+use pyo3::prelude::*;
+use azure_data_cosmos::models;
+use serde_json::Value;
+use crate::config::ClientConfig;
+use crate::utils::json_to_py_dict;
+
+/// Database properties with typed accessors, returned when the client has `typed_properties=True`
+#[pyclass]
+pub struct DatabaseProperties {
+    properties: Value,
+}
+
+#[pymethods]
+impl DatabaseProperties {
+    /// The database id
+    #[getter]
+    pub fn id(&self) -> String {
+        string_property(&self.properties, "id").unwrap_or_default()
+    }
+
+    /// The entity tag of the database resource
+    #[getter]
+    pub fn etag(&self) -> Option<String> {
+        string_property(&self.properties, "_etag")
+    }
+
+    /// The properties as a dict, with the service's key names
+    pub fn as_dict(&self, py: Python) -> PyResult<PyObject> {
+        json_to_py_dict(py, &self.properties)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DatabaseProperties(id={:?})", self.id())
+    }
+}
+
+/// Container properties with typed accessors, returned when the client has `typed_properties=True`
+#[pyclass]
+pub struct ContainerProperties {
+    properties: Value,
+}
+
+#[pymethods]
+impl ContainerProperties {
+    /// The container id
+    #[getter]
+    pub fn id(&self) -> String {
+        string_property(&self.properties, "id").unwrap_or_default()
+    }
+
+    /// The entity tag of the container resource
+    #[getter]
+    pub fn etag(&self) -> Option<String> {
+        string_property(&self.properties, "_etag")
+    }
+
+    /// The partition key definition, e.g. `{"paths": ["/id"], "kind": "Hash", "version": 2}`
+    #[getter]
+    pub fn partition_key(&self, py: Python) -> PyResult<Option<PyObject>> {
+        object_property(py, &self.properties, "partitionKey")
+    }
+
+    /// Default time to live in seconds; -1 means items do not expire unless they set `ttl`
+    #[getter]
+    pub fn default_ttl(&self) -> Option<i64> {
+        self.properties.get("defaultTtl").and_then(Value::as_i64)
+    }
+
+    /// The indexing policy
+    #[getter]
+    pub fn indexing_policy(&self, py: Python) -> PyResult<Option<PyObject>> {
+        object_property(py, &self.properties, "indexingPolicy")
+    }
+
+    /// The properties as a dict, with the service's key names
+    pub fn as_dict(&self, py: Python) -> PyResult<PyObject> {
+        json_to_py_dict(py, &self.properties)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ContainerProperties(id={:?})", self.id())
+    }
+}
+
+/// Database properties as returned to Python: a dict, or `DatabaseProperties` if configured
+pub fn database_properties(py: Python, config: &ClientConfig, properties: Value) -> PyResult<PyObject> {
+    if config.typed_properties {
+        Ok(Py::new(py, DatabaseProperties { properties })?.into_py(py))
+    } else {
+        json_to_py_dict(py, &properties)
+    }
+}
+
+/// Container properties as returned to Python: a dict, or `ContainerProperties` if configured
+pub fn container_properties(py: Python, config: &ClientConfig, properties: Value) -> PyResult<PyObject> {
+    if config.typed_properties {
+        Ok(Py::new(py, ContainerProperties { properties })?.into_py(py))
+    } else {
+        json_to_py_dict(py, &properties)
+    }
+}
+
+/// The service's JSON representation of database properties streamed by `list_databases`
+/// `DatabaseProperties` from `azure_data_cosmos` is not `Serialize`, so the id and the system
+/// properties are copied field by field.
+pub fn database_properties_value(database: &models::DatabaseProperties) -> Value {
+    let system = &database.system_properties;
+    let mut properties = serde_json::Map::new();
+    properties.insert("id".to_string(), Value::from(database.id.clone()));
+    if let Some(resource_id) = &system.resource_id {
+        properties.insert("_rid".to_string(), Value::from(resource_id.clone()));
+    }
+    if let Some(self_link) = &system.self_link {
+        properties.insert("_self".to_string(), Value::from(self_link.clone()));
+    }
+    if let Some(etag) = &system.etag {
+        properties.insert("_etag".to_string(), Value::from(etag.to_string()));
+    }
+    if let Some(last_modified) = system.last_modified {
+        properties.insert("_ts".to_string(), Value::from(last_modified.unix_timestamp()));
+    }
+    Value::Object(properties)
+}
+
+/// Serialize properties streamed by a list operation to the service's JSON representation
+pub fn to_properties_value<T: serde::Serialize>(properties: &T) -> PyResult<Value> {
+    serde_json::to_value(properties)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Failed to serialize properties: {}", e)))
+}
+
+fn string_property(properties: &Value, name: &str) -> Option<String> {
+    properties.get(name).and_then(Value::as_str).map(str::to_string)
+}
+
+fn object_property(py: Python, properties: &Value, name: &str) -> PyResult<Option<PyObject>> {
+    properties.get(name).map(|value| json_to_py_dict(py, value)).transpose()
+}
@@ -0,0 +1,513 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+use azure_data_cosmos::Query;
+use serde_json::Value;
+use std::collections::VecDeque;
+use crate::container::ContainerClient;
+use crate::utils::{json_to_py_dict, py_to_json};
+
+/// A single page of query results
+#[pyclass]
+pub struct QueryPage {
+    /// The items on this page
+    #[pyo3(get)]
+    pub items: Py<PyList>,
+    /// Token to pass as `continuation` to fetch the next page, `None` when exhausted
+    #[pyo3(get)]
+    pub continuation_token: Option<String>,
+    /// Request units charged for this page
+    #[pyo3(get)]
+    pub request_charge: Option<f64>,
+    /// Service activity id of the request that produced this page
+    #[pyo3(get)]
+    pub activity_id: Option<String>,
+    /// Cursor for resuming the query after this page, `None` when exhausted
+    #[pyo3(get)]
+    pub cursor: Option<QueryCursor>,
+    /// Index utilization reported with `populate_index_metrics=True`: a dict with the lists
+    /// `UtilizedSingleIndexes`, `PotentialSingleIndexes`, `UtilizedCompositeIndexes` and
+    /// `PotentialCompositeIndexes`
+    #[pyo3(get)]
+    pub index_metrics: Option<PyObject>,
+}
+
+#[pymethods]
+impl QueryPage {
+    /// Whether the service used a composite index for this page, `None` without index metrics
+    #[getter]
+    fn composite_index_used(&self, py: Python) -> PyResult<Option<bool>> {
+        let Some(metrics) = &self.index_metrics else {
+            return Ok(None);
+        };
+        let utilized = metrics.as_ref(py).get_item("UtilizedCompositeIndexes").ok();
+        Ok(Some(utilized.map(|indexes| indexes.is_true()).transpose()?.unwrap_or(false)))
+    }
+
+    fn __len__(&self, py: Python) -> usize {
+        self.items.as_ref(py).len()
+    }
+
+    fn __repr__(&self, py: Python) -> String {
+        format!(
+            "QueryPage(items={}, continuation_token={:?}, request_charge={:?}, activity_id={:?})",
+            self.items.as_ref(py).len(),
+            self.continuation_token,
+            self.request_charge,
+            self.activity_id
+        )
+    }
+}
+
+/// Iterator over the items of a query, fetching pages lazily
+/// One page is buffered at a time; the next is requested only when the buffer drains, so
+/// memory stays bounded by the page size however many items the query returns.
+#[pyclass]
+pub struct QueryIterator {
+    pub container: Py<ContainerClient>,
+    /// The SQL string or `QueryBuilder`
+    pub query: PyObject,
+    pub max_item_count: Option<i64>,
+    /// A copy of the operation's kwargs, passed to every page request
+    pub kwargs: Option<Py<PyDict>>,
+    pub buffer: VecDeque<PyObject>,
+    pub continuation: Option<String>,
+    pub exhausted: bool,
+}
+
+#[pymethods]
+impl QueryIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>, py: Python) -> PyResult<Option<PyObject>> {
+        // Cross-partition queries may return empty pages that still carry a continuation
+        loop {
+            if let Some(item) = slf.buffer.pop_front() {
+                return Ok(Some(item));
+            }
+            if slf.exhausted {
+                return Ok(None);
+            }
+            let container = slf.container.clone_ref(py);
+            let kwargs = slf.kwargs.as_ref().map(|kw| kw.clone_ref(py));
+            let page = container.borrow(py).fetch_query_page(
+                py,
+                slf.query.as_ref(py),
+                slf.max_item_count,
+                slf.continuation.clone(),
+                kwargs.as_ref().map(|kw| kw.as_ref(py)),
+            )?;
+            slf.exhausted = page.continuation_token.is_none();
+            slf.continuation = page.continuation_token;
+            slf.buffer.extend(page.items.as_ref(py).iter().map(Into::into));
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "QueryIterator(buffered={}, continuation_token={:?}, exhausted={})",
+            self.buffer.len(),
+            self.continuation,
+            self.exhausted
+        )
+    }
+}
+
+/// A resumable position in a query: the continuation token plus the query and partition scope
+/// it belongs to, so a scan can be persisted with `to_dict` and resumed in another process
+#[pyclass]
+#[derive(Clone)]
+pub struct QueryCursor {
+    /// The continuation token of the next page
+    #[pyo3(get)]
+    pub continuation_token: String,
+    /// The SQL text of the query
+    #[pyo3(get)]
+    pub query: String,
+    pub parameters: Vec<(String, Value)>,
+    pub partition_key: Option<Value>,
+    /// Maximum number of items per page
+    #[pyo3(get)]
+    pub max_item_count: Option<i64>,
+    #[pyo3(get)]
+    pub database_id: String,
+    #[pyo3(get)]
+    pub container_id: String,
+    /// The container that produced the cursor; not set on cursors restored with `from_dict`
+    pub container: Option<Py<ContainerClient>>,
+}
+
+#[pymethods]
+impl QueryCursor {
+    /// Fetch the next page of the query
+    /// `container` is required for cursors restored with `from_dict`, and must be the container
+    /// the query ran against
+    #[pyo3(signature = (container=None))]
+    pub fn resume(&self, py: Python, container: Option<&PyAny>) -> PyResult<QueryPage> {
+        let container: Py<ContainerClient> = match container {
+            // Accept both the Rust client and the `ContainerProxy` wrapping it
+            Some(container) => match container.extract() {
+                Ok(client) => client,
+                Err(_) => container.getattr("_client")?.extract()?,
+            },
+            None => self.container.clone().ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "This cursor was restored from a dict; pass the container to resume()"
+                )
+            })?,
+        };
+
+        let client = container.borrow(py);
+        if client.database_id() != self.database_id || client.container_id() != self.container_id {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Cursor belongs to container {}/{}, not {}/{}",
+                self.database_id, self.container_id, client.database_id(), client.container_id()
+            )));
+        }
+
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("parameters", self.parameter_dicts(py)?)?;
+        if let Some(partition_key) = &self.partition_key {
+            kwargs.set_item("partition_key", json_to_py_dict(py, partition_key)?)?;
+        }
+
+        let mut page = client.fetch_query_page(
+            py,
+            PyString::new(py, &self.query),
+            self.max_item_count,
+            Some(self.continuation_token.clone()),
+            Some(kwargs),
+        )?;
+        if let Some(cursor) = &mut page.cursor {
+            cursor.container = Some(container.clone_ref(py));
+        }
+        Ok(page)
+    }
+
+    /// The cursor as a JSON-serializable dict, for persisting in a job store
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        dict.set_item("continuation_token", &self.continuation_token)?;
+        dict.set_item("query", &self.query)?;
+        dict.set_item("parameters", self.parameter_dicts(py)?)?;
+        let partition_key = match &self.partition_key {
+            Some(partition_key) => json_to_py_dict(py, partition_key)?,
+            None => py.None(),
+        };
+        dict.set_item("partition_key", partition_key)?;
+        dict.set_item("max_item_count", self.max_item_count)?;
+        dict.set_item("database_id", &self.database_id)?;
+        dict.set_item("container_id", &self.container_id)?;
+        Ok(dict)
+    }
+
+    /// Restore a cursor saved with `to_dict`
+    #[staticmethod]
+    pub fn from_dict(py: Python, data: &PyDict) -> PyResult<Self> {
+        fn field<'py>(data: &'py PyDict, name: &str) -> PyResult<&'py PyAny> {
+            data.get_item(name)?.ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyKeyError, _>(format!("cursor dict is missing '{}'", name))
+            })
+        }
+
+        let parameters = match data.get_item("parameters")? {
+            Some(parameters) => parameters
+                .iter()?
+                .map(|parameter| {
+                    let parameter = parameter?;
+                    let name = parameter_name(parameter.get_item("name")?.extract()?);
+                    Ok((name, py_to_json(py, parameter.get_item("value")?)?))
+                })
+                .collect::<PyResult<_>>()?,
+            None => Vec::new(),
+        };
+        let partition_key = data.get_item("partition_key")?
+            .filter(|pk| !pk.is_none())
+            .map(|pk| py_to_json(py, pk))
+            .transpose()?;
+        let max_item_count = data.get_item("max_item_count")?
+            .filter(|count| !count.is_none())
+            .map(|count| count.extract())
+            .transpose()?;
+
+        Ok(Self {
+            continuation_token: field(data, "continuation_token")?.extract()?,
+            query: field(data, "query")?.extract()?,
+            parameters,
+            partition_key,
+            max_item_count,
+            database_id: field(data, "database_id")?.extract()?,
+            container_id: field(data, "container_id")?.extract()?,
+            container: None,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "QueryCursor(query={:?}, container={:?}, continuation_token={:?})",
+            self.query, self.container_id, self.continuation_token
+        )
+    }
+}
+
+impl QueryCursor {
+    /// The bound parameters in the `[{"name": "@x", "value": ...}]` form of `query_items`
+    fn parameter_dicts<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        let list = PyList::empty(py);
+        for (name, value) in &self.parameters {
+            let parameter = PyDict::new(py);
+            parameter.set_item("name", name)?;
+            parameter.set_item("value", json_to_py_dict(py, value)?)?;
+            list.append(parameter)?;
+        }
+        Ok(list)
+    }
+}
+
+/// Fluent builder for parameterized queries
+/// Covers SELECT, WHERE, ORDER BY and OFFSET/LIMIT; values are always bound as parameters.
+/// Subclassed by the Python `QueryBuilder`, which exposes `where_` as `where`.
+#[pyclass(subclass)]
+#[derive(Clone)]
+pub struct QueryBuilder {
+    alias: String,
+    fields: Vec<String>,
+    conditions: Vec<String>,
+    order_by: Vec<String>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+    parameters: Vec<(String, PyObject)>,
+}
+
+#[pymethods]
+impl QueryBuilder {
+    #[new]
+    #[pyo3(signature = (alias="c"))]
+    pub fn new(alias: &str) -> Self {
+        Self {
+            alias: alias.to_string(),
+            fields: Vec::new(),
+            conditions: Vec::new(),
+            order_by: Vec::new(),
+            offset: None,
+            limit: None,
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Project the given expressions instead of the whole document
+    #[pyo3(signature = (*fields))]
+    pub fn select(mut slf: PyRefMut<'_, Self>, fields: Vec<String>) -> PyRefMut<'_, Self> {
+        slf.fields.extend(fields);
+        slf
+    }
+
+    /// Add a filter condition, ANDed with earlier ones
+    /// Keyword arguments bind the `@name` parameters used in the condition
+    #[pyo3(signature = (condition, **parameters))]
+    pub fn where_<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        py: Python,
+        condition: String,
+        parameters: Option<&PyDict>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        if let Some(parameters) = parameters {
+            for (name, value) in parameters.iter() {
+                slf.bind(py, parameter_name(name.extract()?), value)?;
+            }
+        }
+        slf.conditions.push(condition);
+        Ok(slf)
+    }
+
+    /// Sort by an expression; call repeatedly for secondary sort keys
+    #[pyo3(signature = (expression, descending=false))]
+    pub fn order_by(mut slf: PyRefMut<'_, Self>, expression: String, descending: bool) -> PyRefMut<'_, Self> {
+        let direction = if descending { "DESC" } else { "ASC" };
+        slf.order_by.push(format!("{} {}", expression, direction));
+        slf
+    }
+
+    /// Skip the first `count` results; requires `limit`
+    pub fn offset(mut slf: PyRefMut<'_, Self>, count: u64) -> PyRefMut<'_, Self> {
+        slf.offset = Some(count);
+        slf
+    }
+
+    /// Return at most `count` results
+    pub fn limit(mut slf: PyRefMut<'_, Self>, count: u64) -> PyRefMut<'_, Self> {
+        slf.limit = Some(count);
+        slf
+    }
+
+    /// The SQL text of the query
+    #[getter]
+    pub fn query(&self) -> PyResult<String> {
+        let fields = if self.fields.is_empty() { "*".to_string() } else { self.fields.join(", ") };
+        let mut sql = format!("SELECT {} FROM {}", fields, self.alias);
+        if !self.conditions.is_empty() {
+            let conditions: Vec<String> = self.conditions.iter().map(|c| format!("({})", c)).collect();
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        if !self.order_by.is_empty() {
+            sql.push_str(&format!(" ORDER BY {}", self.order_by.join(", ")));
+        }
+        match (self.offset, self.limit) {
+            (offset, Some(limit)) => sql.push_str(&format!(" OFFSET {} LIMIT {}", offset.unwrap_or(0), limit)),
+            (Some(_), None) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "offset requires limit; Cosmos DB only supports OFFSET together with LIMIT"
+                ));
+            }
+            (None, None) => {}
+        }
+        Ok(sql)
+    }
+
+    /// The bound parameters, as `[{"name": "@x", "value": ...}]` accepted by `query_items`
+    #[getter]
+    pub fn parameters<'py>(&self, py: Python<'py>) -> PyResult<&'py PyList> {
+        let list = PyList::empty(py);
+        for (name, value) in &self.parameters {
+            let dict = PyDict::new(py);
+            dict.set_item("name", name)?;
+            dict.set_item("value", value)?;
+            list.append(dict)?;
+        }
+        Ok(list)
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("QueryBuilder({:?})", self.query()?))
+    }
+}
+
+impl QueryBuilder {
+    fn bind(&mut self, py: Python, name: String, value: &PyAny) -> PyResult<()> {
+        if let Some((_, existing)) = self.parameters.iter().find(|(n, _)| *n == name) {
+            if !existing.as_ref(py).eq(value)? {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Parameter {} is already bound to a different value", name)
+                ));
+            }
+            return Ok(());
+        }
+        self.parameters.push((name, value.into()));
+        Ok(())
+    }
+}
+
+/// Prefix a parameter name with `@` unless it already has one
+fn parameter_name(name: &str) -> String {
+    if name.starts_with('@') { name.to_string() } else { format!("@{}", name) }
+}
+
+/// Build a query from a SQL string or a `QueryBuilder`, binding any `parameters` kwarg
+/// `parameters` is a list of `{"name": "@x", "value": ...}` dicts, as in the v4 SDK
+pub fn build_query(py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<Query> {
+    let (sql, parameters) = query_parts(py, query, kwargs)?;
+
+    let mut query = Query::from(sql);
+    for (name, value) in parameters {
+        query = query.with_parameter(name, value)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid query parameter: {}", e)))?;
+    }
+    Ok(query)
+}
+
+/// The SQL text and bound parameters of a SQL string or `QueryBuilder` plus `parameters` kwarg
+pub fn query_parts(py: Python, query: &PyAny, kwargs: Option<&PyDict>) -> PyResult<(String, Vec<(String, Value)>)> {
+    let (sql, mut parameters) = match query.extract::<PyRef<QueryBuilder>>() {
+        Ok(builder) => (builder.query()?, builder.parameters(py)?.iter().collect::<Vec<_>>()),
+        Err(_) => (query.extract::<String>()?, Vec::new()),
+    };
+    if let Some(kw) = kwargs {
+        if let Some(extra) = kw.get_item("parameters")? {
+            parameters.extend(extra.iter()?.collect::<PyResult<Vec<_>>>()?);
+        }
+    }
+    Ok((sql, query_parameters(py, parameters)?))
+}
+
+/// Names and values of a list of `{"name": "@x", "value": ...}` parameter dicts
+pub fn query_parameters<'py>(
+    py: Python<'py>,
+    parameters: impl IntoIterator<Item = &'py PyAny>,
+) -> PyResult<Vec<(String, Value)>> {
+    parameters
+        .into_iter()
+        .enumerate()
+        .map(|(index, parameter)| {
+            let field = |key: &str| {
+                parameter.get_item(key).map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Query parameter {} must be a dict with 'name' and 'value' keys", index
+                )))
+            };
+            let name = parameter_name(field("name")?.extract()?);
+            Ok((name, py_to_json(py, field("value")?)?))
+        })
+        .collect()
+}
+
+/// Right-hand side of an equality predicate found by `equality_operands`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Literal(Value),
+    Parameter(String),
+}
+
+/// Find the values `{alias}.{field}` is compared to with `=` in a query
+/// This is a lexical scan for the obvious `c.field = 'x'` / `c.field = @p` forms, not a SQL
+/// parser: predicates under OR or NOT, reversed operands and bracket notation are not found.
+pub fn equality_operands(sql: &str, field: &str) -> Vec<Operand> {
+    let Some(alias) = from_alias(sql) else {
+        return Vec::new();
+    };
+    let target = format!("{}.{}", alias, field);
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '.' || c == '@';
+
+    let mut operands = Vec::new();
+    for (start, _) in sql.match_indices(&target) {
+        let before = sql[..start].chars().next_back();
+        let rest = &sql[start + target.len()..];
+        if before.is_some_and(is_identifier) || rest.chars().next().is_some_and(is_identifier) {
+            continue;
+        }
+
+        let rest = rest.trim_start();
+        let Some(rest) = rest.strip_prefix('=').filter(|r| !r.starts_with('=')) else {
+            continue;
+        };
+        if let Some(operand) = parse_operand(rest.trim_start()) {
+            operands.push(operand);
+        }
+    }
+    operands
+}
+
+/// The alias after `FROM`, e.g. `c` in `SELECT * FROM c WHERE ...`
+fn from_alias(sql: &str) -> Option<&str> {
+    let mut tokens = sql.split_whitespace();
+    tokens.find(|token| token.eq_ignore_ascii_case("from"))?;
+    tokens.next()
+}
+
+fn parse_operand(text: &str) -> Option<Operand> {
+    let mut chars = text.chars();
+    match chars.next()? {
+        quote @ ('\'' | '"') => {
+            let end = text[1..].find(quote)?;
+            Some(Operand::Literal(Value::String(text[1..1 + end].to_string())))
+        }
+        '@' => {
+            let name: String = chars.take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            Some(Operand::Parameter(format!("@{}", name)))
+        }
+        c if c.is_ascii_digit() || c == '-' => {
+            let number: String = text.chars().take_while(|c| c.is_ascii_digit() || matches!(*c, '-' | '.' | 'e' | 'E' | '+')).collect();
+            serde_json::from_str(&number).ok().map(Operand::Literal)
+        }
+        _ => None,
+    }
+}
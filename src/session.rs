@@ -0,0 +1,92 @@
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+/// Session token of one partition key range
+/// Either the simple form `{lsn}` or the vector form `{version}#{global_lsn}#{region}={lsn}...`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RangeToken {
+    version: u64,
+    global_lsn: u64,
+    region_lsns: BTreeMap<u32, u64>,
+    vector: bool,
+}
+
+impl RangeToken {
+    fn parse(text: &str) -> PyResult<Self> {
+        let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid session token '{}'", text));
+        let number = |part: &str| part.trim().parse::<u64>().map_err(|_| invalid());
+
+        let parts: Vec<&str> = text.split('#').collect();
+        if parts.len() == 1 {
+            return Ok(Self { version: 0, global_lsn: number(parts[0])?, region_lsns: BTreeMap::new(), vector: false });
+        }
+
+        let mut region_lsns = BTreeMap::new();
+        for region in &parts[2..] {
+            let (id, lsn) = region.split_once('=').ok_or_else(invalid)?;
+            region_lsns.insert(id.trim().parse::<u32>().map_err(|_| invalid())?, number(lsn)?);
+        }
+        Ok(Self { version: number(parts[0])?, global_lsn: number(parts[1])?, region_lsns, vector: true })
+    }
+
+    /// Combine two tokens of the same range, keeping the highest progress seen by either
+    /// A higher version means the range was reconfigured, so the newer token replaces the older
+    fn merge(self, other: Self) -> Self {
+        if self.version != other.version {
+            return if self.version > other.version { self } else { other };
+        }
+
+        let mut merged = self;
+        merged.global_lsn = merged.global_lsn.max(other.global_lsn);
+        merged.vector |= other.vector;
+        for (region, lsn) in other.region_lsns {
+            let entry = merged.region_lsns.entry(region).or_insert(lsn);
+            *entry = (*entry).max(lsn);
+        }
+        merged
+    }
+
+    fn format(&self) -> String {
+        if !self.vector {
+            return self.global_lsn.to_string();
+        }
+        let mut text = format!("{}#{}", self.version, self.global_lsn);
+        for (region, lsn) in &self.region_lsns {
+            text.push_str(&format!("#{}={}", region, lsn));
+        }
+        text
+    }
+}
+
+/// Merge composite session tokens (`{range}:{token},...`), keeping the max LSN per range
+pub fn merge(tokens: &[&str]) -> PyResult<String> {
+    let mut ranges: BTreeMap<String, RangeToken> = BTreeMap::new();
+    for token in tokens {
+        for range_token in token.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let (range, value) = range_token.split_once(':').ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid session token '{}': expected '<partition key range id>:<token>'", range_token
+                ))
+            })?;
+            let value = RangeToken::parse(value)?;
+            let merged = match ranges.remove(range) {
+                Some(existing) => existing.merge(value),
+                None => value,
+            };
+            ranges.insert(range.to_string(), merged);
+        }
+    }
+
+    Ok(ranges
+        .iter()
+        .map(|(range, token)| format!("{}:{}", range, token.format()))
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Merge session tokens from several sources into one that is at least as recent as each
+#[pyfunction]
+#[pyo3(signature = (*tokens))]
+pub fn merge_session_tokens(tokens: Vec<&str>) -> PyResult<String> {
+    merge(&tokens)
+}
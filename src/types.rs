@@ -32,3 +32,75 @@ impl PartitionKey {
         }
     }
 }
+
+/// Declared JSON type of a container's partition key values
+/// Cosmos does not record the type, so it is supplied by the caller when getting the container client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionKeyType {
+    String,
+    Number,
+}
+
+impl PartitionKeyType {
+    pub fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "string" => Ok(Self::String),
+            "number" => Ok(Self::Number),
+            other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "partition_key_type must be 'string' or 'number', got '{}'", other
+            ))),
+        }
+    }
+
+    /// Coerce a partition key value to this type, warning when a conversion happens
+    pub fn coerce<'py>(&self, py: Python<'py>, value: &'py PyAny) -> PyResult<&'py PyAny> {
+        let is_number = !value.is_instance_of::<pyo3::types::PyBool>()
+            && (value.is_instance_of::<pyo3::types::PyLong>() || value.is_instance_of::<pyo3::types::PyFloat>());
+
+        let coerced = match self {
+            Self::String if is_number => value.str()?.as_ref(),
+            Self::Number if value.is_instance_of::<pyo3::types::PyString>() => {
+                let text = value.extract::<&str>()?;
+                if let Ok(i) = text.trim().parse::<i64>() {
+                    i.into_py(py).into_ref(py)
+                } else if let Some(f) = text.trim().parse::<f64>().ok().filter(|f| f.is_finite()) {
+                    f.into_py(py).into_ref(py)
+                } else {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Partition key {:?} cannot be coerced to the container's number type", text
+                    )));
+                }
+            }
+            _ => return Ok(value),
+        };
+
+        PyErr::warn(
+            py,
+            py.get_type::<pyo3::exceptions::PyUserWarning>(),
+            &format!("Partition key {} was coerced to {}", value.repr()?, coerced.repr()?),
+            1,
+        )?;
+        Ok(coerced)
+    }
+}
+
+/// Partition key kind from a container's definition
+/// `Hash` containers take a single value; `MultiHash` containers take hierarchical keys of up to
+/// three components, listed in the order of the container's paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionKeyKind {
+    Hash,
+    MultiHash,
+}
+
+impl PartitionKeyKind {
+    /// Read the kind from a `partitionKey` definition, inferring it from the path count when absent
+    pub fn from_definition(kind: Option<&str>, path_count: usize) -> Self {
+        match kind {
+            Some("MultiHash") => Self::MultiHash,
+            Some(_) => Self::Hash,
+            None if path_count > 1 => Self::MultiHash,
+            None => Self::Hash,
+        }
+    }
+}
@@ -1,12 +1,59 @@
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyString};
-use serde_json::Value;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyFrozenSet, PyList, PyLong, PySet, PyString, PyTuple};
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
 use pythonize::depythonize;
+use crate::config::ClientConfig;
+use crate::exceptions::deserialization_error;
 
 /// Convert Python object (dict or string) to serde_json::Value
 /// Hybrid approach: accepts both PyDict (PyO3 native serialization) and String (direct serde parsing)
-pub fn py_object_to_json(py: Python, obj: &PyAny) -> PyResult<Value> {
+/// Write-side client settings such as `exclude_none_on_write` and `id_field` are applied to the result
+/// Objects are `BTreeMap`s (serde_json's `preserve_order` is off), so keys serialize sorted.
+pub fn py_object_to_json(py: Python, obj: &PyAny, config: &ClientConfig) -> PyResult<Value> {
+    let mut value = py_object_to_json_value(py, obj)?;
+    if config.exclude_none_on_write {
+        strip_nulls(&mut value);
+    }
+    if let Some(field) = &config.id_field {
+        move_id_field_to_id(&mut value, field)?;
+    }
+    Ok(value)
+}
+
+/// Move the logical id property `field` of an item to `id`, as the service requires
+/// Items without `field` are left as they are; an item with both must agree on the value.
+pub fn move_id_field_to_id(value: &mut Value, field: &str) -> PyResult<()> {
+    let Value::Object(item) = value else {
+        return Ok(());
+    };
+    let Some(logical_id) = item.remove(field) else {
+        return Ok(());
+    };
+    match item.get("id") {
+        Some(id) if *id != logical_id => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Item has both '{}' ({}) and 'id' ({}) with different values", field, logical_id, id
+        ))),
+        _ => {
+            item.insert("id".to_string(), logical_id);
+            Ok(())
+        }
+    }
+}
+
+/// Move the `id` of an item returned by the service to the logical id property `field`
+pub fn move_id_to_id_field(value: &mut Value, field: &str) {
+    if let Value::Object(item) = value {
+        if let Some(id) = item.remove("id") {
+            item.insert(field.to_string(), id);
+        }
+    }
+}
+
+fn py_object_to_json_value(py: Python, obj: &PyAny) -> PyResult<Value> {
     // Fast path: if it's already a JSON string, parse directly with serde
     if let Ok(json_str) = obj.extract::<String>() {
         return serde_json::from_str(&json_str)
@@ -15,34 +62,434 @@ pub fn py_object_to_json(py: Python, obj: &PyAny) -> PyResult<Value> {
             ));
     }
     
-    // Compatible path: if it's a dict, use PyO3's native serialization (faster than Python's json.dumps)
+    // Native path: walk dicts, lists and scalars directly into a serde_json::Value
+    py_to_json(py, obj)
+}
+
+/// Recursively convert a Python object to serde_json::Value
+/// Tuples become arrays; sets and frozensets become sorted arrays, so they do not round-trip
+/// back to sets. `enum.Enum` members are written as their `.value`, so they are read back as
+/// plain values. Objects the walker does not know are handed to `depythonize`.
+/// Documents nested deeper than the client can read back raise `ValueError`.
+pub fn py_to_json(py: Python, obj: &PyAny) -> PyResult<Value> {
+    py_to_json_nested(py, obj, 0)
+}
+
+/// `py_to_json` for a value inside `depth` enclosing dicts and lists
+fn py_to_json_nested(py: Python, obj: &PyAny, depth: usize) -> PyResult<Value> {
+    if obj.is_none() {
+        return Ok(Value::Null);
+    }
+    // bool must be checked before int, since bool is a subclass of int in Python
+    if let Ok(b) = obj.downcast::<PyBool>() {
+        return Ok(Value::Bool(b.is_true()));
+    }
+    if obj.is_instance_of::<PyLong>() {
+        if let Ok(i) = obj.extract::<i64>() {
+            return Ok(Value::from(i));
+        }
+        return obj.extract::<u64>().map(Value::from).map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Integer out of range for JSON: {}", obj))
+        });
+    }
+    if let Ok(f) = obj.downcast::<PyFloat>() {
+        return Number::from_f64(f.value()).map(Value::Number).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Float is not valid JSON: {}", obj))
+        });
+    }
+    if let Ok(s) = obj.downcast::<PyString>() {
+        return Ok(Value::String(s.to_str()?.to_string()));
+    }
+    let is_container = obj.is_instance_of::<PyDict>()
+        || obj.is_instance_of::<PyList>()
+        || obj.is_instance_of::<PyTuple>()
+        || obj.is_instance_of::<PySet>()
+        || obj.is_instance_of::<PyFrozenSet>();
+    // The same limit as `parse_json_document`, which also keeps the walk off the end of the stack
+    if is_container && depth + 1 >= MAX_JSON_DEPTH {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Item is nested deeper than {} levels", MAX_JSON_DEPTH
+        )));
+    }
     if let Ok(dict) = obj.downcast::<PyDict>() {
-        return depythonize(dict)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Failed to serialize dict: {}", e)
-            ));
+        let mut map = Map::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            map.insert(json_key(py, key)?, py_to_json_nested(py, value, depth + 1)?);
+        }
+        return Ok(Value::Object(map));
+    }
+    if let Ok(list) = obj.downcast::<PyList>() {
+        return list.iter()
+            .map(|item| py_to_json_nested(py, item, depth + 1))
+            .collect::<PyResult<_>>()
+            .map(Value::Array);
+    }
+    if let Ok(tuple) = obj.downcast::<PyTuple>() {
+        return tuple.iter()
+            .map(|item| py_to_json_nested(py, item, depth + 1))
+            .collect::<PyResult<_>>()
+            .map(Value::Array);
+    }
+    if obj.is_instance_of::<PySet>() || obj.is_instance_of::<PyFrozenSet>() {
+        return set_to_json(py, obj, depth);
+    }
+    // Members of `int`, `str` and `float` mixin enums such as `IntEnum` were handled above as
+    // their base type, which is their value
+    if let Some(value) = enum_value(py, obj)? {
+        return py_to_json_nested(py, value, depth);
     }
     
-    // Fallback: try to depythonize any Python object
+    // Fallback: try to depythonize any other Python object
     depythonize(obj)
         .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
             format!("Failed to serialize Python object: {}", e)
         ))
 }
 
-/// Convert Python dict to serde_json::Value (legacy function, kept for compatibility)
-pub fn py_dict_to_json(py: Python, dict: &PyDict) -> PyResult<Value> {
-    depythonize(dict)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid JSON: {}", e)))
+/// The `.value` of an `enum.Enum` member, or `None` for any other object
+fn enum_value<'py>(py: Python<'py>, obj: &'py PyAny) -> PyResult<Option<&'py PyAny>> {
+    let enum_type = py.import("enum")?.getattr("Enum")?;
+    if obj.is_instance(enum_type)? {
+        obj.getattr("value").map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Convert a dict key to a JSON object key, following `json.dumps` for non-string scalars
+/// `enum.Enum` keys are converted through their `.value`, like `enum.Enum` values.
+fn json_key(py: Python, key: &PyAny) -> PyResult<String> {
+    if let Ok(s) = key.downcast::<PyString>() {
+        return Ok(s.to_str()?.to_string());
+    }
+    // Checked before the numeric keys, whose `repr` would be the member's
+    if let Some(value) = enum_value(py, key)? {
+        return json_key(py, value);
+    }
+    if key.is_none() {
+        Ok("null".to_string())
+    } else if let Ok(b) = key.downcast::<PyBool>() {
+        Ok(if b.is_true() { "true" } else { "false" }.to_string())
+    } else if key.is_instance_of::<PyLong>() || key.is_instance_of::<PyFloat>() {
+        Ok(key.repr()?.to_str()?.to_string())
+    } else {
+        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            format!("Keys must be str, int, float, bool or None, not {}", key.get_type().name()?)
+        ))
+    }
+}
+
+/// Convert a set to a JSON array in sorted order so the stored document is deterministic
+/// Sets whose elements Python cannot order are sorted by the JSON text of their elements
+fn set_to_json(py: Python, set: &PyAny, depth: usize) -> PyResult<Value> {
+    if let Ok(sorted) = py.import("builtins")?.call_method1("sorted", (set,)) {
+        return py_to_json_nested(py, sorted, depth);
+    }
+    
+    let mut items = set.iter()?
+        .map(|item| py_to_json_nested(py, item?, depth + 1))
+        .collect::<PyResult<Vec<_>>>()?;
+    items.sort_by_cached_key(|item| item.to_string());
+    Ok(Value::Array(items))
+}
+
+/// Recursively remove object keys whose value is null
+pub fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+/// Properties the service adds to every stored item
+const SYSTEM_PROPERTIES: [&str; 5] = ["_rid", "_self", "_etag", "_attachments", "_ts"];
+
+/// Copy of an item without the service-assigned system properties
+pub fn without_system_properties(value: &Value) -> Value {
+    let mut value = value.clone();
+    if let Value::Object(map) = &mut value {
+        for property in SYSTEM_PROPERTIES {
+            map.remove(property);
+        }
+    }
+    value
+}
+
+/// One step of a parsed JSONPath
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse a basic JSONPath such as `$.address.lines[0]` or `meta['display name']`
+/// Only child and array-index steps are supported; wildcards, slices and filters are not.
+fn parse_json_path(path: &str) -> PyResult<Vec<PathSegment>> {
+    let invalid = |reason: &str| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid json_path '{}': {}", path, reason))
+    };
+    let is_key_char = |c: char| c != '.' && c != '[';
+
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+    let mut first = true;
+    while !rest.is_empty() {
+        if let Some(bracketed) = rest.strip_prefix('[') {
+            let end = bracketed.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+            let inner = bracketed[..end].trim();
+            let quoted = inner.len() >= 2
+                && ((inner.starts_with('\'') && inner.ends_with('\'')) || (inner.starts_with('"') && inner.ends_with('"')));
+            if quoted {
+                segments.push(PathSegment::Key(inner[1..inner.len() - 1].to_string()));
+            } else {
+                let index = inner.parse::<usize>().map_err(|_| invalid("brackets must hold an index or a quoted key"))?;
+                segments.push(PathSegment::Index(index));
+            }
+            rest = &bracketed[end + 1..];
+        } else {
+            // A leading key may omit the dot, as in `address.city`
+            let key_start = match rest.strip_prefix('.') {
+                Some(key_start) => key_start,
+                None if first => rest,
+                None => return Err(invalid("expected '.' or '['")),
+            };
+            let end = key_start.find(|c: char| !is_key_char(c)).unwrap_or(key_start.len());
+            if end == 0 {
+                return Err(invalid("empty key"));
+            }
+            segments.push(PathSegment::Key(key_start[..end].to_string()));
+            rest = &key_start[end..];
+        }
+        first = false;
+    }
+    Ok(segments)
+}
+
+/// Take the subtree of `value` at a basic JSONPath, `None` if any step does not exist
+pub fn extract_json_path(value: Value, path: &str) -> PyResult<Option<Value>> {
+    let mut current = value;
+    for segment in parse_json_path(path)? {
+        let next = match (segment, current) {
+            (PathSegment::Key(key), Value::Object(mut map)) => map.remove(&key),
+            (PathSegment::Index(index), Value::Array(mut items)) if index < items.len() => Some(items.swap_remove(index)),
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current))
 }
 
 /// Convert serde_json::Value to Python dict
 pub fn json_to_py_dict(py: Python, value: &Value) -> PyResult<PyObject> {
-    let json_str = serde_json::to_string(value)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("JSON serialization error: {}", e)))?;
-    
-    let json_module = py.import("json")?;
-    json_module.call_method1("loads", (json_str,))?.extract()
+    json_to_py(py, value)
+}
+
+/// Convert a JSON value to the Python object `json.loads` would return for it, without
+/// serializing it to a string and parsing that again
+/// Integers become `int` and other numbers `float`; objects keep serde_json's sorted key order.
+pub fn json_to_py(py: Python, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_py(py),
+        Value::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+            (Some(i), _, _) => i.into_py(py),
+            (None, Some(u), _) => u.into_py(py),
+            (None, None, Some(f)) => f.into_py(py),
+            (None, None, None) => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("JSON number {} cannot be represented", n)
+            )),
+        },
+        Value::String(s) => s.into_py(py),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            dict.into()
+        }
+    })
+}
+
+/// Nesting depth at which serde_json stops parsing a document
+const MAX_JSON_DEPTH: usize = 128;
+
+/// Deserialize a response document, tracking the path being parsed
+/// Documents the binding cannot represent, such as numbers beyond the range of a 64-bit float
+/// or nesting deeper than 128 levels, raise `CosmosDeserializationError` naming the path.
+pub fn parse_json_document(bytes: &[u8]) -> PyResult<Value> {
+    let path = RefCell::new(Vec::new());
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    TrackedValue { path: &path }
+        .deserialize(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|_| value))
+        .map_err(|e| document_error(&path.borrow(), &e))
+}
+
+/// Deserialize a JSON object keeping only the listed top-level fields.
+/// Values of all other fields are skipped by the parser without being materialized.
+pub fn deserialize_projected(bytes: &[u8], fields: &[String]) -> PyResult<Value> {
+    let path = RefCell::new(Vec::new());
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    Projection { fields, path: &path }
+        .deserialize(&mut deserializer)
+        .map(Value::Object)
+        .map_err(|e| document_error(&path.borrow(), &e))
+}
+
+/// Describe a parse failure at `path` in terms of what the binding cannot represent
+fn document_error(path: &[PathSegment], err: &serde_json::Error) -> PyErr {
+    let message = err.to_string();
+    let reason = if message.starts_with("recursion limit exceeded") {
+        format!("nesting is deeper than {} levels", MAX_JSON_DEPTH)
+    } else if message.starts_with("number out of range") {
+        "number is out of range for a 64-bit float".to_string()
+    } else {
+        message
+    };
+    deserialization_error(format_json_path(path), reason)
+}
+
+/// Render a parsed path in the JSONPath syntax accepted by `json_path`
+fn format_json_path(path: &[PathSegment]) -> String {
+    let mut rendered = String::from("$");
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) if !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+                rendered.push('.');
+                rendered.push_str(key);
+            }
+            PathSegment::Key(key) => rendered.push_str(&format!("[{}]", Value::String(key.clone()))),
+            PathSegment::Index(index) => rendered.push_str(&format!("[{}]", index)),
+        }
+    }
+    rendered
+}
+
+/// Builds a `Value` like serde_json's own deserializer, recording the path it is at
+/// On failure the path is left pointing at the value that could not be parsed.
+#[derive(Clone, Copy)]
+struct TrackedValue<'a> {
+    path: &'a RefCell<Vec<PathSegment>>,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for TrackedValue<'a> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for TrackedValue<'a> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        loop {
+            self.path.borrow_mut().push(PathSegment::Index(items.len()));
+            match seq.next_element_seed(self)? {
+                Some(item) => items.push(item),
+                None => {
+                    self.path.borrow_mut().pop();
+                    return Ok(Value::Array(items));
+                }
+            }
+            self.path.borrow_mut().pop();
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut object = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            self.path.borrow_mut().push(PathSegment::Key(key.clone()));
+            let value = map.next_value_seed(self)?;
+            self.path.borrow_mut().pop();
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+struct Projection<'a> {
+    fields: &'a [String],
+    path: &'a RefCell<Vec<PathSegment>>,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for Projection<'a> {
+    type Value = Map<String, Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for Projection<'a> {
+    type Value = Map<String, Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut projected = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if self.fields.contains(&key) {
+                self.path.borrow_mut().push(PathSegment::Key(key.clone()));
+                let value = map.next_value_seed(TrackedValue { path: self.path })?;
+                self.path.borrow_mut().pop();
+                projected.insert(key, value);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(projected)
+    }
 }
 
 /// Convert Python kwargs to options
@@ -1,6 +1,11 @@
 """Test configuration and fixtures for Azure Cosmos DB tests."""
 
+import base64
 import os
+import subprocess
+import sys
+from pathlib import Path
+
 import pytest
 from azure.cosmos import CosmosClient
 from azure.cosmos.aio import CosmosClient as AsyncCosmosClient
@@ -113,3 +118,37 @@ async def async_container(async_database, test_container_id):
         await async_database.delete_container(test_container_id)
     except Exception:
         pass
+
+
+@pytest.fixture(scope="session")
+def document_endpoint(tmp_path_factory):
+    """Serve canned point-read responses from a local stand-in endpoint.
+    
+    Yields ``(url, credential, documents)``: raw JSON written to ``documents / f"{id}.json"`` is
+    returned verbatim for item ``id`` of any container (see ``document_server.py``). The server
+    runs in its own process so it can answer while the calling thread waits on the binding.
+    """
+    documents = tmp_path_factory.mktemp("documents")
+    server = subprocess.Popen(
+        [sys.executable, str(Path(__file__).parent / "document_server.py"), str(documents)],
+        stdout=subprocess.PIPE,
+        text=True,
+    )
+    try:
+        port = int(server.stdout.readline())
+        credential = base64.b64encode(b"canned-documents-key").decode()
+        yield f"http://127.0.0.1:{port}/", credential, documents
+    finally:
+        server.terminate()
+        server.wait()
+
+
+@pytest.fixture(scope="session")
+def canned_documents(document_endpoint):
+    """A container on the stand-in endpoint, as ``(container, documents)``.
+    
+    ``container.read_item(id, partition_key=id)`` returns ``documents / f"{id}.json"``.
+    """
+    url, credential, documents = document_endpoint
+    client = CosmosClient(url, credential=credential)
+    return client.get_database_client("canned").get_container_client("documents"), documents
@@ -0,0 +1,90 @@
+"""A stand-in Cosmos DB endpoint serving canned point-read responses.
+
+Usage: ``python document_server.py <documents_dir>``. Prints the port it listens on, then
+answers ``GET .../docs/<id>`` with the raw contents of ``<documents_dir>/<id>.json``, so tests
+can exercise responses the service would refuse to store. If ``<id>.gone`` holds a count N,
+the first N reads of the document answer 410 Gone with the partition split sub-status instead,
+and likewise ``<id>.unavailable`` makes them answer 503 Service Unavailable. If ``<id>.chunked``
+exists, the document is sent with chunked transfer encoding and no Content-Length.
+Other reads get a minimal resource with a ``/id`` partition key. If ``<documents_dir>/<name>.delay``
+holds a number of seconds, the next read of a path ending in ``<name>`` waits that long first.
+Queries return no documents, and resuming one with a continuation token answers 400 Bad Request.
+"""
+
+import json
+import sys
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+
+
+class DocumentHandler(BaseHTTPRequestHandler):
+    documents = Path(".")
+    protocol_version = "HTTP/1.1"
+
+    def log_message(self, format, *args):
+        pass
+
+    def do_GET(self):
+        segments = self.path.split("?")[0].strip("/").split("/")
+        delay = self.documents / f"{segments[-1]}.delay"
+        if delay.exists():
+            seconds = float(delay.read_text())
+            delay.unlink()
+            time.sleep(seconds)
+        if len(segments) == 6 and segments[4] == "docs":
+            document = self.documents / f"{segments[5]}.json"
+            gone = self.documents / f"{segments[5]}.gone"
+            if gone.exists() and int(gone.read_text()) > 0:
+                gone.write_text(str(int(gone.read_text()) - 1))
+                self.respond(410, json.dumps({"code": "Gone", "message": "Gone"}).encode(), {"x-ms-substatus": "1002"})
+                return
+            unavailable = self.documents / f"{segments[5]}.unavailable"
+            if unavailable.exists() and int(unavailable.read_text()) > 0:
+                unavailable.write_text(str(int(unavailable.read_text()) - 1))
+                self.respond(503, json.dumps({"code": "ServiceUnavailable", "message": "Unavailable"}).encode())
+                return
+            if not document.exists():
+                self.respond(404, json.dumps({"code": "NotFound", "message": "NotFound"}).encode())
+                return
+            if (self.documents / f"{segments[5]}.chunked").exists():
+                self.respond_chunked(200, document.read_bytes())
+                return
+            self.respond(200, document.read_bytes())
+        else:
+            resource = {"id": segments[-1], "partitionKey": {"paths": ["/id"], "kind": "Hash"}}
+            self.respond(200, json.dumps(resource).encode())
+
+    def do_POST(self):
+        self.rfile.read(int(self.headers.get("Content-Length", 0)))
+        if self.headers.get("x-ms-continuation"):
+            self.respond(400, json.dumps({"code": "BadRequest", "message": "Malformed request"}).encode())
+            return
+        self.respond(200, json.dumps({"Documents": [], "_count": 0}).encode())
+
+    def respond(self, status, body, headers=None):
+        self.send_response(status)
+        for name, value in (headers or {}).items():
+            self.send_header(name, value)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def respond_chunked(self, status, body, chunk_size=1024):
+        self.send_response(status)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Transfer-Encoding", "chunked")
+        self.end_headers()
+        for start in range(0, len(body), chunk_size):
+            chunk = body[start:start + chunk_size]
+            self.wfile.write(f"{len(chunk):x}\r\n".encode() + chunk + b"\r\n")
+        self.wfile.write(b"0\r\n\r\n")
+
+
+if __name__ == "__main__":
+    DocumentHandler.documents = Path(sys.argv[1])
+    server = ThreadingHTTPServer(("127.0.0.1", 0), DocumentHandler)
+    server.daemon_threads = True
+    print(server.server_address[1], flush=True)
+    server.serve_forever()
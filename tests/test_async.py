@@ -84,7 +84,7 @@ class TestAsyncDatabaseProxy:
         partition_key = {"paths": ["/id"], "kind": "Hash"}
         result = await async_database.create_container(test_container_id, partition_key)
         assert result is not None
-        assert result.get("id") == test_container_id
+        assert result.id == test_container_id
         
         # Cleanup
         await async_database.delete_container(test_container_id)
@@ -162,6 +162,26 @@ class TestAsyncContainerOperations:
         assert result.get("id") == item["id"]
         assert result.get("value") == item["value"]
 
+    @pytest.mark.asyncio
+    async def test_async_operations_do_not_block_event_loop(self, async_container):
+        """Test that the event loop keeps running other tasks while operations are awaited."""
+        ticks = 0
+        
+        async def ticker():
+            nonlocal ticks
+            while True:
+                ticks += 1
+                await asyncio.sleep(0)
+        
+        ticker_task = asyncio.create_task(ticker())
+        try:
+            await asyncio.gather(*(
+                async_container.create_item(body={"id": f"async_non_blocking_{i}"}) for i in range(5)
+            ))
+        finally:
+            ticker_task.cancel()
+        assert ticks > 5
+
     @pytest.mark.asyncio
     async def test_async_upsert_item(self, async_container):
         """Test upserting an item asynchronously."""
@@ -242,6 +262,20 @@ class TestAsyncContainerOperations:
         assert isinstance(results, list)
         assert len(results) >= 5
 
+    @pytest.mark.asyncio
+    async def test_async_query_items_iter(self, async_container):
+        """Test iterating over query results with async for."""
+        for i in range(5):
+            await async_container.create_item(body={"id": f"async_iter_{i}", "kind": "async_iter"})
+        
+        ids = [
+            item["id"]
+            async for item in async_container.query_items_iter(
+                "SELECT * FROM c WHERE c.kind = 'async_iter'", max_item_count=2
+            )
+        ]
+        assert sorted(ids) == [f"async_iter_{i}" for i in range(5)]
+
     @pytest.mark.asyncio
     async def test_async_query_items_with_filter(self, async_container):
         """Test querying items with a filter asynchronously."""
@@ -291,6 +325,39 @@ class TestAsyncContainerOperations:
         read_results = await asyncio.gather(*read_tasks)
         assert len(read_results) == 10
 
+    @pytest.mark.asyncio
+    async def test_async_operation_timeout(self, async_container):
+        """Test that the timeout keyword aborts the operation like asyncio.wait_for."""
+        import asyncio
+        
+        with pytest.raises(asyncio.TimeoutError):
+            await async_container.read_item(item="timeout", partition_key="timeout", timeout=1e-6)
+
+    @pytest.mark.asyncio
+    async def test_async_operation_cancellation(self, async_container):
+        """Test that cancelling the awaiting task cancels the operation."""
+        import asyncio
+        
+        task = asyncio.ensure_future(
+            async_container.read_item(item="cancelled", partition_key="cancelled")
+        )
+        await asyncio.sleep(0)
+        task.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await task
+
+    @pytest.mark.asyncio
+    async def test_cancelled_token_aborts_operation(self, async_container):
+        """Test that an operation given a cancelled token does not run."""
+        import asyncio
+        from azure.cosmos._rust import CancellationToken
+        
+        token = CancellationToken()
+        token.cancel()
+        assert token.cancelled
+        with pytest.raises(asyncio.CancelledError):
+            async_container._client.read_item("cancelled", "cancelled", cancellation_token=token)
+
 
 class TestAsyncExceptionHandling:
     """Test suite for async exception handling."""
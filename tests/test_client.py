@@ -1,22 +1,100 @@
 """Tests for CosmosClient functionality."""
 
+import asyncio
+import json
+import threading
+import time
+import types
+
 import pytest
-from azure.cosmos import CosmosClient
+from azure.cosmos import CosmosClient, ContainerProperties, DatabaseProperties
 from azure.cosmos.exceptions import (
     CosmosHttpResponseError,
     CosmosResourceNotFoundError,
     CosmosResourceExistsError,
+    CosmosReadOnlyError,
+    CosmosResponseTooLargeError,
+    CosmosServiceUnavailableError,
+    CosmosTimeoutError,
 )
 
 
 class TestCosmosClient:
     """Test suite for CosmosClient."""
 
+    def test_client_creation_with_token_credential(self, account_url, database):
+        """Test that an object with get_token is used as an Azure AD credential."""
+        class StaticTokenCredential:
+            def __init__(self):
+                self.scopes = []
+            
+            def get_token(self, *scopes, **kwargs):
+                self.scopes.append(scopes)
+                return types.SimpleNamespace(token="not-a-real-token", expires_on=int(time.time()) + 3600)
+        
+        credential = StaticTokenCredential()
+        client = CosmosClient(account_url, credential=credential)
+        try:
+            client.get_database_client(database.id).read()
+        except CosmosHttpResponseError:
+            pass  # The service may reject the token; only the credential call is checked
+        assert credential.scopes
+        assert all(scope.endswith("/.default") for scopes in credential.scopes for scope in scopes)
+        
+        with pytest.raises(ValueError, match="get_token"):
+            CosmosClient(account_url, credential=object())
+
+    def test_from_connection_string(self, account_url, account_key):
+        """Test that the endpoint and key are parsed, tolerating whitespace and a trailing ';'."""
+        client = CosmosClient.from_connection_string(
+            f" AccountEndpoint={account_url} ; AccountKey={account_key};"
+        )
+        assert isinstance(client.list_databases(), list)
+        
+        with pytest.raises(ValueError, match="AccountKey"):
+            CosmosClient.from_connection_string(f"AccountEndpoint={account_url};")
+        with pytest.raises(ValueError, match="AccountEndpoint"):
+            CosmosClient.from_connection_string(f"AccountKey={account_key}")
+
+    def test_token_credential_operations_complete(self, document_endpoint):
+        """Test that operations authenticating with a Python credential do not deadlock on the GIL."""
+        class StaticTokenCredential:
+            def get_token(self, *scopes, **kwargs):
+                return types.SimpleNamespace(token="not-a-real-token", expires_on=int(time.time()) + 3600)
+        
+        url, _, documents = document_endpoint
+        (documents / "token_item.json").write_text('{"id": "token_item"}')
+        client = CosmosClient(url, credential=StaticTokenCredential())
+        results = []
+        
+        def run():
+            database = client.get_database_client("canned")
+            results.append(database.read()["id"])
+            container = database.get_container_client("documents")
+            results.append(container.read_item(item="token_item", partition_key="token_item")["id"])
+            results.append(container.query_items("SELECT * FROM c", partition_key="token_item"))
+        
+        worker = threading.Thread(target=run, daemon=True)
+        worker.start()
+        worker.join(timeout=10)
+        assert not worker.is_alive()
+        assert results == ["canned", "token_item", []]
+
     def test_client_creation_with_key(self, account_url, account_key):
         """Test creating a client with account key."""
         client = CosmosClient(account_url, credential=account_key)
         assert client is not None
 
+    def test_from_emulator_rejects_remote_endpoint(self):
+        """Test that the emulator constructor refuses non-local endpoints."""
+        with pytest.raises(ValueError, match="localhost"):
+            CosmosClient.from_emulator("https://myaccount.documents.azure.com:443/")
+
+    def test_from_emulator_accepts_loopback_endpoints(self):
+        """Test that the emulator constructor accepts local endpoints without connecting."""
+        assert CosmosClient.from_emulator() is not None
+        assert CosmosClient.from_emulator("https://127.0.0.1:8081") is not None
+
     def test_client_context_manager(self, account_url, account_key):
         """Test client as context manager."""
         with CosmosClient(account_url, credential=account_key) as client:
@@ -31,6 +109,34 @@ class TestCosmosClient:
         # Cleanup
         client.delete_database(test_database_id)
 
+    def test_create_database_populate_properties(self, client, test_database_id):
+        """Test returning server-assigned properties and the confirmed offer."""
+        database, properties = client.create_database(
+            test_database_id, offer_throughput=400, populate_properties=True
+        )
+        try:
+            assert database.id == test_database_id
+            assert properties["id"] == test_database_id
+            assert properties["_rid"]
+            assert properties["_etag"]
+            assert properties["offer_throughput"] == 400
+        finally:
+            client.delete_database(test_database_id)
+
+    def test_list_offers(self, client, test_database_id, test_container_id):
+        """Test listing the database offer; containers sharing it have none of their own."""
+        database = client.create_database(test_database_id, offer_throughput=400)
+        try:
+            database.create_container(test_container_id, {"paths": ["/id"], "kind": "Hash"})
+            
+            offers = database.list_offers()
+            assert offers == [
+                {"resource": "database", "id": test_database_id, "mode": "manual", "throughput": 400}
+            ]
+            assert sum(offer["throughput"] for offer in offers) == 400
+        finally:
+            client.delete_database(test_database_id)
+
     def test_create_duplicate_database_raises_error(self, client, test_database_id):
         """Test that creating a duplicate database raises an error."""
         client.create_database(test_database_id)
@@ -82,6 +188,330 @@ class TestCosmosClient:
         finally:
             client.delete_database(test_database_id)
 
+    def test_list_databases_returns_properties(self, client, test_database_id):
+        """Test that listed databases carry their plain id and system properties."""
+        client.create_database(test_database_id)
+        
+        try:
+            matches = [db for db in client.list_databases() if db["id"] == test_database_id]
+            assert len(matches) == 1
+            assert matches[0]["_rid"]
+            assert matches[0]["_self"].startswith("dbs/")
+        finally:
+            client.delete_database(test_database_id)
+
+    def test_get_last_requests(self, account_url, account_key):
+        """Test that captured requests are bounded and redact credentials."""
+        client = CosmosClient(account_url, credential=account_key, capture_requests=2)
+        for _ in range(3):
+            client.list_databases()
+        
+        requests = client.get_last_requests()
+        assert len(requests) == 2
+        assert requests[-1]["method"] == "POST"
+        assert requests[-1]["headers"]["authorization"] == "<redacted>"
+        assert len(client.get_last_requests(1)) == 1
+
+    def test_get_last_requests_captures_retries(self, document_endpoint):
+        """Test that each attempt of a request retried after 410 Gone is captured."""
+        url, credential, documents = document_endpoint
+        (documents / "captured_retry.json").write_text('{"id": "captured_retry"}')
+        (documents / "captured_retry.gone").write_text("1")
+        client = CosmosClient(url, credential=credential, capture_requests=10)
+        container = client.get_database_client("canned").get_container_client("documents")
+        
+        assert container.read_item(item="captured_retry", partition_key="captured_retry")["id"] == "captured_retry"
+        reads = [r for r in client.get_last_requests() if r["url"].endswith("/docs/captured_retry")]
+        assert len(reads) == 2
+
+    def test_item_keys_written_sorted(self, account_url, account_key, database, container):
+        """Test that dicts with differently-ordered keys are written as identical bytes."""
+        client = CosmosClient(account_url, credential=account_key, capture_requests=2)
+        recorded_container = client.get_database_client(database.id).get_container_client(container.id)
+        recorded_container.upsert_item(body={"id": "sorted_keys", "b": {"y": 1, "x": 2}, "a": 1})
+        recorded_container.upsert_item(body={"a": 1, "id": "sorted_keys", "b": {"x": 2, "y": 1}})
+        
+        first, second = client.get_last_requests()
+        assert first["body"] == second["body"]
+        assert first["body"].index('"a"') < first["body"].index('"b"') < first["body"].index('"id"')
+
+    def test_nested_item_written_as_json_dumps(self, account_url, account_key, database, container):
+        """Test that deeply nested bodies are written as json.dumps would encode them."""
+        client = CosmosClient(account_url, credential=account_key, capture_requests=1)
+        recorded_container = client.get_database_client(database.id).get_container_client(container.id)
+        leaf = {"int": -3, "float": 0.5, "flag": True, "none": None, "text": "a \"quoted\" line\n"}
+        nested = leaf
+        for level in range(120):
+            nested = {"level": level, "child": nested} if level % 2 else [nested, level]
+        body = {"id": "deeply_nested", "nested": nested}
+        
+        recorded_container.upsert_item(body=body)
+        
+        (request,) = client.get_last_requests()
+        assert request["body"] == json.dumps(body, sort_keys=True, separators=(",", ":"))
+        stored = container.read_item(item="deeply_nested", partition_key="deeply_nested")
+        assert {key: value for key, value in stored.items() if not key.startswith("_")} == body
+
+    def test_item_nested_too_deeply_rejected(self, container):
+        """Test that a body nested deeper than the client can read back is rejected."""
+        nested = {}
+        for _ in range(200):
+            nested = {"child": nested}
+        
+        with pytest.raises(ValueError, match="deeper than 128 levels"):
+            container.create_item(body={"id": "too_deep", "nested": nested})
+
+    def test_get_metrics(self, account_url, account_key):
+        """Test that RU charge and request counts accumulate across operations."""
+        client = CosmosClient(account_url, credential=account_key, track_metrics=True)
+        client.list_databases()
+        client.list_databases()
+        
+        metrics = client.get_metrics()
+        assert metrics["request_count"] >= 2
+        assert metrics["requests_by_operation"]["query_databases"] >= 2
+        assert metrics["total_request_charge"] >= 0
+        assert metrics["throttled_requests"] == 0
+
+    def test_query_cache(self, account_url, account_key, database, container):
+        """Test that repeated queries are served from the cache until their TTL passes."""
+        container.create_item(body={"id": "cached_query"})
+        client = CosmosClient(
+            account_url, credential=account_key, track_metrics=True, enable_query_cache=True, query_cache_ttl=60
+        )
+        cached_container = client.get_database_client(database.id).get_container_client(container.id)
+        query = "SELECT * FROM c WHERE c.id = @id"
+        parameters = [{"name": "@id", "value": "cached_query"}]
+        
+        first = cached_container.query_items(query, parameters=parameters, partition_key="cached_query")
+        request_count = client.get_metrics()["request_count"]
+        container.upsert_item(body={"id": "cached_query", "changed": True})
+        second = cached_container.query_items(query, parameters=parameters, partition_key="cached_query")
+        
+        assert second == first
+        metrics = client.get_metrics()
+        assert metrics["request_count"] == request_count
+        assert metrics["query_cache_hits"] == 1
+        assert metrics["query_cache_misses"] == 1
+        
+        with pytest.raises(ValueError, match="query_cache_ttl"):
+            CosmosClient(account_url, credential=account_key, enable_query_cache=True, query_cache_ttl=0)
+
+    @pytest.fixture
+    def cached_container(self, document_endpoint):
+        """A container on the stand-in endpoint whose client caches query results."""
+        url, credential, _ = document_endpoint
+        client = CosmosClient(url, credential=credential, track_metrics=True, enable_query_cache=True)
+        return client, client.get_database_client("canned").get_container_client("documents")
+
+    def test_query_cache_key_includes_result_options(self, cached_container):
+        """Test that queries differing in result-changing kwargs are not served each other's results."""
+        client, container = cached_container
+        query = "SELECT * FROM c"
+        
+        container.query_items(query, partition_key="p")
+        container.query_items(query, partition_key="p", consistency_level="Eventual")
+        container.query_items(query, partition_key="p", enable_scan_in_query=True)
+        container.query_items(query, partition_key="q")
+        container.query_items(query, partition_key="p", consistency_level="Eventual", max_item_count=5)
+        
+        metrics = client.get_metrics()
+        assert metrics["query_cache_misses"] == 4
+        assert metrics["query_cache_hits"] == 1
+
+    def test_query_cache_is_bounded(self, cached_container):
+        """Test that the oldest cached result is evicted once the cache is full."""
+        client, container = cached_container
+        
+        for i in range(1025):
+            container.query_items(f"SELECT * FROM c WHERE c.n = {i}", partition_key="p")
+        container.query_items("SELECT * FROM c WHERE c.n = 1024", partition_key="p")
+        container.query_items("SELECT * FROM c WHERE c.n = 0", partition_key="p")
+        
+        metrics = client.get_metrics()
+        assert metrics["query_cache_hits"] == 1
+        assert metrics["query_cache_misses"] == 1026
+
+    def test_get_metrics_reports_query_cache_without_track_metrics(self, document_endpoint):
+        """Test that query cache stats are reported even when request totals are not tracked."""
+        url, credential, _ = document_endpoint
+        client = CosmosClient(url, credential=credential, enable_query_cache=True)
+        container = client.get_database_client("canned").get_container_client("documents")
+        
+        container.query_items("SELECT * FROM c", partition_key="p")
+        container.query_items("SELECT * FROM c", partition_key="p")
+        
+        metrics = client.get_metrics()
+        assert metrics == {"query_cache_hits": 1, "query_cache_misses": 1}
+
+    def test_get_metrics_disabled_by_default(self, client):
+        """Test that metrics must be enabled explicitly."""
+        with pytest.raises(ValueError):
+            client.get_metrics()
+
+    def test_get_last_requests_disabled_by_default(self, client):
+        """Test that request capture must be enabled explicitly."""
+        with pytest.raises(ValueError):
+            client.get_last_requests()
+
+    def test_circuit_breaker_state(self, account_url, account_key, client):
+        """Test that the circuit breaker starts closed and is absent unless enabled."""
+        assert client.circuit_breaker_state is None
+        
+        guarded_client = CosmosClient(account_url, credential=account_key, circuit_breaker_threshold=5)
+        assert guarded_client.circuit_breaker_state == "closed"
+        
+        with pytest.raises(ValueError, match="circuit_breaker_threshold"):
+            CosmosClient(account_url, credential=account_key, circuit_breaker_threshold=0)
+
+    @pytest.fixture
+    def guarded_container(self, document_endpoint):
+        """A container on the stand-in endpoint whose client trips after two 503 responses."""
+        url, credential, documents = document_endpoint
+        client = CosmosClient(
+            url,
+            credential=credential,
+            capture_requests=10,
+            circuit_breaker_threshold=2,
+            circuit_breaker_cool_down=0.5,
+        )
+        (documents / "guarded_item.json").write_text('{"id": "guarded_item"}')
+        (documents / "guarded_item.unavailable").write_text("2")
+        container = client.get_database_client("canned").get_container_client("documents")
+        for _ in range(2):
+            with pytest.raises(CosmosServiceUnavailableError):
+                container.read_item(item="guarded_item", partition_key="guarded_item")
+        yield client, container, documents
+        (documents / "guarded_item.unavailable").unlink(missing_ok=True)
+
+    def test_circuit_breaker_fails_fast_and_recovers(self, guarded_container):
+        """Test that an open breaker rejects requests unsent and closes after a good probe."""
+        client, container, _ = guarded_container
+        assert client.circuit_breaker_state == "open"
+        
+        with pytest.raises(CosmosServiceUnavailableError, match="circuit breaker is open"):
+            container.read_item(item="guarded_item", partition_key="guarded_item")
+        assert len(client.get_last_requests()) == 2
+        
+        time.sleep(0.6)
+        assert client.circuit_breaker_state == "half_open"
+        assert container.read_item(item="guarded_item", partition_key="guarded_item")["id"] == "guarded_item"
+        assert client.circuit_breaker_state == "closed"
+
+    def test_circuit_breaker_reopens_after_failed_probe(self, guarded_container):
+        """Test that a 503 answering the half-open probe opens the breaker again."""
+        client, container, documents = guarded_container
+        (documents / "guarded_item.unavailable").write_text("1")
+        
+        time.sleep(0.6)
+        with pytest.raises(CosmosServiceUnavailableError):
+            container.read_item(item="guarded_item", partition_key="guarded_item")
+        assert client.circuit_breaker_state == "open"
+
+    def test_read_only_client_rejects_mutations(self, account_url, account_key, database, container):
+        """Test that a read_only client reads but raises CosmosReadOnlyError on writes."""
+        container.create_item(body={"id": "read_only_item"})
+        read_only_client = CosmosClient(account_url, credential=account_key, read_only=True)
+        read_only_database = read_only_client.get_database_client(database.id)
+        read_only_container = read_only_database.get_container_client(container.id)
+        
+        assert read_only_container.read_item(item="read_only_item", partition_key="read_only_item")
+        with pytest.raises(CosmosReadOnlyError) as exc_info:
+            read_only_container.upsert_item(body={"id": "read_only_item"})
+        assert exc_info.value.operation == "upsert_item"
+        with pytest.raises(CosmosReadOnlyError):
+            read_only_container.delete_item(item="read_only_item", partition_key="read_only_item")
+        with pytest.raises(CosmosReadOnlyError):
+            read_only_client.create_database("read_only_database")
+        with pytest.raises(CosmosReadOnlyError):
+            read_only_database.delete()
+        
+        assert container.read_item(item="read_only_item", partition_key="read_only_item")
+
+    def test_max_response_size_bytes(self, account_url, account_key, database, container):
+        """Test that responses over max_response_size_bytes raise and smaller ones are read."""
+        container.create_item(body={"id": "large_item", "payload": "x" * 4096})
+        container.create_item(body={"id": "small_item"})
+        limited_client = CosmosClient(account_url, credential=account_key, max_response_size_bytes=2048)
+        limited_container = limited_client.get_database_client(database.id).get_container_client(container.id)
+        
+        assert limited_container.read_item(item="small_item", partition_key="small_item")["id"] == "small_item"
+        with pytest.raises(CosmosResponseTooLargeError, match="max_response_size_bytes"):
+            limited_container.read_item(item="large_item", partition_key="large_item")
+        
+        with pytest.raises(ValueError, match="max_response_size_bytes"):
+            CosmosClient(account_url, credential=account_key, max_response_size_bytes=0)
+
+    def test_max_response_size_bytes_counts_chunked_body(self, document_endpoint):
+        """Test that a chunked response without Content-Length is cut off at the limit."""
+        url, credential, documents = document_endpoint
+        (documents / "chunked_large.json").write_text(json.dumps({"id": "chunked_large", "payload": "x" * 8192}))
+        (documents / "chunked_large.chunked").touch()
+        (documents / "chunked_small.json").write_text(json.dumps({"id": "chunked_small"}))
+        (documents / "chunked_small.chunked").touch()
+        limited_client = CosmosClient(url, credential=credential, max_response_size_bytes=2048)
+        limited_container = limited_client.get_database_client("canned").get_container_client("documents")
+        
+        assert limited_container.read_item(item="chunked_small", partition_key="chunked_small")["id"] == "chunked_small"
+        with pytest.raises(CosmosResponseTooLargeError, match="max_response_size_bytes"):
+            limited_container.read_item(item="chunked_large", partition_key="chunked_large")
+
+    def test_in_flight_count_and_cancel_all(self, client, container):
+        """Test that idle clients report no operations and cancel_all spares later ones."""
+        assert client.in_flight_count() == 0
+        
+        client.cancel_all()
+        container.create_item(body={"id": "after_cancel_all"})
+        assert container.read_item(item="after_cancel_all", partition_key="after_cancel_all")["id"] == "after_cancel_all"
+        assert client.in_flight_count() == 0
+
+    def test_cancel_all_cancels_running_operation(self, document_endpoint):
+        """Test that a running database operation is counted and aborted by cancel_all."""
+        url, credential, documents = document_endpoint
+        client = CosmosClient(url, credential=credential)
+        (documents / "slow_database.delay").write_text("5")
+        errors = []
+        
+        def read():
+            try:
+                client.get_database_client("slow_database").read()
+            except BaseException as e:
+                errors.append(e)
+        
+        reader = threading.Thread(target=read)
+        reader.start()
+        deadline = time.monotonic() + 5
+        while client.in_flight_count() == 0 and time.monotonic() < deadline:
+            time.sleep(0.01)
+        assert client.in_flight_count() == 1
+        
+        client.cancel_all()
+        reader.join(timeout=5)
+        assert isinstance(errors[0], asyncio.CancelledError)
+        assert client.in_flight_count() == 0
+
+    def test_operation_timeout_bounds_database_operations(self, document_endpoint):
+        """Test that database operations are abandoned past the client's operation_timeout."""
+        url, credential, documents = document_endpoint
+        client = CosmosClient(url, credential=credential, operation_timeout=0.2)
+        (documents / "timed_database.delay").write_text("2")
+        
+        with pytest.raises(CosmosTimeoutError) as exc_info:
+            client.get_database_client("timed_database").read()
+        assert exc_info.value.operation == "read_database"
+        assert client.in_flight_count() == 0
+
+    def test_operation_timeout_out_of_range_rejected(self, document_endpoint):
+        """Test that a timeout too large to represent raises ValueError instead of panicking."""
+        url, credential, _ = document_endpoint
+        
+        with pytest.raises(ValueError, match="operation_timeout"):
+            CosmosClient(url, credential=credential, operation_timeout=1e30)
+        
+        client = CosmosClient(url, credential=credential)
+        with pytest.raises(ValueError, match="operation_timeout"):
+            client.get_database_client("canned").read(operation_timeout=1e30)
+
 
 class TestDatabaseProxy:
     """Test suite for DatabaseProxy."""
@@ -102,11 +532,54 @@ class TestDatabaseProxy:
         partition_key = {"paths": ["/id"], "kind": "Hash"}
         result = database.create_container(test_container_id, partition_key)
         assert result is not None
-        assert result.get("id") == test_container_id
+        assert result.id == test_container_id
         
         # Cleanup
         database.delete_container(test_container_id)
 
+    def test_create_container_with_path_ttl_and_throughput(self, database, test_container_id):
+        """Test creating a container from a path string with TTL and dedicated throughput."""
+        container = database.create_container(
+            test_container_id, "/category", default_ttl=3600, offer_throughput=400
+        )
+        
+        try:
+            container.create_item(body={"id": "c1", "category": "books"})
+            assert container.read_item(item="c1", partition_key="books")["category"] == "books"
+            properties = container.read()
+            assert properties["partitionKey"]["paths"] == ["/category"]
+            assert properties["defaultTtl"] == 3600
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_create_container_rejects_invalid_arguments(self, database, test_container_id):
+        """Test that malformed paths and TTLs are rejected before any request is sent."""
+        with pytest.raises(ValueError, match="must start with '/'"):
+            database.create_container(test_container_id, "category")
+        with pytest.raises(ValueError, match="default_ttl"):
+            database.create_container(test_container_id, "/category", default_ttl=0)
+        with pytest.raises(NotImplementedError, match="default_ttl=-1"):
+            database.create_container(test_container_id, "/category", default_ttl=-1)
+        with pytest.raises(TypeError):
+            database.create_container(test_container_id, 42)
+
+    def test_geospatial_config_round_trip(self, database, test_container_id):
+        """Test that the geospatial config is persisted and read back."""
+        partition_key = {"paths": ["/id"], "kind": "Hash"}
+        database.create_container(test_container_id, partition_key, geospatial_config="Geometry")
+        
+        try:
+            properties = database.get_container_client(test_container_id).read()
+            assert properties["geospatialConfig"]["type"] == "Geometry"
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_invalid_geospatial_config_raises(self, database, test_container_id):
+        """Test that unknown geospatial types are rejected."""
+        partition_key = {"paths": ["/id"], "kind": "Hash"}
+        with pytest.raises(ValueError):
+            database.create_container(test_container_id, partition_key, geospatial_config="Planar")
+
     def test_create_duplicate_container_raises_error(self, database, test_container_id):
         """Test that creating a duplicate container raises an error."""
         partition_key = {"paths": ["/id"], "kind": "Hash"}
@@ -130,6 +603,39 @@ class TestDatabaseProxy:
         finally:
             database.delete_container(test_container_id)
 
+    def test_get_container_client_is_cached(self, database, test_container_id):
+        """Test that repeated calls reuse the same underlying container client."""
+        first = database.get_container_client(test_container_id)
+        second = database.get_container_client(test_container_id)
+        assert first._client is second._client
+        
+        typed = database.get_container_client(test_container_id, partition_key_type="string")
+        assert typed._client is not first._client
+
+    def test_container_client_cache_evicts_least_recently_used(self, database):
+        """Test that the container client cache is bounded."""
+        first = database.get_container_client("cached_0")
+        recent = database.get_container_client("cached_1")
+        for i in range(2, 257):
+            database.get_container_client(f"cached_{i}")
+            # Keep cached_1 recently used
+            database.get_container_client("cached_1")
+        
+        assert database.get_container_client("cached_1")._client is recent._client
+        assert database.get_container_client("cached_0")._client is not first._client
+
+    def test_get_container_client_validate(self, database, test_container_id):
+        """Test that validate=True fails fast for a missing container."""
+        with pytest.raises(CosmosResourceNotFoundError):
+            database.get_container_client(test_container_id, validate=True)
+        
+        database.create_container(test_container_id, {"paths": ["/id"], "kind": "Hash"})
+        try:
+            container = database.get_container_client(test_container_id, validate=True)
+            assert container.id == test_container_id
+        finally:
+            database.delete_container(test_container_id)
+
     def test_delete_container(self, database, test_container_id):
         """Test deleting a container."""
         partition_key = {"paths": ["/id"], "kind": "Hash"}
@@ -156,6 +662,82 @@ class TestDatabaseProxy:
         finally:
             database.delete_container(test_container_id)
 
+    def test_query_containers(self, database, test_container_id):
+        """Test filtering containers with a parameterized query."""
+        database.create_container(test_container_id, "/category")
+        
+        try:
+            containers = database.query_containers(
+                "SELECT * FROM c WHERE c.id = @id",
+                parameters=[{"name": "@id", "value": test_container_id}],
+            )
+            assert [c["id"] for c in containers] == [test_container_id]
+            assert containers[0]["partitionKey"]["paths"] == ["/category"]
+            
+            assert database.query_containers("SELECT * FROM c WHERE c.id = 'no-such-container'") == []
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_typed_properties(self, account_url, account_key, database, test_container_id):
+        """Test that reads and lists return typed properties when opted in."""
+        typed_client = CosmosClient(account_url, credential=account_key, typed_properties=True)
+        typed_database = typed_client.get_database_client(database.id)
+        database.create_container(test_container_id, {"paths": ["/id"], "kind": "Hash"})
+        
+        try:
+            database_properties = typed_database.read()
+            assert isinstance(database_properties, DatabaseProperties)
+            assert database_properties.id == database.id
+            assert database_properties.etag
+            assert database_properties.as_dict()["id"] == database.id
+            
+            listed = [d for d in typed_client.list_databases() if d.id == database.id]
+            assert isinstance(listed[0], DatabaseProperties)
+            assert listed[0].etag == database_properties.etag
+            
+            properties = typed_database.get_container_client(test_container_id).read()
+            assert isinstance(properties, ContainerProperties)
+            assert properties.partition_key["paths"] == ["/id"]
+            assert properties.indexing_policy is not None
+            assert properties.default_ttl is None
+            assert properties.as_dict()["id"] == test_container_id
+            with pytest.raises(AttributeError):
+                properties.partition_keys
+            
+            assert test_container_id in [c.id for c in typed_database.list_containers()]
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_run_setup(self, database, test_container_id):
+        """Test running setup steps in order."""
+        steps = [
+            {"type": "create_container", "id": test_container_id, "partition_key": {"paths": ["/id"]}},
+            {"type": "create_item", "container": test_container_id, "body": {"id": "seed_1"}},
+            {"type": "upsert_item", "container": test_container_id, "body": {"id": "seed_2"}},
+        ]
+        
+        try:
+            assert database.run_setup(steps) == 3
+            container = database.get_container_client(test_container_id)
+            assert container.read_item(item="seed_2", partition_key="seed_2")["id"] == "seed_2"
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_run_setup_reports_failed_step(self, database, test_container_id):
+        """Test that a failing step is reported and earlier steps are kept."""
+        steps = [
+            {"type": "create_container", "id": test_container_id, "partition_key": {"paths": ["/id"]}},
+            {"type": "create_item", "container": test_container_id, "body": {"id": "dup"}},
+            {"type": "create_item", "container": test_container_id, "body": {"id": "dup"}},
+        ]
+        
+        try:
+            with pytest.raises(RuntimeError, match=r"Setup step 2 \(create_item\)") as exc_info:
+                database.run_setup(steps)
+            assert isinstance(exc_info.value.__cause__, CosmosResourceExistsError)
+        finally:
+            database.delete_container(test_container_id)
+
     def test_delete_database_through_proxy(self, client, test_database_id):
         """Test deleting a database through the database proxy."""
         client.create_database(test_database_id)
@@ -1,9 +1,18 @@
 """Tests for Container item operations."""
 
+import datetime
+import enum
+import time
+
 import pytest
+from azure.cosmos import CosmosClient
 from azure.cosmos.exceptions import (
+    CosmosAccessConditionFailedError,
+    CosmosHttpResponseError,
+    CosmosInvalidContinuationTokenError,
     CosmosResourceNotFoundError,
     CosmosResourceExistsError,
+    CosmosTimeoutError,
 )
 
 
@@ -34,6 +43,49 @@ class TestContainerItemOperations:
         assert result is not None
         assert result.get("id") == item["id"]
 
+    def test_mismatched_partition_key_is_sent_unchecked_by_default(self, container):
+        """Test that a partition key kwarg disagreeing with the body is left to the service."""
+        with pytest.raises(CosmosHttpResponseError):
+            container.create_item(body={"id": "test_item_pk"}, partition_key="other")
+
+    def test_mismatched_partition_key_raises_when_configured(
+        self, account_url, account_key, database, container
+    ):
+        """Test that partition_key_mismatch="raise" rejects the write before sending it."""
+        client = CosmosClient(account_url, credential=account_key, partition_key_mismatch="raise")
+        raise_container = client.get_database_client(database.id).get_container_client(container.id)
+        
+        with pytest.raises(ValueError, match="does not match"):
+            raise_container.create_item(body={"id": "test_item_pk"}, partition_key="other")
+
+    def test_mismatched_partition_key_warns_when_configured(
+        self, account_url, account_key, database, container
+    ):
+        """Test that partition_key_mismatch="warn" writes under the kwarg value."""
+        client = CosmosClient(account_url, credential=account_key, partition_key_mismatch="warn")
+        warn_container = client.get_database_client(database.id).get_container_client(container.id)
+        
+        with pytest.warns(UserWarning, match="does not match"):
+            with pytest.raises(CosmosHttpResponseError):
+                # The service rejects an item whose id differs from its /id partition key
+                warn_container.create_item(body={"id": "test_item_pk"}, partition_key="other")
+
+    def test_idempotent_create_returns_identical_item(self, container):
+        """Test that an idempotent create of an identical item succeeds."""
+        item = {"id": "test_item_idem", "value": 1}
+        container.create_item(body=dict(item))
+        
+        result = container.create_item(body=dict(item), idempotent=True)
+        assert result["value"] == 1
+        assert "_etag" in result
+
+    def test_idempotent_create_of_different_item_raises(self, container):
+        """Test that an idempotent create still raises when the stored item differs."""
+        container.create_item(body={"id": "test_item_idem2", "value": 1})
+        
+        with pytest.raises(CosmosResourceExistsError):
+            container.create_item(body={"id": "test_item_idem2", "value": 2}, idempotent=True)
+
     def test_create_duplicate_item_raises_error(self, container):
         """Test that creating a duplicate item raises an error."""
         item = {
@@ -62,11 +114,413 @@ class TestContainerItemOperations:
         assert result.get("name") == item["name"]
         assert result.get("value") == item["value"]
 
+    def test_read_item_with_fields(self, container):
+        """Test reading only a projection of an item's fields."""
+        item = {
+            "id": "test_item_fields",
+            "name": "Projection Read",
+            "value": 42,
+            "payload": {"nested": ["a", "b", "c"]}
+        }
+        
+        container.create_item(body=item)
+        
+        result = container.read_item(
+            item="test_item_fields",
+            partition_key="test_item_fields",
+            fields=["id", "value"]
+        )
+        assert result == {"id": "test_item_fields", "value": 42}
+
+    def test_read_item_with_etag(self, container):
+        """Test reading an item returns its etag alongside it."""
+        container.create_item(body={"id": "test_item_etag", "value": 1})
+        
+        item, etag = container.read_item_with_etag(item="test_item_etag", partition_key="test_item_etag")
+        assert item["value"] == 1
+        assert etag == item["_etag"]
+        
+        projected, projected_etag = container.read_item_with_etag(
+            item="test_item_etag", partition_key="test_item_etag", fields=["id"]
+        )
+        assert "_etag" not in projected
+        assert projected_etag == etag
+
+    def test_conditional_writes_with_etag(self, container):
+        """Test that writes with a stale etag fail instead of overwriting newer changes."""
+        container.create_item(body={"id": "test_item_if_match", "value": 1})
+        item, stale_etag = container.read_item_with_etag(item="test_item_if_match", partition_key="test_item_if_match")
+        
+        item["value"] = 2
+        container.replace_item(item="test_item_if_match", body=item, etag=stale_etag)
+        
+        item["value"] = 3
+        with pytest.raises(CosmosAccessConditionFailedError):
+            container.replace_item(item="test_item_if_match", body=item, etag=stale_etag)
+        with pytest.raises(CosmosAccessConditionFailedError):
+            container.upsert_item(body=item, if_match=stale_etag)
+        with pytest.raises(CosmosAccessConditionFailedError):
+            container.delete_item(item="test_item_if_match", partition_key="test_item_if_match", etag=stale_etag)
+        assert container.read_item(item="test_item_if_match", partition_key="test_item_if_match")["value"] == 2
+        
+        _, current_etag = container.read_item_with_etag(item="test_item_if_match", partition_key="test_item_if_match")
+        container.upsert_item(body=item, if_match=current_etag)
+        _, current_etag = container.read_item_with_etag(item="test_item_if_match", partition_key="test_item_if_match")
+        container.delete_item(item="test_item_if_match", partition_key="test_item_if_match", etag=current_etag)
+
+    def test_read_item_etag_for_conditional_replace(self, container):
+        """Test that read_item returns an _etag usable for a conditional write."""
+        container.create_item(body={"id": "test_item_read_etag", "value": 1})
+        
+        item = container.read_item(item="test_item_read_etag", partition_key="test_item_read_etag")
+        _, etag = container.read_item_with_etag(item="test_item_read_etag", partition_key="test_item_read_etag")
+        assert item["_etag"] == etag
+        item["value"] = 2
+        container.replace_item(item="test_item_read_etag", body=item, etag=item["_etag"])
+        
+        projected = container.read_item(
+            item="test_item_read_etag", partition_key="test_item_read_etag", fields=["id"], include_etag=True
+        )
+        assert set(projected) == {"id", "_etag"}
+        assert projected["_etag"] != etag
+
+    def test_etag_and_if_match_are_exclusive(self, container):
+        """Test that passing both etag keywords is rejected."""
+        with pytest.raises(ValueError, match="etag or if_match"):
+            container.delete_item(item="x", partition_key="x", etag="a", if_match="b")
+
+    def test_read_item_bytes(self, container):
+        """Test reading an item as the raw response body."""
+        import json
+        container.create_item(body={"id": "test_item_bytes", "value": 1})
+        
+        raw = container.read_item_bytes(item="test_item_bytes", partition_key="test_item_bytes")
+        assert isinstance(raw, bytes)
+        document = json.loads(raw)
+        assert document["value"] == 1
+        assert "_etag" in document
+
+    def test_read_item_with_json_path(self, container):
+        """Test reading only a subtree of an item."""
+        container.create_item(body={
+            "id": "test_item_json_path",
+            "address": {"lines": ["1 Main St", "Apt 2"], "display name": "Home"},
+        })
+        
+        read = lambda path, **kwargs: container.read_item(
+            item="test_item_json_path", partition_key="test_item_json_path", json_path=path, **kwargs
+        )
+        assert read("address.lines") == ["1 Main St", "Apt 2"]
+        assert read("$.address.lines[1]") == "Apt 2"
+        assert read("address['display name']") == "Home"
+        
+        with pytest.raises(KeyError):
+            read("address.zip")
+        assert read("address.lines[5]", json_path_missing_ok=True) is None
+        
+        with pytest.raises(ValueError):
+            read("address[")
+
+    def test_read_item_with_uuid_partition_key(self, container):
+        """Test that a uuid.UUID partition key matches its string form."""
+        import uuid
+        
+        item_id = uuid.uuid4()
+        container.create_item(body={"id": str(item_id)})
+        
+        result = container.read_item(item=str(item_id), partition_key=item_id)
+        assert result["id"] == str(item_id)
+
+    def test_read_item_with_parse_timestamps(self, container):
+        """Test that _ts is exposed as a UTC datetime when requested."""
+        from datetime import datetime, timezone
+        
+        container.create_item(body={"id": "test_item_ts"})
+        
+        result = container.read_item(item="test_item_ts", partition_key="test_item_ts")
+        assert "_ts_datetime" not in result
+        
+        result = container.read_item(
+            item="test_item_ts", partition_key="test_item_ts", parse_timestamps=True
+        )
+        assert result["_ts_datetime"] == datetime.fromtimestamp(result["_ts"], timezone.utc)
+
+    def test_consistency_level(self, container):
+        """Test that consistency_level is validated, relaxes reads and is ignored on writes."""
+        container.create_item(body={"id": "test_item_consistency"}, consistency_level="Strong")
+        
+        result = container.read_item(
+            item="test_item_consistency", partition_key="test_item_consistency",
+            consistency_level="eventual"
+        )
+        assert result["id"] == "test_item_consistency"
+        
+        with pytest.raises(ValueError):
+            container.read_item(
+                item="test_item_consistency", partition_key="test_item_consistency",
+                consistency_level="quorum"
+            )
+
+    def test_read_item_fallback_consistency(self, container):
+        """Test that a read past fallback_timeout is retried and flagged as degraded."""
+        container.create_item(body={"id": "test_item_fallback"})
+        diagnostics = []
+        
+        result = container.read_item(
+            item="test_item_fallback", partition_key="test_item_fallback",
+            fallback_consistency="Eventual", fallback_timeout=1e-6,
+            response_hook=diagnostics.append,
+        )
+        assert result["id"] == "test_item_fallback"
+        assert diagnostics[-1]["degraded"] is True
+        
+        container.read_item(
+            item="test_item_fallback", partition_key="test_item_fallback",
+            fallback_consistency="Eventual", response_hook=diagnostics.append,
+        )
+        assert diagnostics[-1]["degraded"] is False
+
+    def test_operation_timeout(self, container):
+        """Test that an operation exceeding operation_timeout raises CosmosTimeoutError."""
+        container.create_item(body={"id": "test_item_timeout"})
+        
+        with pytest.raises(CosmosTimeoutError) as exc_info:
+            container.read_item(
+                item="test_item_timeout", partition_key="test_item_timeout", operation_timeout=1e-6
+            )
+        assert exc_info.value.operation == "read_item"
+        assert isinstance(exc_info.value, TimeoutError)
+        
+        with pytest.raises(ValueError):
+            container.read_item(item="test_item_timeout", partition_key="test_item_timeout", operation_timeout=0)
+
+    def test_patch_item(self, container):
+        """Test that add, set, replace, remove and incr operations are applied in one patch."""
+        container.create_item(body={"id": "patch_test", "tags": ["a"], "count": 1, "name": "x", "old": True})
+        
+        result = container.patch_item("patch_test", "patch_test", [
+            {"op": "add", "path": "/tags/0", "value": "first"},
+            {"op": "set", "path": "/color", "value": "blue"},
+            {"op": "replace", "path": "/name", "value": "y"},
+            {"op": "remove", "path": "/old"},
+            {"op": "incr", "path": "/count", "value": 2},
+        ])
+        assert result["tags"] == ["first", "a"]
+        assert result["color"] == "blue"
+        assert result["name"] == "y"
+        assert "old" not in result
+        assert result["count"] == 3
+        assert container.read_item(item="patch_test", partition_key="patch_test")["count"] == 3
+
+    def test_patch_item_rejects_unknown_op(self, container):
+        """Test that an unknown op is rejected by name before the patch is sent."""
+        container.create_item(body={"id": "patch_unknown_op", "count": 1})
+        
+        with pytest.raises(ValueError, match="'multiply'"):
+            container.patch_item("patch_unknown_op", "patch_unknown_op", [
+                {"op": "incr", "path": "/count", "value": 1},
+                {"op": "multiply", "path": "/count", "value": 2},
+            ])
+        assert container.read_item(item="patch_unknown_op", partition_key="patch_unknown_op")["count"] == 1
+
+    def test_patch_item_append_unique(self, container):
+        """Test appending to an array only when the value is absent."""
+        container.create_item(body={"id": "append_unique", "tags": ["a"], "name": "x"})
+        
+        result = container.patch_item_append_unique("append_unique", "append_unique", "/tags", "b")
+        assert result["tags"] == ["a", "b"]
+        
+        result = container.patch_item_append_unique("append_unique", "append_unique", "/tags", "a")
+        assert result["tags"] == ["a", "b"]
+        
+        with pytest.raises(ValueError):
+            container.patch_item_append_unique("append_unique", "append_unique", "/name", "y")
+
+    def test_response_hook_receives_diagnostics(self, container):
+        """Test that the response hook is called with the response diagnostics."""
+        container.create_item(body={"id": "test_item_hook"})
+        
+        calls = []
+        container.read_item(
+            item="test_item_hook", partition_key="test_item_hook", response_hook=calls.append
+        )
+        
+        assert len(calls) == 1
+        assert calls[0]["status_code"] == 200
+        assert calls[0]["request_charge"] > 0
+        assert calls[0]["activity_id"]
+        assert "physical_partition_id" in calls[0]
+        assert "partition_key_range_id" in calls[0]
+
+    def test_enable_diagnostics_returns_tuple(self, container):
+        """Test that enable_diagnostics pairs each point operation's result with its diagnostics."""
+        item, diagnostics = container.create_item(body={"id": "diagnostics_item"}, enable_diagnostics=True)
+        assert item["id"] == "diagnostics_item"
+        assert diagnostics["status_code"] == 201
+        assert diagnostics["request_charge"] > 0
+        assert diagnostics["activity_id"]
+        
+        item, diagnostics = container.read_item(
+            item="diagnostics_item", partition_key="diagnostics_item", enable_diagnostics=True
+        )
+        assert item["id"] == "diagnostics_item"
+        assert diagnostics["status_code"] == 200
+        
+        _, diagnostics = container.upsert_item(body={"id": "diagnostics_item", "v": 1}, enable_diagnostics=True)
+        assert diagnostics["status_code"] == 200
+        _, diagnostics = container.replace_item(
+            item="diagnostics_item", body={"id": "diagnostics_item", "v": 2}, enable_diagnostics=True
+        )
+        assert diagnostics["status_code"] == 200
+        
+        result, diagnostics = container.delete_item(
+            item="diagnostics_item", partition_key="diagnostics_item", enable_diagnostics=True
+        )
+        assert result is None
+        assert diagnostics["status_code"] == 204
+        
+        assert isinstance(container.create_item(body={"id": "no_diagnostics"}), dict)
+
     def test_read_nonexistent_item_raises_error(self, container):
         """Test that reading a nonexistent item raises an error."""
         with pytest.raises(CosmosResourceNotFoundError):
             container.read_item(item="nonexistent_item", partition_key="nonexistent")
 
+    def test_read_after_write(self, container):
+        """Test that read_after_write reads a fresh item and still raises 404 after retrying."""
+        container.create_item(body={"id": "read_after_write"})
+        item = container.read_item(
+            item="read_after_write", partition_key="read_after_write", read_after_write=True
+        )
+        assert item["id"] == "read_after_write"
+        
+        started = time.monotonic()
+        with pytest.raises(CosmosResourceNotFoundError):
+            container.read_item(item="nonexistent_item", partition_key="nonexistent", read_after_write=True)
+        assert time.monotonic() - started >= 0.35
+
+    def test_exclude_none_on_write(self, account_url, account_key, database, container):
+        """Test that None values are not persisted when exclude_none_on_write is set."""
+        sparse_client = CosmosClient(account_url, credential=account_key, exclude_none_on_write=True)
+        sparse_container = sparse_client.get_database_client(database.id).get_container_client(container.id)
+        
+        sparse_container.create_item(body={"id": "test_item_sparse", "a": 1, "b": None})
+        
+        result = container.read_item(
+            item="test_item_sparse",
+            partition_key="test_item_sparse",
+            fields=["a", "b"]
+        )
+        assert result == {"a": 1}
+
+    def test_id_field_maps_to_id(self, account_url, account_key, database, container):
+        """Test that id_field is written as id and read back under its own name."""
+        mongo_client = CosmosClient(account_url, credential=account_key, id_field="_id")
+        mongo_container = mongo_client.get_database_client(database.id).get_container_client(container.id)
+        
+        mongo_container.create_item(body={"_id": "test_item_id_field", "value": 1})
+        
+        stored = container.read_item(item="test_item_id_field", partition_key="test_item_id_field")
+        assert stored["id"] == "test_item_id_field"
+        assert "_id" not in stored
+        
+        result = mongo_container.read_item(item="test_item_id_field", partition_key="test_item_id_field")
+        assert result["_id"] == "test_item_id_field"
+        assert "id" not in result
+
+    def test_id_field_cannot_be_id(self, account_url, account_key):
+        """Test that id_field must name a property other than id."""
+        with pytest.raises(ValueError, match="id_field"):
+            CosmosClient(account_url, credential=account_key, id_field="id")
+
+    def test_invalid_throughput_bucket_raises_error(self, container):
+        """Test that throughput buckets outside the allowed range are rejected."""
+        with pytest.raises(ValueError, match="throughput_bucket"):
+            container.create_item(body={"id": "test_item_bucket"}, throughput_bucket=6)
+
+    def test_create_item_with_tuples_and_sets(self, container):
+        """Test that tuples and sets are stored as arrays."""
+        item = {
+            "id": "test_item_collections",
+            "point": (1, 2),
+            "tags": {"b", "c", "a"},
+            "nested": {"pairs": ((1, "x"), (2, "y")), "frozen": frozenset([3, 1, 2])}
+        }
+        
+        container.create_item(body=item)
+        
+        result = container.read_item(item="test_item_collections", partition_key="test_item_collections")
+        assert result["point"] == [1, 2]
+        assert result["tags"] == ["a", "b", "c"]
+        assert result["nested"]["pairs"] == [[1, "x"], [2, "y"]]
+        assert result["nested"]["frozen"] == [1, 2, 3]
+
+    def test_create_item_with_enums(self, container):
+        """Test that Enum members are stored as their values."""
+        class Priority(enum.IntEnum):
+            LOW = 1
+            HIGH = 2
+        
+        class Status(str, enum.Enum):
+            OPEN = "open"
+        
+        class Shape(enum.Enum):
+            SQUARE = (2, 2)
+        
+        item = {
+            "id": "test_item_enums",
+            "priority": Priority.HIGH,
+            "status": Status.OPEN,
+            "shape": Shape.SQUARE,
+            "counts": {Priority.LOW: 3},
+        }
+        
+        container.create_item(body=item)
+        
+        result = container.read_item(item="test_item_enums", partition_key="test_item_enums")
+        assert result["priority"] == 2
+        assert result["status"] == "open"
+        assert result["shape"] == [2, 2]
+        assert result["counts"] == {"1": 3}
+
+    def test_create_item_server_timestamp(self, container):
+        """Test that server_timestamp_field returns the server's _ts without storing the field."""
+        body = {"id": "test_item_server_ts"}
+        result = container.create_item(body=body, server_timestamp_field="modified", parse_timestamps=True)
+        
+        stored = container.read_item(item="test_item_server_ts", partition_key="test_item_server_ts")
+        assert result["modified"] == result["_ts"] == stored["_ts"]
+        assert result["_ts_datetime"].timestamp() == stored["_ts"]
+        assert "modified" not in stored
+        assert "modified" not in body
+
+    def test_writes_without_content_response(self, container):
+        """Test that enable_content_response_on_write=False returns None but still writes."""
+        item_id = "test_item_no_content"
+        assert container.create_item(
+            body={"id": item_id, "value": 1}, enable_content_response_on_write=False
+        ) is None
+        assert container.upsert_item(
+            body={"id": item_id, "value": 2}, enable_content_response_on_write=False
+        ) is None
+        assert container.replace_item(
+            item=item_id, body={"id": item_id, "value": 3}, enable_content_response_on_write=False
+        ) is None
+        assert container.read_item(item=item_id, partition_key=item_id)["value"] == 3
+
+        with pytest.raises(ValueError, match="enable_content_response_on_write"):
+            container.upsert_item(
+                body={"id": item_id}, server_timestamp=True, enable_content_response_on_write=False
+            )
+
+    @pytest.mark.parametrize("item_id", ["a/b", "a\\b", "a#b", "a?b", "trailing "])
+    def test_forbidden_id_rejected_before_write(self, container, item_id):
+        """Test that ids the service forbids raise ValueError client-side."""
+        with pytest.raises(ValueError, match="Item id"):
+            container.create_item(body={"id": item_id}, partition_key=item_id)
+        with pytest.raises(ValueError, match="Item id"):
+            container.upsert_item(body={"id": item_id}, partition_key=item_id)
+
     def test_upsert_item_create(self, container):
         """Test upserting a new item (create)."""
         item = {
@@ -150,6 +604,31 @@ class TestContainerItemOperations:
         with pytest.raises(CosmosResourceNotFoundError):
             container.delete_item(item="nonexistent", partition_key="nonexistent")
 
+    def test_results_keep_json_types(self, container):
+        """Test that read and query results have the types json.loads would give them."""
+        body = {
+            "id": "json_types",
+            "int": 7,
+            "big": 2**63 + 1,
+            "float": 1.0,
+            "negative": -2.5,
+            "flag": False,
+            "missing": None,
+            "nested": {"list": [1, "two", [3.5, None]], "empty": {}},
+        }
+        container.create_item(body=body)
+        
+        item = container.read_item(item="json_types", partition_key="json_types")
+        results = container.query_items("SELECT * FROM c WHERE c.id = 'json_types'")
+        for result in (item, results[0]):
+            assert {key: result[key] for key in body} == body
+            assert type(result["int"]) is int and type(result["big"]) is int
+            assert type(result["float"]) is float
+            assert type(result["flag"]) is bool
+        
+        values = container.query_items("SELECT VALUE c.nested.list FROM c WHERE c.id = 'json_types'")
+        assert values == [[1, "two", [3.5, None]]]
+
     def test_query_items(self, container):
         """Test querying items."""
         # Create multiple items
@@ -186,6 +665,16 @@ class TestContainerItemOperations:
             if "value" in result:
                 assert result["value"] > 5
 
+    def test_query_items_cross_partition(self, container):
+        """Test that a query without partition_key collects results from every partition."""
+        for i in range(4):
+            container.create_item(body={"id": f"cross_partition_{i}", "status": "active" if i % 2 else "idle"})
+        
+        results = container.query_items("SELECT * FROM c WHERE c.status = 'active'")
+        assert sorted(r["id"] for r in results) == ["cross_partition_1", "cross_partition_3"]
+        
+        assert container.query_items("SELECT * FROM c WHERE c.status = 'missing'") == []
+
     def test_query_items_with_projection(self, container):
         """Test querying items with field projection."""
         item = {
@@ -204,6 +693,453 @@ class TestContainerItemOperations:
         assert isinstance(results, list)
         assert len(results) >= 1
 
+    def test_query_items_columns(self, container):
+        """Test columnar query results union heterogeneous rows' properties."""
+        container.create_item(body={"id": "columns_test", "rows": [{"a": 1}, {"a": 2, "b": "x"}, {"b": "y"}]})
+        
+        columns = container.query_items_columns(
+            "SELECT r.a, r.b FROM c JOIN r IN c.rows", partition_key="columns_test"
+        )
+        assert columns == {"a": [1, 2, None], "b": [None, "x", "y"]}
+        
+        with pytest.raises(ValueError, match="not a JSON object"):
+            container.query_items_columns("SELECT VALUE c.id FROM c", partition_key="columns_test")
+
+    def test_query_items_columns_applies_read_options(self, container):
+        """Test that schema coercion and parse_timestamps apply to each row before pivoting."""
+        container.create_item(body={"id": "columns_options_test", "count": "3"})
+        
+        columns = container.query_items_columns(
+            "SELECT c.id, c.count, c._ts FROM c", partition_key="columns_options_test",
+            schema={"count": int}, parse_timestamps=True,
+        )
+        assert columns["count"] == [3]
+        assert isinstance(columns["_ts_datetime"][0], datetime.datetime)
+
+    def test_query_items_with_parameters(self, container):
+        """Test binding query parameters."""
+        for i in range(3):
+            container.create_item(body={"id": f"param_item_{i}", "value": i})
+        
+        results = container.query_items(
+            query="SELECT * FROM c WHERE c.id = @id",
+            parameters=[{"name": "@id", "value": "param_item_1"}],
+            partition_key="param_item_1",
+        )
+        assert [item["value"] for item in results] == [1]
+
+    def test_query_parameter_value_types(self, container):
+        """Test that str, int, float, bool and None parameters bind as their JSON types."""
+        container.create_item(body={
+            "id": "param_types", "name": "x", "count": 3, "ratio": 0.5, "active": True, "missing": None
+        })
+        
+        results = container.query_items(
+            query="SELECT * FROM c WHERE c.name = @name AND c.count = @count AND c.ratio = @ratio "
+                  "AND c.active = @active AND c.missing = @missing",
+            parameters=[
+                {"name": "@name", "value": "x"},
+                {"name": "@count", "value": 3},
+                {"name": "@ratio", "value": 0.5},
+                {"name": "@active", "value": True},
+                {"name": "@missing", "value": None},
+            ],
+            partition_key="param_types",
+        )
+        assert [item["id"] for item in results] == ["param_types"]
+        
+        injected = container.query_items(
+            query="SELECT * FROM c WHERE c.name = @name",
+            parameters=[{"name": "@name", "value": "x' OR '1'='1"}],
+            partition_key="param_types",
+        )
+        assert injected == []
+
+    def test_query_parameter_errors(self, container):
+        """Test that malformed and unbound parameters raise errors rather than crashing."""
+        with pytest.raises(ValueError, match="Query parameter 0"):
+            container.query_items(
+                query="SELECT * FROM c WHERE c.id = @id", parameters=[{"value": "x"}], partition_key="x"
+            )
+        with pytest.raises(CosmosHttpResponseError):
+            container.query_items(query="SELECT * FROM c WHERE c.id = @unbound", parameters=[], partition_key="x")
+
+    def test_partition_key_scopes_query_regardless_of_where(self, container):
+        """Test that the partition_key kwarg scopes a query whose WHERE names another key."""
+        container.create_item(body={"id": "scope_a"})
+        container.create_item(body={"id": "scope_b"})
+        
+        results = container.query_items(
+            query="SELECT * FROM c WHERE c.id = 'scope_b'", partition_key="scope_a"
+        )
+        assert results == []
+
+    def test_strict_partition_warns_on_conflicting_predicate(self, container):
+        """Test that strict_partition warns about a conflicting partition key predicate."""
+        container.create_item(body={"id": "strict_a"})
+        
+        with pytest.warns(UserWarning, match="scopes it to another partition"):
+            container.query_items(
+                query="SELECT * FROM c WHERE c.id = @id",
+                parameters=[{"name": "@id", "value": "strict_b"}],
+                partition_key="strict_a",
+                strict_partition=True,
+            )
+
+    def test_strict_partition_accepts_matching_predicate(self, container):
+        """Test that a predicate matching the partition key does not warn."""
+        import warnings
+        
+        container.create_item(body={"id": "strict_c"})
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            results = container.query_items(
+                query="SELECT * FROM c WHERE c.id = 'strict_c'",
+                partition_key="strict_c",
+                strict_partition=True,
+            )
+        assert len(results) == 1
+
+    def test_query_builder(self, container):
+        """Test building a query with QueryBuilder."""
+        from azure.cosmos import QueryBuilder
+        
+        builder = (
+            QueryBuilder("c")
+            .select("c.id", "c.value")
+            .where("c.value >= @min", min=1)
+            .order_by("c.value", descending=True)
+            .limit(10)
+        )
+        assert builder.query == (
+            "SELECT c.id, c.value FROM c WHERE (c.value >= @min) ORDER BY c.value DESC OFFSET 0 LIMIT 10"
+        )
+        assert builder.parameters == [{"name": "@min", "value": 1}]
+        
+        container.create_item(body={"id": "builder_item", "value": 2})
+        results = container.query_items(builder, partition_key="builder_item")
+        assert results == [{"id": "builder_item", "value": 2}]
+
+    def test_partition_key_delete_status(self, container):
+        """Test that the status counts the items left in the partition."""
+        container.create_item(body={"id": "delete_status"})
+        
+        assert container.partition_key_delete_status("delete_status") == {"pending": True, "remaining_items": 1}
+        container.delete_item(item="delete_status", partition_key="delete_status")
+        assert container.partition_key_delete_status("delete_status") == {"pending": False, "remaining_items": 0}
+
+    def test_query_items_partial(self, container):
+        """Test that partial aggregates are reported and merged across partitions."""
+        for i in range(4):
+            container.create_item(body={"id": f"partial_{i}", "group": "partial", "n": i})
+        
+        partials = []
+        total = container.query_items_partial(
+            "SELECT VALUE SUM(c.n) FROM c WHERE c.group = 'partial'", partials.append
+        )
+        assert total == 6
+        assert partials and partials[-1] == 6
+        
+        count = container.query_items_partial(
+            "SELECT VALUE COUNT(1) FROM c WHERE c.group = 'partial'", lambda value: None
+        )
+        assert count == 4
+        
+        with pytest.raises(ValueError, match="SELECT VALUE"):
+            container.query_items_partial("SELECT VALUE AVG(c.n) FROM c", lambda value: None)
+
+    def test_query_items_max_item_count(self, database, test_container_id):
+        """Test that max_item_count sets the page size while every page is collected."""
+        database.create_container(test_container_id, {"paths": ["/category"], "kind": "Hash"})
+        paged_container = database.get_container_client(test_container_id)
+        try:
+            for i in range(5):
+                paged_container.create_item(body={"id": f"page_size_{i}", "category": "a"})
+            
+            items = paged_container.query_items("SELECT * FROM c", partition_key="a", max_item_count=2)
+            assert sorted(item["id"] for item in items) == [f"page_size_{i}" for i in range(5)]
+            
+            with pytest.raises(ValueError, match="max_item_count"):
+                paged_container.query_items("SELECT * FROM c", partition_key="a", max_item_count=0)
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_query_items_single_page(self, database, test_container_id):
+        """Test that single_page returns the first page and a token to continue from."""
+        database.create_container(test_container_id, {"paths": ["/category"], "kind": "Hash"})
+        paged_container = database.get_container_client(test_container_id)
+        try:
+            for i in range(3):
+                paged_container.create_item(body={"id": f"single_page_{i}", "category": "a"})
+            
+            items, continuation = paged_container.query_items(
+                "SELECT * FROM c", partition_key="a", max_item_count=2, single_page=True
+            )
+            assert len(items) == 2
+            assert continuation is not None
+            
+            rest = paged_container.query_items_paged(
+                "SELECT * FROM c", max_item_count=2, continuation=continuation, partition_key="a"
+            )
+            assert sorted(item["id"] for item in items + rest.items) == [f"single_page_{i}" for i in range(3)]
+            assert rest.continuation_token is None
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_query_items_schema_coercion(self, container):
+        """Test that schema coerces listed fields and names the field on type drift."""
+        container.create_item(body={
+            "id": "test_item_schema", "count": "7", "price": 3, "active": "TRUE", "seen": "2024-01-02T03:04:05Z"
+        })
+        schema = {"count": int, "price": float, "active": bool, "seen": datetime.datetime}
+        
+        items = container.query_items(
+            "SELECT * FROM c", partition_key="test_item_schema", schema=schema
+        )
+        assert items[0]["count"] == 7
+        assert isinstance(items[0]["price"], float)
+        assert items[0]["active"] is True
+        assert items[0]["seen"] == datetime.datetime(2024, 1, 2, 3, 4, 5, tzinfo=datetime.timezone.utc)
+        
+        with pytest.raises(ValueError, match="'active'"):
+            container.query_items("SELECT * FROM c", partition_key="test_item_schema", schema={"active": int})
+
+    def test_query_builder_offset_requires_limit(self):
+        """Test that OFFSET without LIMIT is rejected."""
+        from azure.cosmos import QueryBuilder
+        
+        with pytest.raises(ValueError):
+            QueryBuilder().offset(5).query
+
+    def test_query_items_with_enable_scan(self, container):
+        """Test that queries accept the enable_scan_in_query directive."""
+        container.create_item(body={"id": "scan_test", "value": 7})
+        
+        results = container.query_items(
+            query="SELECT * FROM c WHERE c.value = 7",
+            partition_key="scan_test",
+            enable_scan_in_query=True
+        )
+        assert [r["id"] for r in results] == ["scan_test"]
+
+    def test_query_items_low_precision_order_by(self, container):
+        """Test that ORDER BY queries accept the low-precision directive and validate it."""
+        container.create_item(body={"id": "low_precision", "value": 1.5})
+        
+        results = container.query_items(
+            query="SELECT * FROM c ORDER BY c.value",
+            partition_key="low_precision",
+            enable_low_precision_order_by=True
+        )
+        assert [r["id"] for r in results] == ["low_precision"]
+        
+        with pytest.raises(ValueError, match="enable_low_precision_order_by"):
+            container.query_items(
+                query="SELECT * FROM c ORDER BY c.value",
+                partition_key="low_precision",
+                enable_low_precision_order_by="yes"
+            )
+
+    def test_query_items_paged_index_metrics(self, container):
+        """Test that index utilization is decoded onto the page."""
+        container.create_item(body={"id": "index_metrics_test", "value": 3})
+        
+        page = container.query_items_paged(
+            "SELECT * FROM c WHERE c.value = 3",
+            partition_key="index_metrics_test",
+            populate_index_metrics=True,
+        )
+        assert "UtilizedSingleIndexes" in page.index_metrics
+        assert page.composite_index_used is False
+        
+        # The default policy indexes every path, so no index is missing
+        page = container.query_items_paged(
+            "SELECT * FROM c WHERE c.value = 3", partition_key="index_metrics_test", require_index=True
+        )
+        assert [item["id"] for item in page.items] == ["index_metrics_test"]
+        
+        plain = container.query_items_paged("SELECT * FROM c", partition_key="index_metrics_test")
+        assert plain.index_metrics is None
+        assert plain.composite_index_used is None
+
+    def test_query_items_paged(self, container):
+        """Test paging through query results with continuation tokens."""
+        for i in range(5):
+            container.create_item(body={"id": f"paged_{i}", "value": i})
+        
+        ids = []
+        continuation = None
+        while True:
+            page = container.query_items_paged(
+                "SELECT * FROM c", max_item_count=2, continuation=continuation
+            )
+            assert len(page) <= 2
+            assert page.request_charge > 0
+            assert page.activity_id
+            ids.extend(item["id"] for item in page.items)
+            continuation = page.continuation_token
+            if continuation is None:
+                break
+        
+        assert sorted(ids) == [f"paged_{i}" for i in range(5)]
+
+    def test_query_items_paged_garbage_continuation(self, container):
+        """Test that an unusable continuation token raises CosmosInvalidContinuationTokenError."""
+        container.create_item(body={"id": "garbage_continuation"})
+        
+        with pytest.raises(CosmosInvalidContinuationTokenError, match="Restart the query"):
+            container.query_items_paged(
+                "SELECT * FROM c", partition_key="garbage_continuation", continuation="garbage-token"
+            )
+        with pytest.raises(CosmosInvalidContinuationTokenError):
+            container.query_items_paged("SELECT * FROM c", continuation="\x00")
+
+    def test_query_cursor_resume(self, container):
+        """Test resuming a query from a cursor persisted as a dict."""
+        import json
+        from azure.cosmos import QueryCursor
+        
+        for i in range(5):
+            container.create_item(body={"id": f"cursor_{i}", "value": i})
+        
+        query = "SELECT * FROM c WHERE STARTSWITH(c.id, @prefix)"
+        parameters = [{"name": "@prefix", "value": "cursor_"}]
+        page = container.query_items_paged(query, max_item_count=2, parameters=parameters)
+        ids = [item["id"] for item in page.items]
+        
+        page = page.cursor.resume()
+        ids.extend(item["id"] for item in page.items)
+        
+        while page.cursor is not None:
+            saved = json.dumps(page.cursor.to_dict())
+            cursor = QueryCursor.from_dict(json.loads(saved))
+            with pytest.raises(ValueError):
+                cursor.resume()
+            page = cursor.resume(container)
+            ids.extend(item["id"] for item in page.items)
+        
+        assert sorted(ids) == [f"cursor_{i}" for i in range(5)]
+
+    def test_read_all_items(self, container):
+        """Test reading every item with a bounded page size."""
+        for i in range(5):
+            container.create_item(body={"id": f"read_all_{i}"})
+        
+        items = container.read_all_items(max_item_count=2)
+        assert sorted(item["id"] for item in items) == [f"read_all_{i}" for i in range(5)]
+        
+        with pytest.raises(ValueError):
+            container.read_all_items(max_item_count=0)
+
+    def test_read_all_items_paged(self, container):
+        """Test reading the container one page at a time."""
+        for i in range(5):
+            container.create_item(body={"id": f"read_all_paged_{i}"})
+        
+        ids = []
+        continuation = None
+        while True:
+            page = container.read_all_items_paged(max_item_count=2, continuation=continuation)
+            assert len(page) <= 2
+            ids.extend(item["id"] for item in page.items)
+            continuation = page.continuation_token
+            if continuation is None:
+                break
+        
+        assert sorted(ids) == [f"read_all_paged_{i}" for i in range(5)]
+
+    def test_query_items_page_tuple(self, container):
+        """Test the tuple-returning paged query variant."""
+        container.create_item(body={"id": "page_tuple", "value": 1})
+        
+        items, continuation = container.query_items_page(
+            "SELECT * FROM c WHERE c.id = 'page_tuple'", partition_key="page_tuple"
+        )
+        assert [item["id"] for item in items] == ["page_tuple"]
+        assert continuation is None
+
+    def test_query_items_page_drains_with_continuation(self, container):
+        """Test that following continuation tokens visits every result once, then returns None."""
+        for i in range(5):
+            container.create_item(body={"id": f"page_drain_{i}", "kind": "page_drain"})
+        
+        seen = []
+        continuation = None
+        while True:
+            items, continuation = container.query_items_page(
+                "SELECT * FROM c WHERE c.kind = 'page_drain'", max_item_count=2, continuation=continuation
+            )
+            assert len(items) <= 2
+            seen.extend(item["id"] for item in items)
+            if continuation is None:
+                break
+        assert sorted(seen) == [f"page_drain_{i}" for i in range(5)]
+
+    def test_query_items_iter_fetches_pages_lazily(self, container):
+        """Test that the iterator requests a page only when the previous one is consumed."""
+        for i in range(5):
+            container.create_item(body={"id": f"iter_{i}", "kind": "iter"})
+        
+        pages = []
+        iterator = container.query_items_iter(
+            "SELECT * FROM c WHERE c.kind = @kind",
+            max_item_count=2,
+            parameters=[{"name": "@kind", "value": "iter"}],
+            response_hook=pages.append,
+        )
+        assert pages == []
+        
+        first = next(iterator)
+        assert len(pages) == 1
+        ids = [first["id"]] + [item["id"] for item in iterator]
+        assert sorted(ids) == [f"iter_{i}" for i in range(5)]
+        assert len(pages) >= 3
+        assert next(iterator, None) is None
+        
+        with pytest.raises(ValueError):
+            container.query_items_iter("SELECT * FROM c", max_item_count=0)
+
+    def test_query_items_foreach(self, container):
+        """Test streaming query results to a callback."""
+        for i in range(5):
+            container.create_item(body={"id": f"foreach_item_{i}", "value": i})
+        
+        seen = []
+        count = container.query_items_foreach("SELECT * FROM c", seen.append)
+        assert count == 5
+        assert sorted(item["value"] for item in seen) == list(range(5))
+
+    def test_query_items_foreach_callback_error_stops_scan(self, container):
+        """Test that an exception from the callback propagates and stops the scan."""
+        for i in range(3):
+            container.create_item(body={"id": f"foreach_err_{i}"})
+        
+        seen = []
+        
+        def callback(item):
+            seen.append(item)
+            raise RuntimeError("sink full")
+        
+        with pytest.raises(RuntimeError, match="sink full"):
+            container.query_items_foreach("SELECT * FROM c", callback)
+        assert len(seen) == 1
+
+    def test_read_items_by_ids(self, container):
+        """Test reading several items by id with a single IN query."""
+        for i in range(5):
+            container.create_item(body={"id": f"by_ids_{i}", "value": i})
+        
+        results = container.read_items_by_ids(["by_ids_0", "by_ids_2", "by_ids_4", "missing"])
+        assert sorted(r["id"] for r in results) == ["by_ids_0", "by_ids_2", "by_ids_4"]
+
+    def test_read_items_by_ids_parameters(self, canned_documents):
+        """Test that extra parameters are accepted but may not reuse the id placeholders."""
+        container, _ = canned_documents
+        
+        assert container.read_items_by_ids(["a", "b"], parameters=[{"name": "@tenant", "value": "t1"}]) == []
+        with pytest.raises(ValueError, match="@id1"):
+            container.read_items_by_ids(["a", "b"], parameters=[{"name": "@id1", "value": "c"}])
+
     def test_multiple_items_operations(self, container):
         """Test creating, reading, updating, and deleting multiple items."""
         # Create multiple items
@@ -239,6 +1175,275 @@ class TestContainerItemOperations:
             container.delete_item(item=f"multi_{i}", partition_key=f"multi_{i}")
 
 
+class TestGoneRetries:
+    """Test suite for retries of 410 Gone responses during partition splits."""
+
+    def test_read_succeeds_after_partition_split(self, canned_documents):
+        """Test that reads answered 410 Gone with a split sub-status are retried."""
+        container, documents = canned_documents
+        (documents / "split_item.json").write_text('{"id": "split_item"}')
+        (documents / "split_item.gone").write_text("3")
+        
+        assert container.read_item(item="split_item", partition_key="split_item")["id"] == "split_item"
+
+    def test_gone_raised_after_retries_exhausted(self, canned_documents):
+        """Test that a 410 Gone outlasting the retries is raised."""
+        container, documents = canned_documents
+        (documents / "gone_item.json").write_text('{"id": "gone_item"}')
+        (documents / "gone_item.gone").write_text("4")
+        
+        with pytest.raises(CosmosHttpResponseError) as exc_info:
+            container.read_item(item="gone_item", partition_key="gone_item")
+        assert exc_info.value.status_code == 410
+
+
+class TestQueryOnlyHeaders:
+    """Test suite for query directives sent as headers, checked against a stand-in endpoint."""
+
+    def test_low_precision_order_by_sent_only_on_queries(self, document_endpoint):
+        """Test that enable_low_precision_order_by sets its header on queries but not reads."""
+        url, credential, documents = document_endpoint
+        client = CosmosClient(url, credential=credential, capture_requests=2)
+        container = client.get_database_client("canned").get_container_client("documents")
+        (documents / "low_precision.json").write_text('{"id": "low_precision"}')
+        header = "x-ms-documentdb-query-enable-low-precision-order-by"
+        
+        container.query_items(
+            "SELECT * FROM c ORDER BY c.value", partition_key="low_precision", enable_low_precision_order_by=True
+        )
+        container.read_item(item="low_precision", partition_key="low_precision", enable_low_precision_order_by=True)
+        
+        query, read = client.get_last_requests()
+        assert query["headers"][header] == "true"
+        assert header not in read["headers"]
+
+
+class TestInvalidContinuation:
+    """Test suite for continuation tokens rejected by a stand-in endpoint."""
+
+    def test_rejected_continuation_raises_invalid_token_error(self, canned_documents):
+        """Test that a 400 answering a resumed query is reported as an invalid token."""
+        container, _ = canned_documents
+        
+        assert container.query_items_paged("SELECT * FROM c", partition_key="p").items == []
+        with pytest.raises(CosmosInvalidContinuationTokenError) as exc_info:
+            container.query_items_paged("SELECT * FROM c", partition_key="p", continuation="stale-token")
+        assert exc_info.value.__cause__.status_code == 400
+
+
+class TestFallbackConsistency:
+    """Test suite for fallback_consistency reads against a stand-in endpoint."""
+
+    def test_slow_read_falls_back(self, canned_documents):
+        """Test that a read outlasting fallback_timeout is sent again at the fallback level."""
+        container, documents = canned_documents
+        (documents / "slow_item.json").write_text('{"id": "slow_item"}')
+        (documents / "slow_item.delay").write_text("2")
+        diagnostics = []
+        
+        result = container.read_item(
+            item="slow_item", partition_key="slow_item",
+            fallback_consistency="Eventual", fallback_timeout=0.2,
+            response_hook=diagnostics.append,
+        )
+        assert result["id"] == "slow_item"
+        assert diagnostics[-1]["degraded"] is True
+
+    def test_failed_read_does_not_fall_back(self, canned_documents):
+        """Test that errors other than timeouts are raised without a fallback read."""
+        container, _ = canned_documents
+        
+        with pytest.raises(CosmosResourceNotFoundError):
+            container.read_item(
+                item="missing_item", partition_key="missing_item",
+                fallback_consistency="Eventual", fallback_timeout=5,
+            )
+
+
+class TestPartitionKeyTypeCoercion:
+    """Test suite for declared partition key types."""
+
+    @pytest.fixture
+    def numeric_container(self, database, test_container_id):
+        database.create_container(test_container_id, {"paths": ["/num"], "kind": "Hash"})
+        database.get_container_client(test_container_id).create_item(
+            body={"id": "numeric_item", "num": 42}, partition_key=42
+        )
+        yield database.get_container_client(test_container_id, partition_key_type="number")
+        database.delete_container(test_container_id)
+
+    def test_numeric_string_is_coerced(self, numeric_container):
+        """Test that a numeric string finds an item stored under a number."""
+        with pytest.warns(UserWarning, match="coerced"):
+            result = numeric_container.read_item(item="numeric_item", partition_key="42")
+        assert result["num"] == 42
+
+    def test_uncoercible_value_raises(self, numeric_container):
+        """Test that values which cannot be coerced are rejected."""
+        with pytest.raises(ValueError, match="cannot be coerced"):
+            numeric_container.read_item(item="numeric_item", partition_key="forty-two")
+
+    def test_invalid_partition_key_type(self, database):
+        """Test that unknown partition key types are rejected."""
+        with pytest.raises(ValueError):
+            database.get_container_client("any", partition_key_type="bool")
+
+
+class TestUndefinedPartitionKey:
+    """Test suite for items without a partition key value."""
+
+    @pytest.fixture
+    def category_container(self, database, test_container_id):
+        database.create_container(test_container_id, {"paths": ["/category"], "kind": "Hash"})
+        yield database.get_container_client(test_container_id)
+        database.delete_container(test_container_id)
+
+    def test_none_scopes_query_to_undefined_partition(self, category_container):
+        """Test that partition_key=None differs from leaving partition_key out."""
+        category_container.create_item(body={"id": "no_category"}, partition_key=None)
+        category_container.create_item(body={"id": "with_category", "category": "a"})
+        category_container.create_item(body={"id": "empty_category", "category": ""})
+        
+        scoped = category_container.query_items("SELECT * FROM c", partition_key=None)
+        assert [item["id"] for item in scoped] == ["no_category"]
+        
+        cross_partition = category_container.query_items_paged("SELECT * FROM c")
+        assert {item["id"] for item in cross_partition.items} == {"no_category", "with_category", "empty_category"}
+        
+        empty = category_container.query_items("SELECT * FROM c", partition_key="")
+        assert [item["id"] for item in empty] == ["empty_category"]
+
+    def test_read_all_items_spans_partitions(self, category_container):
+        """Test that read_all_items returns items from every partition, including the undefined one."""
+        category_container.create_item(body={"id": "no_category"}, partition_key=None)
+        for i in range(4):
+            category_container.create_item(body={"id": f"all_{i}", "category": f"c{i % 2}"})
+        
+        items = category_container.read_all_items(max_item_count=1)
+        assert sorted(item["id"] for item in items) == ["all_0", "all_1", "all_2", "all_3", "no_category"]
+        
+        scoped = category_container.read_all_items(partition_key="c1")
+        assert sorted(item["id"] for item in scoped) == ["all_1", "all_3"]
+
+    def test_read_item_with_none_partition_key(self, category_container):
+        """Test point reads of an item without a partition key value."""
+        category_container.create_item(body={"id": "no_category"}, partition_key=None)
+        
+        item = category_container.read_item(item="no_category", partition_key=None)
+        assert "category" not in item
+
+    def test_delete_item_with_none_partition_key(self, category_container):
+        """Test that partition_key=None deletes from the undefined partition, not the empty key."""
+        category_container.create_item(body={"id": "no_category"}, partition_key=None)
+        category_container.create_item(body={"id": "no_category", "category": ""})
+        
+        category_container.delete_item(item="no_category", partition_key=None)
+        
+        with pytest.raises(CosmosResourceNotFoundError):
+            category_container.read_item(item="no_category", partition_key=None)
+        assert category_container.read_item(item="no_category", partition_key="")["category"] == ""
+
+    def test_partition_key_read_from_container_path(self, category_container):
+        """Test that writes use the key path, not an id guess, and only guess when asked."""
+        category_container.create_item(body={"id": "path_item", "category": "a"})
+        assert category_container.read_item(item="path_item", partition_key="a")["category"] == "a"
+
+        with pytest.raises(ValueError, match="/category"):
+            category_container.create_item(body={"id": "no_path_item"})
+        assert category_container.resolve_partition_key({"id": "no_path_item"}, partition_key=None) is None
+        assert category_container.resolve_partition_key(
+            {"id": "no_path_item"}, auto_partition_key=True
+        ) == "no_path_item"
+
+
+class TestHierarchicalPartitionKeys:
+    """Test suite for containers with hierarchical partition keys."""
+
+    @pytest.fixture
+    def hierarchical_container(self, database, test_container_id):
+        database.create_container(test_container_id, {"paths": ["/tenantId", "/userId"], "kind": "MultiHash"})
+        yield database.get_container_client(test_container_id)
+        database.delete_container(test_container_id)
+
+    def test_partition_key_extracted_from_body(self, hierarchical_container):
+        """Test that each key component is taken from the body in path order."""
+        hierarchical_container.create_item(body={"id": "h1", "tenantId": "t1", "userId": "u1"})
+        
+        item = hierarchical_container.read_item(item="h1", partition_key=["t1", "u1"])
+        assert item["userId"] == "u1"
+
+    def test_missing_component_names_path(self, hierarchical_container):
+        """Test that a missing key component is reported by path."""
+        with pytest.raises(ValueError, match="/userId"):
+            hierarchical_container.create_item(body={"id": "h2", "tenantId": "t1"})
+
+    def test_resolve_partition_key(self, hierarchical_container):
+        """Test that the key components are resolved without writing the item."""
+        body = {"id": "h3", "tenantId": "t1", "userId": "u1"}
+        assert hierarchical_container.resolve_partition_key(body) == ["t1", "u1"]
+        
+        with pytest.raises(ValueError, match="/userId"):
+            hierarchical_container.resolve_partition_key({"id": "h3", "tenantId": "t1"})
+        with pytest.raises(CosmosResourceNotFoundError):
+            hierarchical_container.read_item(item="h3", partition_key=["t1", "u1"])
+
+    def test_get_item_partition_key(self, hierarchical_container):
+        """Test that a read item reports the key components it is stored under."""
+        hierarchical_container.create_item(body={"id": "h4", "tenantId": "t1", "userId": "u2"})
+        item = hierarchical_container.read_item(item="h4", partition_key=["t1", "u2"])
+        
+        assert hierarchical_container.get_item_partition_key(item) == ["t1", "u2"]
+        assert hierarchical_container.get_item_partition_key({"id": "h5", "tenantId": "t1"}) == ["t1", None]
+
+    def test_scalar_partition_key_rejected(self, hierarchical_container):
+        """Test that a single value is rejected for a MultiHash container once its key kind is known."""
+        hierarchical_container.create_item(body={"id": "h1", "tenantId": "t1", "userId": "u1"})
+        
+        with pytest.raises(ValueError, match="MultiHash"):
+            hierarchical_container.read_item(item="h1", partition_key="t1")
+
+    def test_too_many_components_rejected(self, hierarchical_container):
+        """Test that more components than key paths are rejected."""
+        with pytest.raises(ValueError, match="2 partition key paths"):
+            hierarchical_container.read_item(item="h1", partition_key=["t1", "u1", "x"])
+
+    def test_mixed_type_components(self, database, test_container_id):
+        """Test that string and number components can be combined."""
+        database.create_container(test_container_id, {"paths": ["/tenantId", "/version"], "kind": "MultiHash"})
+        try:
+            container = database.get_container_client(test_container_id)
+            container.create_item(body={"id": "m1", "tenantId": "t1", "version": 42})
+            
+            assert container.read_item(item="m1", partition_key=["t1", 42])["version"] == 42
+            results = list(container.query_items("SELECT * FROM c", partition_key=["t1", 42]))
+            assert [item["id"] for item in results] == ["m1"]
+            container.delete_item(item="m1", partition_key=["t1", 42])
+            with pytest.raises(CosmosResourceNotFoundError):
+                container.read_item(item="m1", partition_key=["t1", 42])
+        finally:
+            database.delete_container(test_container_id)
+
+    def test_query_scoped_to_components(self, hierarchical_container):
+        """Test that a query with a key list only returns items in that logical partition."""
+        hierarchical_container.create_item(body={"id": "q1", "tenantId": "t1", "userId": "u1"})
+        hierarchical_container.create_item(body={"id": "q2", "tenantId": "t1", "userId": "u2"})
+        
+        results = list(hierarchical_container.query_items("SELECT * FROM c", partition_key=["t1", "u2"]))
+        assert [item["id"] for item in results] == ["q2"]
+
+    def test_empty_partition_key_list_rejected(self, hierarchical_container):
+        """Test that an empty list of key components is rejected."""
+        with pytest.raises(ValueError, match="empty list"):
+            hierarchical_container.read_item(item="h1", partition_key=[])
+        with pytest.raises(ValueError, match="empty list"):
+            hierarchical_container.delete_item(item="h1", partition_key=[])
+
+    def test_list_partition_key_rejected_for_hash(self, container):
+        """Test that a multi-component key is rejected for a Hash container."""
+        with pytest.raises(ValueError, match="Hash"):
+            container.read_item(item="item1", partition_key=["item1", "other"])
+
+
 class TestContainerProxy:
     """Test suite for ContainerProxy."""
 
@@ -253,6 +1458,11 @@ class TestContainerProxy:
         assert result is not None
         assert result.get("id") == container.id
 
+    def test_wait_until_ready(self, container):
+        """A freshly created container finishes indexing well within the timeout."""
+        elapsed = container.wait_until_ready(timeout=30)
+        assert 0 <= elapsed < 30
+
     def test_delete_container(self, database, test_container_id):
         """Test deleting a container through the container proxy."""
         partition_key = {"paths": ["/id"], "kind": "Hash"}
@@ -7,6 +7,10 @@ from azure.cosmos.exceptions import (
     CosmosResourceNotFoundError,
     CosmosResourceExistsError,
     CosmosAccessConditionFailedError,
+    CosmosServiceUnavailableError,
+    CosmosInvalidContinuationTokenError,
+    CosmosResponseTooLargeError,
+    CosmosDeserializationError,
 )
 
 
@@ -19,6 +23,48 @@ class TestExceptions:
         assert issubclass(CosmosResourceNotFoundError, CosmosHttpResponseError)
         assert issubclass(CosmosResourceExistsError, CosmosHttpResponseError)
         assert issubclass(CosmosAccessConditionFailedError, CosmosHttpResponseError)
+        assert issubclass(CosmosServiceUnavailableError, CosmosHttpResponseError)
+        assert issubclass(CosmosInvalidContinuationTokenError, CosmosHttpResponseError)
+        assert issubclass(CosmosResponseTooLargeError, CosmosHttpResponseError)
+        assert issubclass(CosmosDeserializationError, ValueError)
+
+    def test_error_carries_correlation_and_activity_ids(self, container):
+        """Test that errors expose the caller's correlation id and the service activity id."""
+        with pytest.raises(CosmosResourceNotFoundError) as exc_info:
+            container.read_item(
+                item="nonexistent_item", partition_key="nonexistent_item", correlation_id="trace-123"
+            )
+        
+        assert exc_info.value.correlation_id == "trace-123"
+        assert exc_info.value.activity_id
+
+    def test_error_carries_request_charge(self, container):
+        """Test that errors expose the RU charge and retry-after of the failed request."""
+        with pytest.raises(CosmosResourceNotFoundError) as exc_info:
+            container.read_item(item="nonexistent_item", partition_key="nonexistent_item")
+        
+        assert exc_info.value.request_charge is not None
+        assert exc_info.value.request_charge >= 0
+        assert exc_info.value.retry_after_ms is None
+
+    def test_errors_carry_status_code_and_message(self, container):
+        """Test that errors expose the response's status code and their message."""
+        container.create_item(body={"id": "status_item"})
+        _, etag = container.read_item_with_etag(item="status_item", partition_key="status_item")
+        container.upsert_item(body={"id": "status_item", "changed": True})
+        
+        failures = [
+            (CosmosResourceNotFoundError, 404, lambda: container.read_item(item="missing", partition_key="missing")),
+            (CosmosResourceExistsError, 409, lambda: container.create_item(body={"id": "status_item"})),
+            (CosmosAccessConditionFailedError, 412, lambda: container.replace_item(
+                item="status_item", body={"id": "status_item"}, etag=etag
+            )),
+        ]
+        for error_type, status_code, operation in failures:
+            with pytest.raises(error_type) as exc_info:
+                operation()
+            assert exc_info.value.status_code == status_code
+            assert exc_info.value.message == str(exc_info.value)
 
     def test_invalid_credentials_raises_error(self, account_url):
         """Test that invalid credentials raise an error."""
@@ -216,6 +262,32 @@ class TestEdgeCases:
         read_result = container.read_item(item="nested_item", partition_key="nested_item")
         assert read_result["level1"]["level2"]["level3"]["level4"]["value"] == "deep_value"
 
+    def test_too_deeply_nested_json_names_path(self, canned_documents):
+        """Test that reading a document nested beyond the parser limit names the path."""
+        container, documents = canned_documents
+        # The service refuses to store documents this deep, so the response is canned
+        nested = '{"value": "deep_value"}'
+        for _ in range(200):
+            nested = '{"child": ' + nested + '}'
+        (documents / "too_deep_item.json").write_text('{"id": "too_deep_item", "root": ' + nested + '}')
+        
+        with pytest.raises(CosmosDeserializationError) as exc_info:
+            container.read_item(item="too_deep_item", partition_key="too_deep_item")
+        assert exc_info.value.path.startswith("$.root.child.child")
+        assert "128 levels" in exc_info.value.reason
+
+    def test_out_of_range_number_names_path(self, canned_documents):
+        """Test that reading a number beyond the range of a float names the path."""
+        container, documents = canned_documents
+        (documents / "huge_number_item.json").write_text(
+            '{"id": "huge_number_item", "stats": {"values": [1, 1e400]}}'
+        )
+        
+        with pytest.raises(CosmosDeserializationError) as exc_info:
+            container.read_item(item="huge_number_item", partition_key="huge_number_item")
+        assert exc_info.value.path == "$.stats.values[1]"
+        assert "out of range" in exc_info.value.reason
+
     def test_numeric_partition_keys(self, database, test_container_id):
         """Test using numeric partition keys."""
         # Create container with numeric partition key path
@@ -0,0 +1,43 @@
+"""Tests for session token handling."""
+
+import pytest
+from azure.cosmos import CosmosClient, merge_session_tokens
+
+
+class TestMergeSessionTokens:
+    """Test suite for merging session tokens."""
+
+    def test_merge_overlapping_ranges_keeps_max_lsn(self):
+        """Test that overlapping ranges keep the highest LSN and others are kept as is."""
+        a = "0:1#100#1=20,1:1#50#1=5"
+        b = "1:1#70#1=3,2:1#10"
+        
+        assert merge_session_tokens(a, b) == "0:1#100#1=20,1:1#70#1=5,2:1#10"
+
+    def test_merge_prefers_newer_range_version(self):
+        """Test that a token of a newer range version replaces an older one."""
+        assert merge_session_tokens("0:1#500", "0:2#100") == "0:2#100"
+
+    def test_merge_simple_tokens(self):
+        """Test merging tokens in the simple (non-vector) format."""
+        assert merge_session_tokens("0:120,1:40", "0:100,1:90") == "0:120,1:90"
+
+    def test_invalid_token_raises(self):
+        """Test that malformed tokens are rejected."""
+        with pytest.raises(ValueError):
+            merge_session_tokens("not-a-token")
+
+    def test_read_with_session_token_from_another_client(
+        self, account_url, account_key, database, container
+    ):
+        """Test reading another client's write with its session token."""
+        tokens = []
+        container.create_item(
+            body={"id": "session_item"},
+            response_hook=lambda diagnostics: tokens.append(diagnostics["session_token"]),
+        )
+        
+        reader = CosmosClient(account_url, credential=account_key, initial_session_token=tokens[0])
+        reader_container = reader.get_database_client(database.id).get_container_client(container.id)
+        item = reader_container.read_item(item="session_item", partition_key="session_item")
+        assert item["id"] == "session_item"